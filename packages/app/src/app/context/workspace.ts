@@ -1745,10 +1745,11 @@ export function createWorkspaceStore(options: {
       version: existing?.version ?? 1,
       workspace: existing?.workspace ?? null,
       authorizedRoots: nextRoots,
+      updatedAt: existing?.updatedAt ?? null,
     };
 
     await workspaceOpenworkWrite({ workspacePath: root, config: cfg });
-    setWorkspaceConfig(cfg);
+    setWorkspaceConfig(await workspaceOpenworkRead({ workspacePath: root }));
   }
 
   async function addAuthorizedDir() {
@@ -250,6 +250,18 @@ export async function workspaceImportConfig(input: {
   });
 }
 
+export type RemoteReachability = {
+  reachable: boolean;
+  url: string;
+  status: number | null;
+  latencyMs: number;
+  error: string | null;
+};
+
+export async function workspaceCheckRemote(workspaceId: string): Promise<RemoteReachability> {
+  return invoke<RemoteReachability>("workspace_check_remote", { workspaceId });
+}
+
 export type OpencodeCommandDraft = {
   name: string;
   description?: string;
@@ -267,6 +279,7 @@ export type WorkspaceOpenworkConfig = {
     preset?: string | null;
   } | null;
   authorizedRoots: string[];
+  updatedAt?: number | null;
 };
 
 export async function workspaceOpenworkRead(input: {
@@ -347,6 +360,22 @@ export async function openworkServerInfo(): Promise<OpenworkServerInfo> {
   return invoke<OpenworkServerInfo>("openwork_server_info");
 }
 
+export async function openworkServerStart(workspacePaths: string[] = []): Promise<OpenworkServerInfo> {
+  return invoke<OpenworkServerInfo>("openwork_server_start", { workspacePaths });
+}
+
+export async function openworkServerStop(): Promise<OpenworkServerInfo> {
+  return invoke<OpenworkServerInfo>("openwork_server_stop");
+}
+
+export async function openworkServerRotateTokens(): Promise<OpenworkServerInfo> {
+  return invoke<OpenworkServerInfo>("openwork_server_rotate_tokens");
+}
+
+export async function openworkServerQr(kind?: "connect" | "lan" | "mdns"): Promise<string> {
+  return invoke<string>("openwork_server_qr", { kind: kind ?? null });
+}
+
 export async function engineInfo(): Promise<EngineInfo> {
   return invoke<EngineInfo>("engine_info");
 }
@@ -452,6 +481,24 @@ export async function opkgInstall(projectDir: string, pkg: string): Promise<Exec
   return invoke<ExecResult>("opkg_install", { projectDir, package: pkg });
 }
 
+export async function opkgUninstall(projectDir: string, pkg: string): Promise<ExecResult> {
+  return invoke<ExecResult>("opkg_uninstall", { projectDir, package: pkg });
+}
+
+export type OpkgPackage = {
+  name: string;
+  version: string | null;
+};
+
+export type OpkgPackageList = {
+  packages: OpkgPackage[];
+  raw: string | null;
+};
+
+export async function opkgList(projectDir: string): Promise<OpkgPackageList> {
+  return invoke<OpkgPackageList>("opkg_list", { projectDir });
+}
+
 export async function importSkill(
   projectDir: string,
   sourceDir: string,
@@ -464,25 +511,46 @@ export async function importSkill(
   });
 }
 
+export async function importSkillFromGit(
+  projectDir: string,
+  repoUrl: string,
+  options?: { subdir?: string; overwrite?: boolean },
+): Promise<ExecResult> {
+  return invoke<ExecResult>("import_skill_from_git", {
+    projectDir,
+    repoUrl,
+    subdir: options?.subdir ?? null,
+    overwrite: options?.overwrite ?? false,
+  });
+}
+
+export type SkillFile = {
+  relativePath: string;
+  content: string;
+};
+
 export async function installSkillTemplate(
   projectDir: string,
   name: string,
   content: string,
-  options?: { overwrite?: boolean },
+  options?: { overwrite?: boolean; files?: SkillFile[] },
 ): Promise<ExecResult> {
   return invoke<ExecResult>("install_skill_template", {
     projectDir,
     name,
     content,
     overwrite: options?.overwrite ?? false,
+    files: options?.files ?? null,
   });
 }
 
 export type LocalSkillCard = {
   name: string;
+  displayName?: string | null;
   path: string;
   description?: string;
   trigger?: string;
+  enabled: boolean;
 };
 
 export async function listLocalSkills(projectDir: string): Promise<LocalSkillCard[]> {
@@ -493,6 +561,24 @@ export async function uninstallSkill(projectDir: string, name: string): Promise<
   return invoke<ExecResult>("uninstall_skill", { projectDir, name });
 }
 
+export type SkillContent = {
+  name: string;
+  path: string;
+  content: string;
+};
+
+export async function readSkill(projectDir: string, name: string): Promise<SkillContent> {
+  return invoke<SkillContent>("skill_read", { projectDir, name });
+}
+
+export async function setSkillEnabled(
+  projectDir: string,
+  name: string,
+  enabled: boolean,
+): Promise<ExecResult> {
+  return invoke<ExecResult>("skill_set_enabled", { projectDir, name, enabled });
+}
+
 export type OpencodeConfigFile = {
   path: string;
   exists: boolean;
@@ -510,6 +596,35 @@ export async function updaterEnvironment(): Promise<UpdaterEnvironment> {
   return invoke<UpdaterEnvironment>("updater_environment");
 }
 
+export type UpdateCheck = {
+  supported: boolean;
+  reason: string | null;
+  available: boolean;
+  currentVersion: string;
+  version: string | null;
+  notes: string | null;
+  pubDate: string | null;
+};
+
+export async function updaterCheckForUpdate(): Promise<UpdateCheck> {
+  return invoke<UpdateCheck>("updater_check_for_update");
+}
+
+export type UpdateInstall = {
+  installed: boolean;
+  version: string | null;
+  relaunchRequired: boolean;
+};
+
+export type UpdateProgressEvent = {
+  downloaded: number;
+  total: number | null;
+};
+
+export async function updaterDownloadAndInstall(): Promise<UpdateInstall> {
+  return invoke<UpdateInstall>("updater_download_and_install");
+}
+
 export async function readOpencodeConfig(
   scope: "project" | "global",
   projectDir: string,
@@ -521,8 +636,64 @@ export async function writeOpencodeConfig(
   scope: "project" | "global",
   projectDir: string,
   content: string,
+  options?: { force?: boolean },
+): Promise<ExecResult> {
+  return invoke<ExecResult>("write_opencode_config", {
+    scope,
+    projectDir,
+    content,
+    force: options?.force ?? false,
+  });
+}
+
+export type ConfigBackup = {
+  id: string;
+  createdAt: number;
+  size: number;
+};
+
+export async function opencodeConfigBackup(
+  scope: "project" | "global",
+  projectDir: string,
+): Promise<ConfigBackup> {
+  return invoke<ConfigBackup>("opencode_config_backup", { scope, projectDir });
+}
+
+export async function opencodeConfigListBackups(
+  scope: "project" | "global",
+  projectDir: string,
+): Promise<ConfigBackup[]> {
+  return invoke<ConfigBackup[]>("opencode_config_list_backups", { scope, projectDir });
+}
+
+export async function opencodeConfigRestore(
+  scope: "project" | "global",
+  projectDir: string,
+  backupId: string,
 ): Promise<ExecResult> {
-  return invoke<ExecResult>("write_opencode_config", { scope, projectDir, content });
+  return invoke<ExecResult>("opencode_config_restore", { scope, projectDir, backupId });
+}
+
+export type ConfigIssue = {
+  message: string;
+  key: string | null;
+  line: number | null;
+  column: number | null;
+};
+
+export type ConfigContentValidation = {
+  valid: boolean;
+  errors: ConfigIssue[];
+};
+
+export async function validateOpencodeConfig(
+  content: string,
+  options?: { checkSchema?: boolean },
+): Promise<ConfigContentValidation> {
+  return invoke<ConfigContentValidation>("validate_opencode_config", {
+    content,
+    checkSchema: options?.checkSchema ?? false,
+  });
 }
 
 export async function resetOpenworkState(mode: "onboarding" | "all"): Promise<void> {
@@ -0,0 +1,316 @@
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+use tauri::AppHandle;
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::commands::workspace::is_secret_name;
+use crate::config::profiles_dir;
+use crate::fs::write_atomic;
+use crate::types::WorkspaceState;
+use crate::utils::now_ms;
+use crate::workspace::state::openwork_state_paths;
+
+const MANIFEST_NAME: &str = "manifest.json";
+const MANIFEST_KIND: &str = "openwork-app-data";
+const DATA_PREFIX: &str = "data/";
+const PROFILES_PREFIX: &str = "profiles/";
+const WORKSPACE_STATE_FILE: &str = "openwork-workspaces.json";
+const MAX_IMPORT_ENTRIES: usize = 10_000;
+const MAX_IMPORT_BYTES: u64 = 500 * 1024 * 1024;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDataExportSummary {
+    pub output_path: String,
+    pub included: usize,
+    pub excluded: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDataImportResult {
+    pub restored: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// Caches, logs, runtime files and server credentials are machine-specific and stay
+/// behind.
+fn exclude_data_entry(relative: &str) -> bool {
+    let top = relative.split('/').next().unwrap_or_default();
+    let name = relative.rsplit('/').next().unwrap_or_default();
+    top == "logs"
+        || top == "cache"
+        || top.starts_with("openwork-server")
+        || top == "opencode-engine.pid"
+        || name.ends_with(".bak")
+        || name.ends_with(".tmp")
+        || is_secret_name(name)
+}
+
+fn collect_files(
+    root: &Path,
+    prefix: &str,
+    exclude: fn(&str) -> bool,
+    included: &mut Vec<(PathBuf, String)>,
+    excluded: &mut Vec<String>,
+) {
+    if !root.is_dir() {
+        return;
+    }
+    for entry in WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        if exclude(&relative) {
+            excluded.push(format!("{prefix}{relative}"));
+            continue;
+        }
+        included.push((entry.path().to_path_buf(), format!("{prefix}{relative}")));
+    }
+}
+
+/// Zips OpenWork's app data (workspace list, preferences, starter workspace) and config
+/// profiles for moving to another machine.
+#[tauri::command]
+pub fn export_app_data(app: AppHandle, dest_zip: String) -> Result<AppDataExportSummary, String> {
+    let dest_zip = dest_zip.trim().to_string();
+    if dest_zip.is_empty() {
+        return Err("destZip is required".to_string());
+    }
+
+    let (data_dir, _) = openwork_state_paths(&app)?;
+    let mut included = Vec::new();
+    let mut excluded = Vec::new();
+    collect_files(
+        &data_dir,
+        DATA_PREFIX,
+        exclude_data_entry,
+        &mut included,
+        &mut excluded,
+    );
+    collect_files(
+        &profiles_dir()?,
+        PROFILES_PREFIX,
+        exclude_profile_entry,
+        &mut included,
+        &mut excluded,
+    );
+    if included.is_empty() {
+        return Err("No OpenWork data found to export".to_string());
+    }
+
+    let output_path = PathBuf::from(&dest_zip);
+    if output_path.starts_with(&data_dir) {
+        return Err("destZip must be outside the OpenWork data directory".to_string());
+    }
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create export folder {}: {e}", parent.display()))?;
+    }
+
+    let file = fs::File::create(&output_path)
+        .map_err(|e| format!("Failed to create {}: {e}", output_path.display()))?;
+    let mut zip = ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut names = Vec::new();
+    for (src, name) in included {
+        let buffer =
+            fs::read(&src).map_err(|e| format!("Failed to read {}: {e}", src.display()))?;
+        zip.start_file(name.clone(), options)
+            .map_err(|e| format!("Failed to add {name}: {e}"))?;
+        zip.write_all(&buffer)
+            .map_err(|e| format!("Failed to write {}: {e}", src.display()))?;
+        names.push(name);
+    }
+
+    let manifest = serde_json::json!({
+        "kind": MANIFEST_KIND,
+        "version": 1,
+        "createdAtMs": now_ms(),
+        "included": names,
+        "excluded": excluded,
+    });
+    let manifest_json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    zip.start_file(MANIFEST_NAME, options)
+        .map_err(|e| format!("Failed to add manifest: {e}"))?;
+    zip.write_all(manifest_json.as_bytes())
+        .map_err(|e| format!("Failed to write manifest: {e}"))?;
+    zip.finish()
+        .map_err(|e| format!("Failed to finalize export: {e}"))?;
+
+    Ok(AppDataExportSummary {
+        output_path: output_path.to_string_lossy().to_string(),
+        included: names.len(),
+        excluded,
+    })
+}
+
+fn validate_manifest(archive: &mut ZipArchive<fs::File>) -> Result<(), String> {
+    let mut entry = archive.by_name(MANIFEST_NAME).map_err(|_| {
+        "Archive is not an OpenWork data backup (missing manifest.json)".to_string()
+    })?;
+    let mut raw = String::new();
+    entry
+        .read_to_string(&mut raw)
+        .map_err(|e| format!("Failed to read manifest: {e}"))?;
+    let manifest: serde_json::Value =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid manifest: {e}"))?;
+    if manifest.get("kind").and_then(|kind| kind.as_str()) != Some(MANIFEST_KIND) {
+        return Err("Archive is not an OpenWork data backup".to_string());
+    }
+    Ok(())
+}
+
+/// Existing workspaces win on id clashes; imported ones are appended.
+fn merge_workspace_state(existing: &Path, incoming: &[u8]) -> Result<Vec<u8>, String> {
+    let raw = fs::read_to_string(existing)
+        .map_err(|e| format!("Failed to read {}: {e}", existing.display()))?;
+    let mut state: WorkspaceState = serde_json::from_str(&raw)
+        .map_err(|e| format!("Failed to parse {}: {e}", existing.display()))?;
+    let imported: WorkspaceState = serde_json::from_slice(incoming)
+        .map_err(|e| format!("Failed to parse imported {WORKSPACE_STATE_FILE}: {e}"))?;
+    for workspace in imported.workspaces {
+        if !state.workspaces.iter().any(|w| w.id == workspace.id) {
+            state.workspaces.push(workspace);
+        }
+    }
+    serde_json::to_vec_pretty(&state).map_err(|e| e.to_string())
+}
+
+fn exclude_profile_entry(relative: &str) -> bool {
+    is_secret_name(relative.rsplit('/').next().unwrap_or_default())
+}
+
+/// Restores a backup made by `export_app_data`. With `merge`, existing files are kept
+/// and workspace lists are combined; otherwise archived files replace local ones.
+/// Replacing or merging into an existing workspace list requires `confirm_overwrite`,
+/// and entries the export leaves out are refused. Restart the app afterwards so
+/// everything picks up the restored state.
+#[tauri::command]
+pub fn import_app_data(
+    app: AppHandle,
+    src_zip: String,
+    merge: bool,
+    confirm_overwrite: Option<bool>,
+) -> Result<AppDataImportResult, String> {
+    let src_zip = src_zip.trim().to_string();
+    if src_zip.is_empty() {
+        return Err("srcZip is required".to_string());
+    }
+
+    let (data_dir, state_path) = openwork_state_paths(&app)?;
+    if state_path.exists() && !confirm_overwrite.unwrap_or(false) {
+        return Err(
+            "OpenWork already has workspace state; pass confirmOverwrite to replace or merge it"
+                .to_string(),
+        );
+    }
+    let profiles = profiles_dir()?;
+
+    let file = fs::File::open(&src_zip).map_err(|e| format!("Failed to open {src_zip}: {e}"))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Failed to read archive: {e}"))?;
+    validate_manifest(&mut archive)?;
+    if archive.len() > MAX_IMPORT_ENTRIES {
+        return Err(format!(
+            "Archive has {} entries; backups may contain at most {MAX_IMPORT_ENTRIES}",
+            archive.len()
+        ));
+    }
+    let too_large = || {
+        format!(
+            "Archive expands to more than {} MB",
+            MAX_IMPORT_BYTES / (1024 * 1024)
+        )
+    };
+
+    // Validate every entry before writing anything.
+    let mut targets = Vec::new();
+    let mut declared: u64 = 0;
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        declared = declared.saturating_add(entry.size());
+        if declared > MAX_IMPORT_BYTES {
+            return Err(too_large());
+        }
+        let name = entry.name().to_string();
+        if name == MANIFEST_NAME || name.ends_with('/') {
+            continue;
+        }
+        let unsafe_path = Path::new(&name).components().any(|component| {
+            matches!(
+                component,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_)
+            )
+        });
+        if unsafe_path {
+            return Err("Archive contains an unsafe path".to_string());
+        }
+        let (root, relative, exclude): (&Path, &str, fn(&str) -> bool) =
+            if let Some(relative) = name.strip_prefix(DATA_PREFIX) {
+                (&data_dir, relative, exclude_data_entry)
+            } else if let Some(relative) = name.strip_prefix(PROFILES_PREFIX) {
+                (&profiles, relative, exclude_profile_entry)
+            } else {
+                return Err(format!("Unexpected archive entry: {name}"));
+            };
+        // The export never includes these, so an archive that does was not made by it.
+        if exclude(relative) {
+            return Err(format!("Archive contains an excluded entry: {name}"));
+        }
+        let target = root.join(relative);
+        targets.push((i, name, target));
+    }
+
+    let mut restored = Vec::new();
+    let mut skipped = Vec::new();
+    let mut remaining = MAX_IMPORT_BYTES;
+    for (index, name, target) in targets {
+        let entry = archive.by_index(index).map_err(|e| e.to_string())?;
+        let mut buffer = Vec::new();
+        // Declared sizes can lie, so never read past the remaining budget.
+        entry
+            .take(remaining + 1)
+            .read_to_end(&mut buffer)
+            .map_err(|e| format!("Failed to read archive entry: {e}"))?;
+        if buffer.len() as u64 > remaining {
+            return Err(too_large());
+        }
+        remaining -= buffer.len() as u64;
+
+        if merge && target.exists() {
+            if target != state_path {
+                skipped.push(name);
+                continue;
+            }
+            buffer = merge_workspace_state(&state_path, &buffer)?;
+        }
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        write_atomic(&target, &buffer)?;
+        restored.push(name);
+    }
+
+    println!(
+        "[app-data] restored {} files ({} skipped)",
+        restored.len(),
+        skipped.len()
+    );
+    Ok(AppDataImportResult { restored, skipped })
+}
@@ -1,5 +1,13 @@
-use crate::config::{read_opencode_config as read_inner, write_opencode_config as write_inner};
-use crate::types::{ExecResult, OpencodeConfigFile};
+use crate::config::{
+    apply_config_profile, backup_opencode_config, check_opencode_config_content,
+    list_config_profiles, list_opencode_config_backups, read_opencode_config as read_inner,
+    restore_opencode_config, save_config_profile, validate_opencode_config as validate_inner,
+    write_opencode_config as write_inner,
+};
+use crate::types::{
+    ConfigBackup, ConfigContentValidation, ConfigProfile, ConfigValidation, ExecResult,
+    OpencodeConfigFile,
+};
 
 #[tauri::command]
 pub fn read_opencode_config(
@@ -9,11 +17,83 @@ pub fn read_opencode_config(
     read_inner(scope.trim(), &project_dir)
 }
 
+/// Refuses content that does not parse unless `force` is set, so the editor cannot
+/// leave the engine with a config it fails to load.
 #[tauri::command]
 pub fn write_opencode_config(
     scope: String,
     project_dir: String,
     content: String,
+    force: Option<bool>,
 ) -> Result<ExecResult, String> {
+    if !force.unwrap_or(false) {
+        if let Some(issue) = check_opencode_config_content(&content, false)
+            .errors
+            .into_iter()
+            .next()
+        {
+            let position = match (issue.line, issue.column) {
+                (Some(line), Some(column)) => format!(" (line {line}, column {column})"),
+                _ => String::new(),
+            };
+            return Err(format!("Invalid config{position}: {}", issue.message));
+        }
+    }
     write_inner(scope.trim(), &project_dir, &content)
 }
+
+#[tauri::command]
+pub fn validate_opencode_config(
+    content: String,
+    check_schema: Option<bool>,
+) -> ConfigContentValidation {
+    check_opencode_config_content(&content, check_schema.unwrap_or(false))
+}
+
+#[tauri::command]
+pub fn config_validate(project_dir: String) -> Result<ConfigValidation, String> {
+    validate_inner("project", &project_dir)
+}
+
+#[tauri::command]
+pub fn config_save_profile(
+    project_dir: String,
+    profile_name: String,
+) -> Result<ExecResult, String> {
+    save_config_profile(&project_dir, &profile_name)
+}
+
+#[tauri::command]
+pub fn config_apply_profile(
+    project_dir: String,
+    profile_name: String,
+) -> Result<ExecResult, String> {
+    apply_config_profile(&project_dir, &profile_name)
+}
+
+#[tauri::command]
+pub fn config_list_profiles() -> Result<Vec<ConfigProfile>, String> {
+    list_config_profiles()
+}
+
+#[tauri::command]
+pub fn opencode_config_backup(scope: String, project_dir: String) -> Result<ConfigBackup, String> {
+    backup_opencode_config(&scope, &project_dir)
+}
+
+#[tauri::command]
+pub fn opencode_config_list_backups(
+    scope: String,
+    project_dir: String,
+) -> Result<Vec<ConfigBackup>, String> {
+    list_opencode_config_backups(&scope, &project_dir)
+}
+
+#[tauri::command]
+pub fn opencode_config_restore(
+    scope: String,
+    project_dir: String,
+    backup_id: String,
+) -> Result<ExecResult, String> {
+    restore_opencode_config(&scope, &project_dir, &backup_id)
+}
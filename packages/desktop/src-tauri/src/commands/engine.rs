@@ -1,21 +1,43 @@
 use tauri::{AppHandle, Manager, State};
 
 use crate::config::{read_opencode_config, write_opencode_config};
+use crate::engine::auth_watch::AuthWatchState;
 use crate::engine::doctor::{
     opencode_serve_help, opencode_version, resolve_engine_path, resolve_sidecar_candidate,
+    sidecar_arch_mismatch, version_meets_minimum, MIN_OPENCODE_VERSION,
 };
 use crate::engine::manager::EngineManager;
-use crate::engine::spawn::{find_free_port, spawn_engine};
+use crate::engine::paths::{
+    all_opencode_paths, resolve_opencode_executable, set_preferred_opencode_binary,
+};
+use crate::engine::pidfile::{
+    engine_pidfile_path, reap_stale_engine, remove_engine_pidfile, write_engine_pidfile,
+};
+use crate::engine::spawn::{
+    describe_engine_command, engine_ready_timeout, engine_responding, find_free_port, spawn_engine,
+};
+use crate::install::InstallManager;
 use crate::commands::owpenbot::owpenbot_start;
 use crate::openwrk::{self, OpenwrkSpawnOptions};
 use crate::openwrk::manager::OpenwrkManager;
 use crate::openwork_server::{manager::OpenworkServerManager, resolve_connect_url, start_openwork_server};
 use crate::owpenbot::manager::OwpenbotManager;
 use crate::owpenbot::spawn::resolve_owpenbot_health_port;
-use crate::types::{EngineDoctorResult, EngineInfo, EngineRuntime, ExecResult};
-use crate::utils::truncate_output;
+use crate::preferences::{
+    clamp_log_buffer_chars, load_preferences, load_preferences_or_default, log_buffer_chars,
+    save_preferences,
+};
+use crate::types::{
+    CrashSnapshot, EngineCommand, EngineConnectInfo, EngineDoctorResult, EngineInfo, EngineMetrics,
+    EngineRuntime, ExecResult, InstallOutcome, OpencodeCandidate, OpenwrkStatus, ProcessStat,
+    WorkspaceInfo, WorkspaceType,
+};
+use crate::utils::{compact_output, now_ms, redact_secrets, truncate_output};
+use crate::workspace::scope::resolve_authorized_dir;
+use crate::workspace::state::load_workspace_state;
 use serde_json::json;
-use tauri_plugin_shell::process::CommandEvent;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
 use uuid::Uuid;
 
 #[derive(Default)]
@@ -26,27 +48,44 @@ struct OutputState {
     exit_code: Option<i32>,
 }
 
+fn current_openwrk_status(openwrk_manager: &OpenwrkManager) -> OpenwrkStatus {
+    let (data_dir, daemon_base_url, last_stderr) = openwrk_manager
+        .inner
+        .lock()
+        .map(|state| {
+            (
+                state.data_dir.clone(),
+                state.daemon_base_url.clone(),
+                state.last_stderr.clone(),
+            )
+        })
+        .unwrap_or_default();
+    openwrk::resolve_openwrk_status(
+        &data_dir.unwrap_or_else(openwrk::resolve_openwrk_data_dir),
+        daemon_base_url.as_deref(),
+        last_stderr,
+    )
+}
+
 #[tauri::command]
 pub fn engine_info(manager: State<EngineManager>, openwrk_manager: State<OpenwrkManager>) -> EngineInfo {
     let mut state = manager.inner.lock().expect("engine mutex poisoned");
+    // Re-adopt, once, a daemon left running by `engine_stop(keep_daemon)`. Health is
+    // checked rather than our child handle, so attached daemons are picked up too.
+    if state.daemon_kept {
+        state.daemon_kept = false;
+        if current_openwrk_status(&openwrk_manager).running {
+            state.runtime = EngineRuntime::Openwrk;
+            state.started_at = Some(now_ms());
+        }
+    }
     if state.runtime == EngineRuntime::Openwrk {
-        let data_dir = openwrk_manager
+        let (last_stdout, last_stderr) = openwrk_manager
             .inner
             .lock()
-            .ok()
-            .and_then(|state| state.data_dir.clone())
-            .unwrap_or_else(openwrk::resolve_openwrk_data_dir);
-        let last_stdout = openwrk_manager
-            .inner
-            .lock()
-            .ok()
-            .and_then(|state| state.last_stdout.clone());
-        let last_stderr = openwrk_manager
-            .inner
-            .lock()
-            .ok()
-            .and_then(|state| state.last_stderr.clone());
-        let status = openwrk::resolve_openwrk_status(&data_dir, last_stderr.clone());
+            .map(|state| (state.last_stdout.clone(), state.last_stderr.clone()))
+            .unwrap_or_default();
+        let status = current_openwrk_status(&openwrk_manager);
         let opencode = status.opencode.clone();
         let base_url = opencode
             .as_ref()
@@ -74,30 +113,259 @@ pub fn engine_info(manager: State<EngineManager>, openwrk_manager: State<Openwrk
     EngineManager::snapshot_locked(&mut state)
 }
 
+fn live_child_pid(child: Option<&CommandChild>, exited: bool) -> Option<u32> {
+    child.filter(|_| !exited).map(CommandChild::pid)
+}
+
+/// CPU and memory for each tracked child (engine, openwrk, OpenWork server, owpenbot).
+/// `cpu_percent` is relative to one core, so busy multi-threaded processes can exceed
+/// 100. Children that have exited are left out.
+#[tauri::command]
+pub async fn process_stats(
+    manager: State<'_, EngineManager>,
+    openwrk_manager: State<'_, OpenwrkManager>,
+    openwork_manager: State<'_, OpenworkServerManager>,
+    owpenbot_manager: State<'_, OwpenbotManager>,
+) -> Result<Vec<ProcessStat>, String> {
+    let engine_pid = manager
+        .inner
+        .lock()
+        .ok()
+        .and_then(|state| live_child_pid(state.child.as_ref(), state.child_exited));
+    let openwrk_pid = openwrk_manager
+        .inner
+        .lock()
+        .ok()
+        .and_then(|state| live_child_pid(state.child.as_ref(), state.child_exited));
+    let openwork_pid = openwork_manager
+        .inner
+        .lock()
+        .ok()
+        .and_then(|state| live_child_pid(state.child.as_ref(), state.child_exited));
+    let owpenbot_pid = owpenbot_manager
+        .inner
+        .lock()
+        .ok()
+        .and_then(|state| live_child_pid(state.child.as_ref(), state.child_exited));
+    let tracked: Vec<(&'static str, u32)> = [
+        ("engine", engine_pid),
+        ("openwrk", openwrk_pid),
+        ("openwork-server", openwork_pid),
+        ("owpenbot", owpenbot_pid),
+    ]
+    .into_iter()
+    .filter_map(|(name, pid)| Some((name, pid?)))
+    .collect();
+    if tracked.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let pids: Vec<Pid> = tracked.iter().map(|(_, pid)| Pid::from_u32(*pid)).collect();
+        let refresh = ProcessRefreshKind::nothing().with_cpu().with_memory();
+        let mut system = System::new();
+        // CPU usage is a delta between two samples.
+        system.refresh_processes_specifics(ProcessesToUpdate::Some(&pids), true, refresh);
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        system.refresh_processes_specifics(ProcessesToUpdate::Some(&pids), true, refresh);
+
+        tracked
+            .into_iter()
+            .filter_map(|(name, pid)| {
+                let process = system.process(Pid::from_u32(pid))?;
+                Some(ProcessStat {
+                    name: name.to_string(),
+                    pid,
+                    cpu_percent: process.cpu_usage(),
+                    memory_bytes: process.memory(),
+                })
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Failed to collect process stats: {e}"))
+}
+
+#[tauri::command]
+pub fn engine_metrics(
+    manager: State<EngineManager>,
+    openwrk_manager: State<OpenwrkManager>,
+) -> EngineMetrics {
+    let mut state = manager.inner.lock().expect("engine mutex poisoned");
+    let info = EngineManager::snapshot_locked(&mut state);
+    let (running, last_stdout, last_stderr) = if state.runtime == EngineRuntime::Openwrk {
+        let openwrk_state = openwrk_manager.inner.lock().ok();
+        (
+            state.started_at.is_some(),
+            openwrk_state
+                .as_ref()
+                .and_then(|state| state.last_stdout.clone()),
+            openwrk_state
+                .as_ref()
+                .and_then(|state| state.last_stderr.clone()),
+        )
+    } else {
+        (info.running, info.last_stdout, info.last_stderr)
+    };
+
+    let last_error = last_stderr.as_deref().and_then(|stderr| {
+        stderr
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+    });
+    let uptime_ms = if running {
+        state
+            .started_at
+            .map(|started_at| now_ms().saturating_sub(started_at))
+    } else {
+        None
+    };
+
+    EngineMetrics {
+        running,
+        runtime: state.runtime.clone(),
+        started_at: state.started_at,
+        uptime_ms,
+        restart_count: state.restart_count,
+        last_error,
+        stdout_bytes: last_stdout.as_ref().map(|value| value.len()).unwrap_or(0),
+        stderr_bytes: last_stderr.as_ref().map(|value| value.len()).unwrap_or(0),
+    }
+}
+
+/// With `keep_daemon` on the openwrk runtime, only the UI side is detached: the daemon
+/// (and the OpenCode server it supervises) keeps running for scheduled jobs, and the
+/// next `engine_info` poll picks it back up.
 #[tauri::command]
 pub fn engine_stop(
     manager: State<EngineManager>,
     openwrk_manager: State<OpenwrkManager>,
     openwork_manager: State<OpenworkServerManager>,
     owpenbot_manager: State<OwpenbotManager>,
+    keep_daemon: Option<bool>,
 ) -> EngineInfo {
     let mut state = manager.inner.lock().expect("engine mutex poisoned");
-    if let Ok(mut openwrk_state) = openwrk_manager.inner.lock() {
-        OpenwrkManager::stop_locked(&mut openwrk_state);
+    let keep_daemon = keep_daemon.unwrap_or(false) && state.runtime == EngineRuntime::Openwrk;
+    if !keep_daemon {
+        if let Ok(mut openwrk_state) = openwrk_manager.inner.lock() {
+            OpenwrkManager::stop_locked(&mut openwrk_state);
+        }
+    }
+    let kept = keep_daemon.then(|| {
+        (
+            state.project_dir.clone(),
+            state.workspace_paths.clone(),
+            state.opencode_username.clone(),
+            state.opencode_password.clone(),
+        )
+    });
+    let shutdown = EngineManager::stop_locked(&mut state);
+    if let Some((project_dir, workspace_paths, opencode_username, opencode_password)) = kept {
+        // The daemon's OpenCode still expects these once `engine_info` re-adopts it.
+        state.project_dir = project_dir;
+        state.workspace_paths = workspace_paths;
+        state.opencode_username = opencode_username;
+        state.opencode_password = opencode_password;
+        state.daemon_kept = true;
     }
-    EngineManager::stop_locked(&mut state);
     if let Ok(mut openwork_state) = openwork_manager.inner.lock() {
         OpenworkServerManager::stop_locked(&mut openwork_state);
     }
     if let Ok(mut owpenbot_state) = owpenbot_manager.inner.lock() {
         OwpenbotManager::stop_locked(&mut owpenbot_state);
     }
-    EngineManager::snapshot_locked(&mut state)
+    let info = EngineManager::snapshot_locked(&mut state);
+    drop(state);
+    shutdown.finish();
+    info
+}
+
+/// Collapses repeated lines in the buffered engine output, keeping the most recent
+/// `log_buffer_chars` (the same limit the reader loops use).
+#[tauri::command]
+pub fn engine_compact_output(
+    app: AppHandle,
+    manager: State<EngineManager>,
+    openwrk_manager: State<OpenwrkManager>,
+) -> EngineInfo {
+    let log_chars = log_buffer_chars(&app);
+    let compact = |buffer: &mut Option<String>| {
+        if let Some(value) = buffer.as_deref() {
+            *buffer = Some(compact_output(value, log_chars, true));
+        }
+    };
+    if let Ok(mut state) = manager.inner.lock() {
+        compact(&mut state.last_stdout);
+        compact(&mut state.last_stderr);
+    }
+    if let Ok(mut state) = openwrk_manager.inner.lock() {
+        compact(&mut state.last_stdout);
+        compact(&mut state.last_stderr);
+    }
+
+    engine_info(manager, openwrk_manager)
+}
+
+#[tauri::command]
+pub fn auth_watch_start(
+    app: AppHandle,
+    auth_watch: State<AuthWatchState>,
+    timeout_ms: Option<u64>,
+) -> Result<(), String> {
+    let timeout = std::time::Duration::from_millis(timeout_ms.unwrap_or(10 * 60 * 1000));
+    auth_watch.start(&app, timeout);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn auth_watch_stop(auth_watch: State<AuthWatchState>) {
+    auth_watch.stop();
+}
+
+/// The workspace the running engine was started for, matched by path against the
+/// workspace list. `None` when no engine is running or its directory isn't a workspace.
+#[tauri::command]
+pub fn engine_current_workspace(
+    app: AppHandle,
+    manager: State<EngineManager>,
+) -> Result<Option<WorkspaceInfo>, String> {
+    let project_dir = {
+        let mut state = manager
+            .inner
+            .lock()
+            .map_err(|_| "engine mutex poisoned".to_string())?;
+        let info = EngineManager::snapshot_locked(&mut state);
+        // Openwrk owns the engine process, so there is no child to report `running`.
+        let running =
+            info.running || (info.runtime == EngineRuntime::Openwrk && info.base_url.is_some());
+        if !running {
+            return Ok(None);
+        }
+        info.project_dir
+    };
+    let Some(project_dir) = project_dir else {
+        return Ok(None);
+    };
+
+    let canonical = |path: &str| {
+        std::fs::canonicalize(path.trim()).unwrap_or_else(|_| std::path::PathBuf::from(path.trim()))
+    };
+    let engine_dir = canonical(&project_dir);
+    let state = load_workspace_state(&app)?;
+    Ok(state.workspaces.into_iter().find(|workspace| {
+        workspace.workspace_type == WorkspaceType::Local && canonical(&workspace.path) == engine_dir
+    }))
 }
 
 #[tauri::command]
 pub fn engine_doctor(app: AppHandle, prefer_sidecar: Option<bool>) -> EngineDoctorResult {
-    let prefer_sidecar = prefer_sidecar.unwrap_or(false);
+    run_engine_doctor(&app, prefer_sidecar.unwrap_or(false))
+}
+
+fn run_engine_doctor(app: &AppHandle, prefer_sidecar: bool) -> EngineDoctorResult {
     let resource_dir = app.path().resource_dir().ok();
 
     let current_bin_dir = tauri::process::current_binary(&app.env())
@@ -125,6 +393,23 @@ pub fn engine_doctor(app: AppHandle, prefer_sidecar: Option<bool>) -> EngineDoct
             None => (None, false, None, None, None),
         };
 
+    let mut notes = notes;
+    if let Some(version) = version.as_deref() {
+        if !version_meets_minimum(version, MIN_OPENCODE_VERSION) {
+            notes.push(format!(
+                "OpenCode {version} is older than the minimum supported {MIN_OPENCODE_VERSION}"
+            ));
+        }
+    }
+
+    let arch_mismatch = match resolved.as_deref().and_then(sidecar_arch_mismatch) {
+        Some(note) => {
+            notes.push(format!("Architecture mismatch: {note}"));
+            true
+        }
+        None => false,
+    };
+
     EngineDoctorResult {
         found: resolved.is_some(),
         in_path,
@@ -135,45 +420,288 @@ pub fn engine_doctor(app: AppHandle, prefer_sidecar: Option<bool>) -> EngineDoct
         serve_help_status,
         serve_help_stdout,
         serve_help_stderr,
+        arch_mismatch,
     }
 }
 
 #[tauri::command]
-pub fn engine_install() -> Result<ExecResult, String> {
+pub fn crash_snapshot(
+    app: AppHandle,
+    manager: State<EngineManager>,
+    openwrk_manager: State<OpenwrkManager>,
+    openwork_manager: State<OpenworkServerManager>,
+    owpenbot_manager: State<OwpenbotManager>,
+) -> CrashSnapshot {
+    let (engine_stderr, opencode_password) = manager
+        .inner
+        .lock()
+        .map(|state| (state.last_stderr.clone(), state.opencode_password.clone()))
+        .unwrap_or_default();
+    let openwrk_stderr = openwrk_manager
+        .inner
+        .lock()
+        .ok()
+        .and_then(|state| state.last_stderr.clone());
+    let (openwork_server_stderr, client_token, host_token) = openwork_manager
+        .inner
+        .lock()
+        .map(|state| {
+            (
+                state.last_stderr.clone(),
+                state.client_token.clone(),
+                state.host_token.clone(),
+            )
+        })
+        .unwrap_or_default();
+    let owpenbot_stderr = owpenbot_manager
+        .inner
+        .lock()
+        .ok()
+        .and_then(|state| state.last_stderr.clone());
+
+    let secrets: Vec<&str> = [&opencode_password, &client_token, &host_token]
+        .into_iter()
+        .filter_map(|secret| secret.as_deref())
+        .collect();
+    let snapshot = |stderr: Option<String>| {
+        stderr.map(|value| redact_secrets(&truncate_output(&value, 2000), &secrets))
+    };
+
+    CrashSnapshot {
+        openwork_version: app.package_info().version.to_string(),
+        engine_stderr: snapshot(engine_stderr),
+        openwrk_stderr: snapshot(openwrk_stderr),
+        openwork_server_stderr: snapshot(openwork_server_stderr),
+        owpenbot_stderr: snapshot(owpenbot_stderr),
+        doctor: run_engine_doctor(&app, false),
+    }
+}
+
+#[tauri::command]
+pub fn get_default_runtime(app: AppHandle) -> Result<EngineRuntime, String> {
+    Ok(load_preferences_or_default(&app).0.default_runtime)
+}
+
+/// Stores the reader-loop buffer size and returns the clamped value. It applies to
+/// processes started after the change.
+#[tauri::command]
+pub fn set_log_buffer_chars(app: AppHandle, chars: usize) -> Result<usize, String> {
+    let chars = clamp_log_buffer_chars(chars);
+    let mut preferences = load_preferences(&app)?;
+    preferences.log_buffer_chars = chars;
+    save_preferences(&app, &preferences)?;
+    Ok(chars)
+}
+
+#[tauri::command]
+pub fn set_default_runtime(app: AppHandle, runtime: EngineRuntime) -> Result<ExecResult, String> {
+    if runtime == EngineRuntime::Openwrk {
+        let current_bin_dir = tauri::process::current_binary(&app.env())
+            .ok()
+            .and_then(|path| path.parent().map(|parent| parent.to_path_buf()));
+        if openwrk::resolve_openwrk_binary(current_bin_dir.as_deref()).is_none() {
+            return Ok(ExecResult {
+                ok: false,
+                status: 1,
+                stdout: String::new(),
+                stderr: "openwrk sidecar is missing or a build stub; keeping the current default runtime"
+                    .to_string(),
+            });
+        }
+    }
+
+    let mut preferences = load_preferences(&app)?;
+    preferences.default_runtime = runtime.clone();
+    save_preferences(&app, &preferences)?;
+
+    Ok(ExecResult {
+        ok: true,
+        status: 0,
+        stdout: format!("Default runtime set to {runtime:?}"),
+        stderr: String::new(),
+    })
+}
+
+#[tauri::command]
+pub fn list_opencode_candidates() -> Vec<OpencodeCandidate> {
+    let (selected, _in_path, _notes) = resolve_opencode_executable();
+    all_opencode_paths()
+        .into_iter()
+        .map(|path| OpencodeCandidate {
+            version: opencode_version(path.as_os_str()),
+            selected: selected.as_ref() == Some(&path),
+            path: path.to_string_lossy().to_string(),
+        })
+        .collect()
+}
+
+/// Persists an explicit opencode binary; an empty or missing path clears the override.
+#[tauri::command]
+pub fn set_opencode_binary(app: AppHandle, path: Option<String>) -> Result<(), String> {
+    let path = path
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+    if let Some(path) = path.as_deref() {
+        if !std::path::Path::new(path).is_file() {
+            return Err(format!("opencode binary not found: {path}"));
+        }
+    }
+
+    let mut preferences = load_preferences(&app)?;
+    preferences.opencode_bin_path = path.clone();
+    save_preferences(&app, &preferences)?;
+    set_preferred_opencode_binary(path.map(std::path::PathBuf::from));
+    Ok(())
+}
+
+pub fn guided_install_supported() -> bool {
+    !cfg!(windows)
+}
+
+#[tauri::command]
+pub async fn engine_install(
+    app: AppHandle,
+    install_manager: State<'_, InstallManager>,
+) -> Result<InstallOutcome, String> {
     #[cfg(windows)]
     {
-        return Ok(ExecResult {
-      ok: false,
-      status: -1,
-      stdout: String::new(),
-      stderr: "Guided install is not supported on Windows yet. Install OpenCode via Scoop/Chocolatey or https://opencode.ai/install, then restart OpenWork.".to_string(),
+        let _ = (&app, &install_manager);
+        return Ok(InstallOutcome {
+      install_id: String::new(),
+      result: ExecResult {
+        ok: false,
+        status: -1,
+        stdout: String::new(),
+        stderr: "Guided install is not supported on Windows yet. Install OpenCode via Scoop/Chocolatey or https://opencode.ai/install, then restart OpenWork.".to_string(),
+      },
     });
     }
 
     #[cfg(not(windows))]
     {
+        use crate::install::run_install;
+        use tauri_plugin_shell::ShellExt;
+
         let install_dir = crate::paths::home_dir()
             .unwrap_or_else(|| std::path::PathBuf::from("."))
             .join(".opencode")
             .join("bin");
 
-        let output = std::process::Command::new("bash")
-            .arg("-lc")
-            .arg("curl -fsSL https://opencode.ai/install | bash")
-            .env("OPENCODE_INSTALL_DIR", install_dir)
-            .output()
-            .map_err(|e| format!("Failed to run installer: {e}"))?;
-
-        let status = output.status.code().unwrap_or(-1);
-        Ok(ExecResult {
-            ok: output.status.success(),
-            status,
-            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        let command = app
+            .shell()
+            .command("bash")
+            .args(["-lc", "curl -fsSL https://opencode.ai/install | bash"])
+            .env("OPENCODE_INSTALL_DIR", install_dir);
+
+        run_install(&app, &install_manager, "engine", command, None).await
+    }
+}
+
+fn start_openwrk_dependents(
+    app: &AppHandle,
+    manager: &EngineManager,
+    openwork_manager: &OpenworkServerManager,
+    owpenbot_manager: State<OwpenbotManager>,
+    workspace_paths: &[String],
+    opencode_connect_url: String,
+) {
+    let Ok((project_dir, opencode_username, opencode_password)) =
+        manager.inner.lock().map(|state| {
+            (
+                state.project_dir.clone().unwrap_or_default(),
+                state.opencode_username.clone(),
+                state.opencode_password.clone(),
+            )
         })
+    else {
+        return;
+    };
+    let log_chars = log_buffer_chars(app);
+
+    let owpenbot_health_port = match resolve_owpenbot_health_port() {
+        Ok(port) => Some(port),
+        Err(error) => {
+            if let Ok(mut state) = manager.inner.lock() {
+                state.last_stderr = Some(truncate_output(
+                    &format!("Owpenbot health port: {error}"),
+                    log_chars,
+                ));
+            }
+            None
+        }
+    };
+
+    if let Err(error) = start_openwork_server(
+        app,
+        openwork_manager,
+        workspace_paths,
+        Some(&opencode_connect_url),
+        opencode_username.as_deref(),
+        opencode_password.as_deref(),
+        owpenbot_health_port,
+    ) {
+        if let Ok(mut state) = manager.inner.lock() {
+            state.last_stderr = Some(truncate_output(
+                &format!("OpenWork server: {error}"),
+                log_chars,
+            ));
+        }
     }
+
+    if let Err(error) = owpenbot_start(
+        app.clone(),
+        owpenbot_manager,
+        project_dir,
+        Some(opencode_connect_url),
+        opencode_username,
+        opencode_password,
+        owpenbot_health_port,
+    ) {
+        if let Ok(mut state) = manager.inner.lock() {
+            state.last_stderr = Some(truncate_output(&format!("Owpenbot: {error}"), log_chars));
+        }
+    }
+}
+
+/// The engine's local `base_url` next to the connect URL handed to the OpenWork server
+/// and owpenbot, derived the same way `engine_start` does.
+#[tauri::command]
+pub fn engine_connect_url(manager: State<EngineManager>) -> Result<EngineConnectInfo, String> {
+    let state = manager
+        .inner
+        .lock()
+        .map_err(|_| "engine mutex poisoned".to_string())?;
+    let connect_url = state
+        .port
+        .and_then(resolve_connect_url)
+        .or_else(|| state.base_url.clone());
+    let has_credentials = state
+        .opencode_username
+        .as_deref()
+        .is_some_and(|value| !value.trim().is_empty())
+        && state
+            .opencode_password
+            .as_deref()
+            .is_some_and(|value| !value.trim().is_empty());
+
+    Ok(EngineConnectInfo {
+        base_url: state.base_url.clone(),
+        connect_url,
+        has_credentials,
+    })
+}
+
+/// The `opencode serve` invocation used by the last direct `engine_start`, with the
+/// server password redacted. Openwrk-managed engines are launched by the daemon, so
+/// there is nothing to report for them.
+#[tauri::command]
+pub fn engine_last_command(manager: State<EngineManager>) -> Option<EngineCommand> {
+    let state = manager.inner.lock().expect("engine mutex poisoned");
+    state.last_command.clone()
 }
 
+/// Starts the engine for `project_dir`. This never changes the active workspace, so
+/// callers can warm up an engine for any workspace; see `engine_current_workspace`.
 #[tauri::command]
 pub fn engine_start(
     app: AppHandle,
@@ -185,18 +713,126 @@ pub fn engine_start(
     prefer_sidecar: Option<bool>,
     runtime: Option<EngineRuntime>,
     workspace_paths: Option<Vec<String>>,
+    preflight: Option<bool>,
+    engine_cwd: Option<String>,
 ) -> Result<EngineInfo, String> {
+    start_engine(
+        app,
+        manager,
+        openwrk_manager,
+        openwork_manager,
+        owpenbot_manager,
+        project_dir,
+        prefer_sidecar,
+        runtime,
+        workspace_paths,
+        preflight,
+        engine_cwd,
+        None,
+    )
+}
+
+/// Stops the engine and starts it again with the project, workspace list, runtime,
+/// sidecar preference and working directory of the last `engine_start`, keeping the
+/// current credentials so connected clients stay authorized. The preflight doctor check
+/// is skipped. Useful after editing `opencode.json`, which OpenCode only reads at startup.
+#[tauri::command]
+pub fn engine_restart(
+    app: AppHandle,
+    manager: State<EngineManager>,
+    openwrk_manager: State<OpenwrkManager>,
+    openwork_manager: State<OpenworkServerManager>,
+    owpenbot_manager: State<OwpenbotManager>,
+) -> Result<EngineInfo, String> {
+    let (project_dir, workspace_paths, prefer_sidecar, runtime, engine_cwd, credentials) = {
+        let state = manager.inner.lock().expect("engine mutex poisoned");
+        let Some(project_dir) = state.project_dir.clone() else {
+            return Err("Engine has not been started yet; call engine_start first".to_string());
+        };
+        let engine_cwd = state
+            .last_command
+            .as_ref()
+            .filter(|_| state.runtime == EngineRuntime::Direct)
+            .map(|command| command.cwd.clone());
+        let credentials = (
+            state.opencode_username.clone(),
+            state.opencode_password.clone(),
+        );
+        (
+            project_dir,
+            state.workspace_paths.clone(),
+            state.prefer_sidecar,
+            state.runtime.clone(),
+            engine_cwd,
+            credentials,
+        )
+    };
+
+    start_engine(
+        app,
+        manager,
+        openwrk_manager,
+        openwork_manager,
+        owpenbot_manager,
+        project_dir,
+        Some(prefer_sidecar),
+        Some(runtime),
+        Some(workspace_paths),
+        Some(false),
+        engine_cwd,
+        Some(credentials),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn start_engine(
+    app: AppHandle,
+    manager: State<EngineManager>,
+    openwrk_manager: State<OpenwrkManager>,
+    openwork_manager: State<OpenworkServerManager>,
+    owpenbot_manager: State<OwpenbotManager>,
+    project_dir: String,
+    prefer_sidecar: Option<bool>,
+    runtime: Option<EngineRuntime>,
+    workspace_paths: Option<Vec<String>>,
+    preflight: Option<bool>,
+    engine_cwd: Option<String>,
+    credentials: Option<(Option<String>, Option<String>)>,
+) -> Result<EngineInfo, String> {
+    let _starting = manager.begin_start()?;
     let project_dir = project_dir.trim().to_string();
     if project_dir.is_empty() {
         return Err("projectDir is required".to_string());
     }
 
+    if preflight.unwrap_or(true) {
+        let doctor = run_engine_doctor(&app, prefer_sidecar.unwrap_or(false));
+        let outdated = doctor
+            .version
+            .as_deref()
+            .is_some_and(|version| !version_meets_minimum(version, MIN_OPENCODE_VERSION));
+        if !doctor.found || outdated {
+            return Err(serde_json::to_string(&doctor)
+                .map_err(|e| format!("Failed to serialize engine doctor result: {e}"))?);
+        }
+    }
+
     // OpenCode is spawned with `current_dir(project_dir)`. If the user selected a
     // workspace path that doesn't exist yet (common during onboarding), spawning
     // fails with `os error 2`.
     std::fs::create_dir_all(&project_dir)
         .map_err(|e| format!("Failed to create projectDir directory: {e}"))?;
 
+    // `project_dir` drives config and skills resolution; `engine_cwd` only moves the
+    // direct engine's working directory, e.g. to a package inside a monorepo.
+    let engine_cwd = match engine_cwd.filter(|value| !value.trim().is_empty()) {
+        Some(dir) => resolve_authorized_dir(&project_dir, &dir)
+            .map_err(|e| format!("Invalid engineCwd: {e}"))?
+            .to_string_lossy()
+            .to_string(),
+        None => project_dir.clone(),
+    };
+
     let config = read_opencode_config("project", &project_dir)?;
     if !config.exists {
         let content = serde_json::to_string_pretty(&json!({
@@ -209,7 +845,10 @@ pub fn engine_start(
         }
     }
 
-    let runtime = runtime.unwrap_or(EngineRuntime::Direct);
+    let (preferences, preferences_warning) = load_preferences_or_default(&app);
+    let runtime = runtime.unwrap_or(preferences.default_runtime);
+    let collapse_output = preferences.collapse_duplicate_output;
+    let log_chars = clamp_log_buffer_chars(preferences.log_buffer_chars);
     let mut workspace_paths = workspace_paths.unwrap_or_default();
     workspace_paths.retain(|path| !path.trim().is_empty());
     workspace_paths.retain(|path| path.trim() != project_dir);
@@ -221,26 +860,34 @@ pub fn engine_start(
         .unwrap_or_else(|| "0.0.0.0".to_string());
     let client_host = "127.0.0.1".to_string();
     let port = find_free_port()?;
-    let enable_auth = std::env::var("OPENWORK_OPENCODE_AUTH")
-        .ok()
-        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
-        .unwrap_or(true);
-    let opencode_username = if enable_auth {
-        Some("opencode".to_string())
-    } else {
-        None
-    };
-    let opencode_password = if enable_auth {
-        Some(Uuid::new_v4().to_string())
-    } else {
-        None
-    };
+    let (opencode_username, opencode_password) = credentials.unwrap_or_else(|| {
+        let enable_auth = std::env::var("OPENWORK_OPENCODE_AUTH")
+            .ok()
+            .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+            .unwrap_or(true);
+        if enable_auth {
+            (
+                Some("opencode".to_string()),
+                Some(Uuid::new_v4().to_string()),
+            )
+        } else {
+            (None, None)
+        }
+    });
 
     let mut state = manager.inner.lock().expect("engine mutex poisoned");
-    EngineManager::stop_locked(&mut state);
+    let restarting = state.started_at.is_some();
+    let shutdown = EngineManager::stop_locked(&mut state);
+    if restarting {
+        state.restart_count = state.restart_count.saturating_add(1);
+    }
     if let Ok(mut openwrk_state) = openwrk_manager.inner.lock() {
         OpenwrkManager::stop_locked(&mut openwrk_state);
     }
+    // Wait for the old engine without the lock so `engine_info` polls keep answering.
+    drop(state);
+    shutdown.finish();
+    let mut state = manager.inner.lock().expect("engine mutex poisoned");
     state.runtime = runtime.clone();
 
     let resource_dir = app.path().resource_dir().ok();
@@ -309,7 +956,8 @@ pub fn engine_start(
                                 .unwrap_or_default()
                                 .to_string()
                                 + &line;
-                            state.last_stdout = Some(truncate_output(&next, 8000));
+                            state.last_stdout =
+                                Some(compact_output(&next, log_chars, collapse_output));
                         }
                     }
                     CommandEvent::Stderr(line_bytes) => {
@@ -321,7 +969,8 @@ pub fn engine_start(
                                 .unwrap_or_default()
                                 .to_string()
                                 + &line;
-                            state.last_stderr = Some(truncate_output(&next, 8000));
+                            state.last_stderr =
+                                Some(compact_output(&next, log_chars, collapse_output));
                         }
                     }
                     CommandEvent::Terminated(_) => {
@@ -338,7 +987,8 @@ pub fn engine_start(
                                 .unwrap_or_default()
                                 .to_string()
                                 + &message;
-                            state.last_stderr = Some(truncate_output(&next, 8000));
+                            state.last_stderr =
+                                Some(compact_output(&next, log_chars, collapse_output));
                         }
                     }
                     _ => {}
@@ -362,52 +1012,27 @@ pub fn engine_start(
             state.child = None;
             state.child_exited = false;
             state.project_dir = Some(project_dir.clone());
+            state.workspace_paths = workspace_paths.clone();
+            state.prefer_sidecar = prefer_sidecar;
             state.hostname = Some("127.0.0.1".to_string());
             state.port = Some(opencode_port);
             state.base_url = Some(opencode_base_url.clone());
             state.opencode_username = opencode_username.clone();
             state.opencode_password = opencode_password.clone();
             state.last_stdout = None;
-            state.last_stderr = None;
+            state.last_stderr = preferences_warning.clone();
+            state.started_at = Some(now_ms());
+            state.last_command = None;
         }
 
-        let owpenbot_health_port = match resolve_owpenbot_health_port() {
-            Ok(port) => Some(port),
-            Err(error) => {
-                if let Ok(mut state) = manager.inner.lock() {
-                    state.last_stderr = Some(truncate_output(&format!("Owpenbot health port: {error}"), 8000));
-                }
-                None
-            }
-        };
-
-        if let Err(error) = start_openwork_server(
+        start_openwrk_dependents(
             &app,
+            &manager,
             &openwork_manager,
-            &workspace_paths,
-            Some(&opencode_connect_url),
-            opencode_username.as_deref(),
-            opencode_password.as_deref(),
-            owpenbot_health_port,
-        ) {
-            if let Ok(mut state) = manager.inner.lock() {
-                state.last_stderr = Some(truncate_output(&format!("OpenWork server: {error}"), 8000));
-            }
-        }
-
-        if let Err(error) = owpenbot_start(
-            app.clone(),
             owpenbot_manager,
-            project_dir.clone(),
-            Some(opencode_connect_url),
-            opencode_username.clone(),
-            opencode_password.clone(),
-            owpenbot_health_port,
-        ) {
-            if let Ok(mut state) = manager.inner.lock() {
-                state.last_stderr = Some(truncate_output(&format!("Owpenbot: {error}"), 8000));
-            }
-        }
+            &workspace_paths,
+            opencode_connect_url,
+        );
 
         return Ok(EngineInfo {
             running: true,
@@ -420,24 +1045,43 @@ pub fn engine_start(
             opencode_password,
             pid: Some(opencode.pid),
             last_stdout: None,
-            last_stderr: None,
+            last_stderr: preferences_warning,
         });
     }
 
+    let pidfile = engine_pidfile_path(&app).ok();
+    if let Some(note) = pidfile.as_deref().and_then(reap_stale_engine) {
+        println!("[engine] {note}");
+    }
+
     let (mut rx, child) = spawn_engine(
         &app,
         &program,
         &bind_host,
         port,
-        &project_dir,
+        &engine_cwd,
         use_sidecar,
         opencode_username.as_deref(),
         opencode_password.as_deref(),
     )?;
 
     state.last_stdout = None;
-    state.last_stderr = None;
+    state.last_stderr = preferences_warning;
     state.child_exited = false;
+    if let Some(path) = pidfile {
+        match write_engine_pidfile(&path, child.pid(), port) {
+            Ok(()) => state.pidfile = Some(path),
+            Err(error) => println!("[engine] {error}"),
+        }
+    }
+    state.last_command = Some(describe_engine_command(
+        &program,
+        &bind_host,
+        port,
+        &engine_cwd,
+        opencode_username.as_deref(),
+        opencode_password.as_deref(),
+    ));
 
     let output_state = std::sync::Arc::new(std::sync::Mutex::new(OutputState::default()));
     let output_state_handle = output_state.clone();
@@ -458,7 +1102,7 @@ pub fn engine_start(
                             .unwrap_or_default()
                             .to_string()
                             + &line;
-                        state.last_stdout = Some(truncate_output(&next, 8000));
+                        state.last_stdout = Some(compact_output(&next, log_chars, collapse_output));
                     }
                 }
                 CommandEvent::Stderr(line_bytes) => {
@@ -473,7 +1117,7 @@ pub fn engine_start(
                             .unwrap_or_default()
                             .to_string()
                             + &line;
-                        state.last_stderr = Some(truncate_output(&next, 8000));
+                        state.last_stderr = Some(compact_output(&next, log_chars, collapse_output));
                     }
                 }
                 CommandEvent::Terminated(payload) => {
@@ -500,8 +1144,16 @@ pub fn engine_start(
         }
     });
 
-    let warmup_deadline = std::time::Instant::now() + std::time::Duration::from_secs(2);
-    loop {
+    // The engine is tracked (so `engine_stop` can kill it) but the lock is released while
+    // polling, so `engine_info` and friends don't block for the whole readiness timeout.
+    let pid = child.pid();
+    state.child = Some(child);
+    drop(state);
+
+    let base_url = format!("http://{client_host}:{port}");
+    let ready_timeout = engine_ready_timeout();
+    let ready_deadline = std::time::Instant::now() + ready_timeout;
+    let ready = loop {
         if let Ok(output) = output_state.lock() {
             if output.exited {
                 let stdout = output.stdout.trim().to_string();
@@ -510,12 +1162,12 @@ pub fn engine_start(
                 let stdout = if stdout.is_empty() {
                     None
                 } else {
-                    Some(truncate_output(&stdout, 8000))
+                    Some(truncate_output(&stdout, log_chars))
                 };
                 let stderr = if stderr.is_empty() {
                     None
                 } else {
-                    Some(truncate_output(&stderr, 8000))
+                    Some(truncate_output(&stderr, log_chars))
                 };
 
                 let mut parts = Vec::new();
@@ -532,7 +1184,7 @@ pub fn engine_start(
                     format!("\n\n{}", parts.join("\n\n"))
                 };
 
-                return Err(format!(
+                break Err(format!(
                     "OpenCode exited immediately with status {}.{}",
                     output.exit_code.unwrap_or(-1),
                     suffix
@@ -540,26 +1192,66 @@ pub fn engine_start(
             }
         }
 
-        if std::time::Instant::now() >= warmup_deadline {
-            break;
+        if engine_responding(&base_url) {
+            break Ok(());
+        }
+
+        if std::time::Instant::now() >= ready_deadline {
+            let stderr = output_state
+                .lock()
+                .map(|output| truncate_output(output.stderr.trim(), log_chars))
+                .unwrap_or_default();
+            let suffix = if stderr.is_empty() {
+                String::new()
+            } else {
+                format!("\n\nstderr:\n{stderr}")
+            };
+            break Err(format!(
+                "OpenCode did not respond at {base_url} within {}ms.{suffix}",
+                ready_timeout.as_millis()
+            ));
         }
 
         std::thread::sleep(std::time::Duration::from_millis(150));
+    };
+
+    let mut state = manager.inner.lock().expect("engine mutex poisoned");
+    let still_ours = state.child.as_ref().is_some_and(|child| child.pid() == pid);
+    if let Err(error) = ready {
+        // If the engine was stopped meanwhile, `stop_locked` already cleaned up.
+        if still_ours {
+            if let Some(child) = state.child.take() {
+                let _ = child.kill();
+            }
+            state.child_exited = true;
+            if let Some(pidfile) = state.pidfile.take() {
+                remove_engine_pidfile(&pidfile);
+            }
+        }
+        return Err(error);
+    }
+    if !still_ours {
+        return Err("Engine was stopped before it became ready".to_string());
     }
 
-    state.child = Some(child);
     state.project_dir = Some(project_dir.clone());
+    state.workspace_paths = workspace_paths.clone();
+    state.prefer_sidecar = prefer_sidecar;
     state.hostname = Some(client_host.clone());
     state.port = Some(port);
-    state.base_url = Some(format!("http://{client_host}:{port}"));
+    state.base_url = Some(base_url);
     state.opencode_username = opencode_username.clone();
     state.opencode_password = opencode_password.clone();
+    state.started_at = Some(now_ms());
 
     let opencode_connect_url = resolve_connect_url(port).unwrap_or_else(|| format!("http://{client_host}:{port}"));
     let owpenbot_health_port = match resolve_owpenbot_health_port() {
         Ok(port) => Some(port),
         Err(error) => {
-            state.last_stderr = Some(truncate_output(&format!("Owpenbot health port: {error}"), 8000));
+            state.last_stderr = Some(truncate_output(
+                &format!("Owpenbot health port: {error}"),
+                log_chars,
+            ));
             None
         }
     };
@@ -573,7 +1265,7 @@ pub fn engine_start(
         opencode_password.as_deref(),
         owpenbot_health_port,
     ) {
-        state.last_stderr = Some(truncate_output(&format!("OpenWork server: {error}"), 8000));
+        state.last_stderr = Some(truncate_output(&format!("OpenWork server: {error}"), log_chars));
     }
 
     if let Err(error) = owpenbot_start(
@@ -585,8 +1277,118 @@ pub fn engine_start(
         opencode_password,
         owpenbot_health_port,
     ) {
-        state.last_stderr = Some(truncate_output(&format!("Owpenbot: {error}"), 8000));
+        state.last_stderr = Some(truncate_output(&format!("Owpenbot: {error}"), log_chars));
     }
 
     Ok(EngineManager::snapshot_locked(&mut state))
 }
+
+#[tauri::command]
+pub fn engine_attach_openwrk(
+    app: AppHandle,
+    manager: State<EngineManager>,
+    openwrk_manager: State<OpenwrkManager>,
+    openwork_manager: State<OpenworkServerManager>,
+    owpenbot_manager: State<OwpenbotManager>,
+    daemon_base_url: String,
+    opencode_username: Option<String>,
+    opencode_password: Option<String>,
+) -> Result<EngineInfo, String> {
+    let daemon_base_url = daemon_base_url.trim().trim_end_matches('/').to_string();
+    if daemon_base_url.is_empty() {
+        return Err("daemonBaseUrl is required".to_string());
+    }
+
+    let health = openwrk::fetch_openwrk_health(&daemon_base_url)
+        .map_err(|e| format!("Failed to reach openwrk at {daemon_base_url}: {e}"))?;
+    if !health.ok {
+        return Err(format!("Openwrk at {daemon_base_url} is not healthy"));
+    }
+    let opencode = health
+        .opencode
+        .ok_or_else(|| "Openwrk did not report OpenCode status".to_string())?;
+
+    let workspaces = openwrk::fetch_openwrk_workspaces(&daemon_base_url).ok();
+    let workspace_paths: Vec<String> = workspaces
+        .as_ref()
+        .map(|list| list.workspaces.iter().map(|ws| ws.path.clone()).collect())
+        .unwrap_or_default();
+    let project_dir = workspaces
+        .as_ref()
+        .and_then(|list| {
+            let active = list.active_id.as_ref().or(health.active_id.as_ref())?;
+            list.workspaces.iter().find(|ws| &ws.id == active)
+        })
+        .map(|ws| ws.path.clone())
+        .or_else(|| workspace_paths.first().cloned());
+
+    let opencode_port = opencode.port;
+    let opencode_base_url = format!("http://127.0.0.1:{opencode_port}");
+    let opencode_connect_url =
+        resolve_connect_url(opencode_port).unwrap_or_else(|| opencode_base_url.clone());
+
+    let shutdown = {
+        let mut state = manager.inner.lock().expect("engine mutex poisoned");
+        let restarting = state.started_at.is_some();
+        let shutdown = EngineManager::stop_locked(&mut state);
+        if restarting {
+            state.restart_count = state.restart_count.saturating_add(1);
+        }
+        if let Ok(mut openwrk_state) = openwrk_manager.inner.lock() {
+            // Re-attaching to the daemon we spawned must not kill it out from under us.
+            let managed_url = openwrk_state.daemon_base_url.clone().or_else(|| {
+                let data_dir = openwrk_state.data_dir.as_deref()?;
+                Some(openwrk::read_openwrk_state(data_dir)?.daemon?.base_url)
+            });
+            let attaching_managed = openwrk_state.child.is_some()
+                && !openwrk_state.child_exited
+                && managed_url
+                    .as_deref()
+                    .is_some_and(|url| url.trim_end_matches('/') == daemon_base_url);
+            if !attaching_managed {
+                OpenwrkManager::stop_locked(&mut openwrk_state);
+            }
+            openwrk_state.daemon_base_url = Some(daemon_base_url.clone());
+        }
+
+        state.runtime = EngineRuntime::Openwrk;
+        state.child_exited = false;
+        state.project_dir = project_dir.clone();
+        state.workspace_paths = workspace_paths.clone();
+        state.hostname = Some("127.0.0.1".to_string());
+        state.port = Some(opencode_port);
+        state.base_url = Some(opencode_base_url.clone());
+        state.opencode_username = opencode_username.clone();
+        state.opencode_password = opencode_password.clone();
+        state.started_at = Some(now_ms());
+        shutdown
+    };
+    shutdown.finish();
+
+    println!("[engine] attached to openwrk daemon at {daemon_base_url}");
+
+    if project_dir.is_some() {
+        start_openwrk_dependents(
+            &app,
+            &manager,
+            &openwork_manager,
+            owpenbot_manager,
+            &workspace_paths,
+            opencode_connect_url,
+        );
+    }
+
+    Ok(EngineInfo {
+        running: true,
+        runtime: EngineRuntime::Openwrk,
+        base_url: Some(opencode_base_url),
+        project_dir,
+        hostname: Some("127.0.0.1".to_string()),
+        port: Some(opencode_port),
+        opencode_username,
+        opencode_password,
+        pid: Some(opencode.pid),
+        last_stdout: None,
+        last_stderr: None,
+    })
+}
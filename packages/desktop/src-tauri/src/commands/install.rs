@@ -0,0 +1,13 @@
+use tauri::State;
+
+use crate::install::InstallManager;
+
+#[tauri::command]
+pub fn cancel_install(manager: State<InstallManager>, id: String) -> Result<bool, String> {
+    let id = id.trim();
+    if id.is_empty() {
+        return Err("id is required".to_string());
+    }
+
+    Ok(manager.cancel(id))
+}
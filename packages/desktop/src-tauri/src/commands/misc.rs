@@ -1,12 +1,24 @@
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
-
-use crate::engine::doctor::resolve_engine_path;
+use std::time::{Duration, SystemTime};
+
+use crate::commands::engine::guided_install_supported;
+use crate::commands::scheduler::scheduler_supported;
+use crate::config::{parse_opencode_config, read_opencode_config};
+use crate::engine::doctor::{opencode_subcommands, resolve_engine_path, resolve_sidecar_candidate};
+use crate::engine::spawn::build_engine_args;
+use crate::openwork_server::spawn::build_openwork_args;
 use crate::paths::home_dir;
 use crate::platform::command_for_program;
-use crate::types::{ExecResult, WorkspaceOpenworkConfig};
-use crate::workspace::state::load_workspace_state;
+use crate::preferences::load_preferences;
+use crate::types::{
+    CorsOrigins, DataDirStatus, ExecResult, MissingPlugin, PlatformCapabilities, SystemTimeStatus,
+};
+use crate::updater::updater_environment;
+use crate::utils::checked_now_ms;
+use crate::workspace::scope::read_workspace_openwork_config;
+use crate::workspace::state::{check_data_dir_status, load_workspace_state};
 use tauri::{AppHandle, Manager};
 
 #[derive(serde::Serialize)]
@@ -16,6 +28,12 @@ pub struct CacheResetResult {
     pub errors: Vec<String>,
 }
 
+#[derive(serde::Serialize)]
+pub struct BackupCleanupResult {
+    pub removed: Vec<String>,
+    pub errors: Vec<String>,
+}
+
 fn opencode_cache_candidates() -> Vec<PathBuf> {
     let mut candidates: Vec<PathBuf> = Vec::new();
 
@@ -78,26 +96,6 @@ fn validate_server_name(name: &str) -> Result<String, String> {
     Ok(trimmed.to_string())
 }
 
-fn read_workspace_openwork_config(
-    workspace_path: &Path,
-) -> Result<WorkspaceOpenworkConfig, String> {
-    let openwork_path = workspace_path.join(".opencode").join("openwork.json");
-    if !openwork_path.exists() {
-        let mut cfg = WorkspaceOpenworkConfig::default();
-        let workspace_value = workspace_path.to_string_lossy().to_string();
-        if !workspace_value.trim().is_empty() {
-            cfg.authorized_roots.push(workspace_value);
-        }
-        return Ok(cfg);
-    }
-
-    let raw = fs::read_to_string(&openwork_path)
-        .map_err(|e| format!("Failed to read {}: {e}", openwork_path.display()))?;
-
-    serde_json::from_str::<WorkspaceOpenworkConfig>(&raw)
-        .map_err(|e| format!("Failed to parse {}: {e}", openwork_path.display()))
-}
-
 fn load_authorized_roots(app: &AppHandle) -> Result<Vec<PathBuf>, String> {
     let state = load_workspace_state(app)?;
     let mut roots = Vec::new();
@@ -188,6 +186,75 @@ pub fn reset_opencode_cache() -> Result<CacheResetResult, String> {
     })
 }
 
+fn collect_backup_files(
+    dir: &Path,
+    is_root: bool,
+    out: &mut Vec<(PathBuf, SystemTime)>,
+) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {e}", dir.display()))? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+        let path = entry.path();
+
+        if file_type.is_dir() {
+            // User workspaces live under the data dir; their files are not ours to prune.
+            if is_root && entry.file_name() == "workspaces" {
+                continue;
+            }
+            collect_backup_files(&path, false, out)?;
+            continue;
+        }
+
+        if !file_type.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some("bak") {
+            continue;
+        }
+
+        let modified = entry
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        out.push((path, modified));
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn cleanup_backups(app: AppHandle) -> Result<BackupCleanupResult, String> {
+    let preferences = load_preferences(&app)?;
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {e}"))?;
+
+    let mut removed = Vec::new();
+    let mut errors = Vec::new();
+    if !data_dir.is_dir() {
+        return Ok(BackupCleanupResult { removed, errors });
+    }
+
+    let mut backups = Vec::new();
+    collect_backup_files(&data_dir, true, &mut backups)?;
+    backups.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+    let max_age = Duration::from_secs(preferences.backup_retention_days.saturating_mul(86_400));
+    let cutoff = SystemTime::now()
+        .checked_sub(max_age)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    for (index, (path, modified)) in backups.into_iter().enumerate() {
+        if index < preferences.backup_max_count && modified >= cutoff {
+            continue;
+        }
+        match fs::remove_file(&path) {
+            Ok(()) => removed.push(path.to_string_lossy().to_string()),
+            Err(err) => errors.push(format!("Failed to remove {}: {err}", path.display())),
+        }
+    }
+
+    Ok(BackupCleanupResult { removed, errors })
+}
+
 #[tauri::command]
 pub fn reset_openwork_state(app: tauri::AppHandle, mode: String) -> Result<(), String> {
     let mode = mode.trim();
@@ -260,3 +327,234 @@ pub fn opencode_mcp_auth(
         stderr: String::from_utf8_lossy(&output.stderr).to_string(),
     })
 }
+
+const SELF_DIAGNOSE_SUBCOMMANDS: [&str; 3] = ["doctor", "diagnose", "diagnostics"];
+
+/// Runs OpenCode's own `doctor`-style subcommand in `project_dir`, when the installed
+/// CLI lists one in `--help`.
+#[tauri::command]
+pub fn opencode_self_diagnose(app: AppHandle, project_dir: String) -> Result<ExecResult, String> {
+    let project_dir = validate_project_dir(&app, &project_dir)?;
+
+    let resource_dir = app.path().resource_dir().ok();
+    let current_bin_dir = tauri::process::current_binary(&app.env())
+        .ok()
+        .and_then(|path| path.parent().map(|parent| parent.to_path_buf()));
+    let (program, _in_path, notes) =
+        resolve_engine_path(true, resource_dir.as_deref(), current_bin_dir.as_deref());
+    let Some(program) = program else {
+        let notes_text = notes.join("\n");
+        return Err(format!(
+      "OpenCode CLI not found.\n\nInstall with:\n- brew install anomalyco/tap/opencode\n- curl -fsSL https://opencode.ai/install | bash\n\nNotes:\n{notes_text}"
+    ));
+    };
+
+    let subcommands = opencode_subcommands(program.as_os_str());
+    let Some(subcommand) = SELF_DIAGNOSE_SUBCOMMANDS
+        .iter()
+        .find(|name| subcommands.iter().any(|candidate| candidate == *name))
+    else {
+        return Ok(ExecResult {
+            ok: false,
+            status: -1,
+            stdout: String::new(),
+            stderr: format!(
+                "Self-diagnostics are not supported by this OpenCode version (no {} subcommand).",
+                SELF_DIAGNOSE_SUBCOMMANDS.join("/")
+            ),
+        });
+    };
+
+    let output = command_for_program(&program)
+        .arg(subcommand)
+        .current_dir(&project_dir)
+        .output()
+        .map_err(|e| format!("Failed to run opencode {subcommand}: {e}"))?;
+
+    let status = output.status.code().unwrap_or(-1);
+    Ok(ExecResult {
+        ok: output.status.success(),
+        status,
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+    })
+}
+
+/// Package name for an npm plugin spec (`name`, `name@1.2`, `@scope/name@^1`, `npm:...`);
+/// `None` for local file plugins.
+fn plugin_package_name(spec: &str) -> Option<String> {
+    let spec = spec.trim();
+    let spec = spec.strip_prefix("npm:").unwrap_or(spec);
+    if spec.is_empty()
+        || spec.starts_with("file:")
+        || spec.starts_with('.')
+        || spec.starts_with('/')
+        || spec.starts_with('~')
+        || Path::new(spec).is_absolute()
+    {
+        return None;
+    }
+    let version_at = if let Some(rest) = spec.strip_prefix('@') {
+        rest.find('@').map(|index| index + 1)
+    } else {
+        spec.find('@')
+    };
+    Some(version_at.map_or(spec, |index| &spec[..index]).to_string())
+}
+
+fn local_plugin_path(project_dir: &Path, spec: &str) -> PathBuf {
+    let spec = spec.trim();
+    let spec = spec.strip_prefix("file://").unwrap_or(spec);
+    if let Some(rest) = spec.strip_prefix("~/") {
+        if let Some(home) = home_dir() {
+            return home.join(rest);
+        }
+    }
+    project_dir.join(spec)
+}
+
+/// `node_modules` folders OpenCode resolves plugins from: the project, its `.opencode`
+/// folder, the global config dir, and OpenCode's own install cache.
+fn plugin_module_dirs(project_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![
+        project_dir.join("node_modules"),
+        project_dir.join(".opencode").join("node_modules"),
+    ];
+    if let Ok(global) = read_opencode_config("global", "") {
+        if let Some(parent) = Path::new(&global.path).parent() {
+            dirs.push(parent.join("node_modules"));
+        }
+    }
+    dirs.extend(
+        opencode_cache_candidates()
+            .into_iter()
+            .map(|dir| dir.join("node_modules")),
+    );
+    dirs
+}
+
+/// Lists plugins named in the project's `plugin` config that are not installed anywhere
+/// OpenCode looks. npm entries carry a `package` that can be passed to `opkg_install`.
+#[tauri::command]
+pub fn diagnose_missing_plugins(
+    app: AppHandle,
+    project_dir: String,
+) -> Result<Vec<MissingPlugin>, String> {
+    let project_dir = validate_project_dir(&app, &project_dir)?;
+    let config = read_opencode_config("project", &project_dir.to_string_lossy())?;
+    let Some(content) = config.content else {
+        return Ok(Vec::new());
+    };
+    let value = parse_opencode_config(&content)
+        .map_err(|e| format!("Failed to parse {}: {e}", config.path))?;
+    let specs: Vec<String> = match value.get("plugin") {
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(|s| s.to_string()))
+            .collect(),
+        Some(serde_json::Value::String(spec)) => vec![spec.clone()],
+        _ => Vec::new(),
+    };
+
+    let module_dirs = plugin_module_dirs(&project_dir);
+    let mut missing = Vec::new();
+    for spec in specs {
+        match plugin_package_name(&spec) {
+            Some(package) => {
+                let installed = module_dirs
+                    .iter()
+                    .any(|dir| dir.join(&package).join("package.json").is_file());
+                if !installed {
+                    missing.push(MissingPlugin {
+                        reason: format!("{package} is not installed in any node_modules"),
+                        spec,
+                        package: Some(package),
+                    });
+                }
+            }
+            None => {
+                let path = local_plugin_path(&project_dir, &spec);
+                if !path.exists() {
+                    missing.push(MissingPlugin {
+                        reason: format!("{} does not exist", path.display()),
+                        spec,
+                        package: None,
+                    });
+                }
+            }
+        }
+    }
+    Ok(missing)
+}
+
+/// Reports whether the system clock looks sane; a clock set before the epoch makes
+/// `now_ms` return 0, so new workspaces and templates sort oddly.
+#[tauri::command]
+pub fn system_time_status() -> SystemTimeStatus {
+    let (now_ms, clock_ok) = checked_now_ms();
+    let message = if clock_ok {
+        None
+    } else {
+        let message = format!(
+            "System clock is set before 2024 ({now_ms}ms since the Unix epoch). \
+             Check the date and time settings; new items may sort incorrectly."
+        );
+        println!("[clock] {message}");
+        Some(message)
+    };
+    SystemTimeStatus {
+        now_ms,
+        clock_ok,
+        message,
+    }
+}
+
+#[tauri::command]
+pub fn platform_capabilities(app: AppHandle) -> PlatformCapabilities {
+    let resource_dir = app.path().resource_dir().ok();
+    let current_bin_dir = tauri::process::current_binary(&app.env())
+        .ok()
+        .and_then(|path| path.parent().map(|parent| parent.to_path_buf()));
+    let (sidecar, _notes) =
+        resolve_sidecar_candidate(true, resource_dir.as_deref(), current_bin_dir.as_deref());
+
+    PlatformCapabilities {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        scheduler_supported: scheduler_supported(),
+        guided_install_supported: guided_install_supported(),
+        sidecar_supported: sidecar.is_some(),
+        updater_supported: updater_environment().supported,
+    }
+}
+
+fn cors_origins(args: &[String]) -> Vec<String> {
+    args.iter()
+        .skip_while(|arg| arg.as_str() != "--cors")
+        .nth(1)
+        .map(|value| {
+            value
+                .split(',')
+                .map(|origin| origin.trim().to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Origins the engine and OpenWork server accept, read from the same argument builders
+/// used to spawn them (openwrk passes the engine the same `--cors` value).
+#[tauri::command]
+pub fn list_cors_origins() -> CorsOrigins {
+    let engine_args = build_engine_args("127.0.0.1", 0);
+    let openwork_args = build_openwork_args("127.0.0.1", 0, &[], "", "", None, None);
+    CorsOrigins {
+        engine: cors_origins(&engine_args),
+        openwork_server: cors_origins(&openwork_args),
+    }
+}
+
+#[tauri::command]
+pub fn check_data_dir_writable(app: AppHandle) -> DataDirStatus {
+    check_data_dir_status(&app)
+}
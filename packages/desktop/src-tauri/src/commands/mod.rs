@@ -1,6 +1,8 @@
+pub mod app_data;
 pub mod command_files;
 pub mod config;
 pub mod engine;
+pub mod install;
 pub mod misc;
 pub mod openwrk;
 pub mod openwork_server;
@@ -8,5 +10,6 @@ pub mod opkg;
 pub mod owpenbot;
 pub mod scheduler;
 pub mod skills;
+pub mod templates;
 pub mod updater;
 pub mod workspace;
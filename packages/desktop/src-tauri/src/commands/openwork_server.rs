@@ -1,12 +1,192 @@
-use tauri::State;
+use std::time::Duration;
 
+use tauri::{AppHandle, State};
+
+use crate::engine::manager::EngineManager;
 use crate::openwork_server::manager::OpenworkServerManager;
-use crate::types::OpenworkServerInfo;
+use crate::openwork_server::{resolve_connect_url, rotate_openwork_tokens, start_openwork_server};
+use crate::owpenbot::spawn::resolve_owpenbot_health_port;
+use crate::types::{OpenworkServerClient, OpenworkServerClients, OpenworkServerInfo};
+use crate::utils::qr_png_base64;
+
+const CLIENTS_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(serde::Serialize)]
+pub struct OpenworkServerResetResult {
+    pub stopped: bool,
+}
 
 #[tauri::command]
 pub fn openwork_server_info(manager: State<OpenworkServerManager>) -> OpenworkServerInfo {
-    let mut state = manager.inner.lock().expect("openwork server mutex poisoned");
+    let mut state = manager
+        .inner
+        .lock()
+        .expect("openwork server mutex poisoned");
     OpenworkServerManager::snapshot_locked(&mut state)
 }
 
-// start/stop are handled by engine lifecycle
+/// Starts (or restarts) LAN sharing without touching the engine. The running engine's
+/// connection details are handed to the server; `workspace_paths` defaults to the
+/// engine's project dir.
+#[tauri::command]
+pub fn openwork_server_start(
+    app: AppHandle,
+    manager: State<OpenworkServerManager>,
+    engine_manager: State<EngineManager>,
+    workspace_paths: Vec<String>,
+) -> Result<OpenworkServerInfo, String> {
+    let (project_dir, port, base_url, username, password) = {
+        let state = engine_manager
+            .inner
+            .lock()
+            .map_err(|_| "engine mutex poisoned".to_string())?;
+        (
+            state.project_dir.clone(),
+            state.port,
+            state.base_url.clone(),
+            state.opencode_username.clone(),
+            state.opencode_password.clone(),
+        )
+    };
+
+    let mut workspace_paths: Vec<String> = workspace_paths
+        .into_iter()
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .collect();
+    if workspace_paths.is_empty() {
+        workspace_paths.extend(project_dir.filter(|path| !path.trim().is_empty()));
+    }
+    if workspace_paths.is_empty() {
+        return Err("workspacePaths is required when the engine is not running".to_string());
+    }
+
+    let opencode_connect_url = port.and_then(resolve_connect_url).or(base_url);
+    start_openwork_server(
+        &app,
+        &manager,
+        &workspace_paths,
+        opencode_connect_url.as_deref(),
+        username.as_deref(),
+        password.as_deref(),
+        resolve_owpenbot_health_port().ok(),
+    )
+}
+
+/// Stops LAN sharing; the engine keeps running.
+#[tauri::command]
+pub fn openwork_server_stop(
+    manager: State<OpenworkServerManager>,
+) -> Result<OpenworkServerInfo, String> {
+    let mut state = manager
+        .inner
+        .lock()
+        .map_err(|_| "openwork server mutex poisoned".to_string())?;
+    OpenworkServerManager::stop_locked(&mut state);
+    Ok(OpenworkServerManager::snapshot_locked(&mut state))
+}
+
+/// Respawns the server with new tokens; links shared before the rotation stop working.
+#[tauri::command]
+pub fn openwork_server_rotate_tokens(
+    app: AppHandle,
+    manager: State<OpenworkServerManager>,
+) -> Result<OpenworkServerInfo, String> {
+    rotate_openwork_tokens(&app, &manager)
+}
+
+/// QR code (base64 PNG) for the server's `connect`, `lan` or `mdns` URL. Without a
+/// `kind`, the connect URL is used, then the LAN and mDNS URLs.
+#[tauri::command]
+pub fn openwork_server_qr(
+    manager: State<OpenworkServerManager>,
+    kind: Option<String>,
+) -> Result<String, String> {
+    let info = {
+        let mut state = manager
+            .inner
+            .lock()
+            .map_err(|_| "openwork server mutex poisoned".to_string())?;
+        OpenworkServerManager::snapshot_locked(&mut state)
+    };
+    if !info.running {
+        return Err("OpenWork server is not running".to_string());
+    }
+
+    let url = match kind.as_deref().map(str::trim).unwrap_or("") {
+        "" => info.connect_url.or(info.lan_url).or(info.mdns_url),
+        "connect" => info.connect_url,
+        "lan" => info.lan_url,
+        "mdns" => info.mdns_url,
+        other => return Err(format!("Unknown URL kind: {other}")),
+    };
+    let url = url.ok_or_else(|| "OpenWork server has no shareable URL".to_string())?;
+    qr_png_base64(&url)
+}
+
+fn clients_unavailable(note: impl Into<String>) -> OpenworkServerClients {
+    OpenworkServerClients {
+        clients: Vec::new(),
+        note: Some(note.into()),
+    }
+}
+
+/// Lists the remote clients connected to the LAN-shared server. Older servers without a
+/// `/clients` endpoint, or a server that is not running, yield an empty list and a note.
+#[tauri::command]
+pub fn openwork_server_clients(manager: State<OpenworkServerManager>) -> OpenworkServerClients {
+    let info = {
+        let Ok(mut state) = manager.inner.lock() else {
+            return clients_unavailable("openwork server mutex poisoned");
+        };
+        OpenworkServerManager::snapshot_locked(&mut state)
+    };
+    let (Some(base_url), Some(host_token)) = (info.base_url, info.host_token) else {
+        return clients_unavailable("OpenWork server is not running");
+    };
+
+    let url = format!("{}/clients", base_url.trim_end_matches('/'));
+    let agent = ureq::AgentBuilder::new().timeout(CLIENTS_TIMEOUT).build();
+    let response = match agent
+        .get(&url)
+        .set("Accept", "application/json")
+        .set("X-OpenWork-Host-Token", &host_token)
+        .call()
+    {
+        Ok(response) => response,
+        Err(ureq::Error::Status(404, _)) => {
+            return clients_unavailable("This OpenWork server does not report connected clients")
+        }
+        Err(e) => return clients_unavailable(format!("Failed to reach {url}: {e}")),
+    };
+
+    let payload: serde_json::Value = match response.into_json() {
+        Ok(payload) => payload,
+        Err(e) => return clients_unavailable(format!("Failed to parse clients response: {e}")),
+    };
+    // Accept both a bare array and `{ "clients": [...] }`.
+    let items = payload.get("clients").cloned().unwrap_or(payload);
+    match serde_json::from_value::<Vec<OpenworkServerClient>>(items) {
+        Ok(clients) => OpenworkServerClients {
+            clients,
+            note: None,
+        },
+        Err(e) => clients_unavailable(format!("Unexpected clients response: {e}")),
+    }
+}
+
+/// Stops the managed server and clears its recorded state so the next start spawns a
+/// fresh child. The server keeps no lock or pid files; its only state on disk is the
+/// user's `~/.config/openwork/server.json`, which is left alone.
+#[tauri::command]
+pub fn openwork_server_reset(
+    manager: State<OpenworkServerManager>,
+) -> Result<OpenworkServerResetResult, String> {
+    let mut state = manager
+        .inner
+        .lock()
+        .map_err(|_| "openwork server mutex poisoned".to_string())?;
+    let stopped = OpenworkServerManager::snapshot_locked(&mut state).running;
+    OpenworkServerManager::stop_locked(&mut state);
+    Ok(OpenworkServerResetResult { stopped })
+}
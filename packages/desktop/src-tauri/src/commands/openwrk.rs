@@ -27,9 +27,18 @@ fn resolve_data_dir(manager: &OpenwrkManager) -> String {
         .unwrap_or_else(resolve_openwrk_data_dir)
 }
 
+fn resolve_daemon_base_url(manager: &OpenwrkManager) -> Option<String> {
+    manager
+        .inner
+        .lock()
+        .ok()
+        .and_then(|state| state.daemon_base_url.clone())
+}
+
 fn resolve_base_url(manager: &OpenwrkManager) -> Result<String, String> {
     let data_dir = resolve_data_dir(manager);
-    let status = resolve_openwrk_status(&data_dir, None);
+    let daemon_base_url = resolve_daemon_base_url(manager);
+    let status = resolve_openwrk_status(&data_dir, daemon_base_url.as_deref(), None);
     status
         .daemon
         .map(|daemon| daemon.base_url)
@@ -44,7 +53,8 @@ pub fn openwrk_status(manager: State<OpenwrkManager>) -> OpenwrkStatus {
         .lock()
         .ok()
         .and_then(|state| state.last_stderr.clone());
-    resolve_openwrk_status(&data_dir, last_error)
+    let daemon_base_url = resolve_daemon_base_url(&manager);
+    resolve_openwrk_status(&data_dir, daemon_base_url.as_deref(), last_error)
 }
 
 #[tauri::command]
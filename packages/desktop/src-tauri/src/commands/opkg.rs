@@ -1,9 +1,28 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use tauri::{AppHandle, State};
+use tauri_plugin_shell::ShellExt;
+use walkdir::WalkDir;
+
+use crate::commands::skills::validate_skill_name;
 use crate::fs::copy_dir_recursive;
-use crate::opkg::opkg_install as opkg_install_inner;
-use crate::types::ExecResult;
+use crate::install::{run_install, InstallManager};
+use crate::opkg::{opkg_command, opkg_not_found, parse_opkg_list};
+use crate::paths::git_program;
+use crate::types::{ExecResult, InstallOutcome, OpkgPackageList};
+use crate::utils::truncate_output;
 
-#[tauri::command]
-pub fn opkg_install(project_dir: String, package: String) -> Result<ExecResult, String> {
+const OPKG_PROGRESS_EVENT: &str = "openwork://opkg-progress";
+
+/// Runs `opkg <subcommand> <package>`, streaming output on `openwork://opkg-progress`.
+async fn run_opkg(
+    app: &AppHandle,
+    manager: &InstallManager,
+    project_dir: String,
+    package: String,
+    subcommand: &str,
+) -> Result<InstallOutcome, String> {
     let project_dir = project_dir.trim().to_string();
     if project_dir.is_empty() {
         return Err("projectDir is required".to_string());
@@ -14,7 +33,73 @@ pub fn opkg_install(project_dir: String, package: String) -> Result<ExecResult,
         return Err("package is required".to_string());
     }
 
-    opkg_install_inner(&project_dir, &package)
+    match opkg_command(app, &project_dir, &[subcommand, &package]) {
+        Some(command) => {
+            run_install(app, manager, "opkg", command, Some(OPKG_PROGRESS_EVENT)).await
+        }
+        None => Ok(InstallOutcome {
+            install_id: String::new(),
+            result: opkg_not_found(),
+        }),
+    }
+}
+
+#[tauri::command]
+pub async fn opkg_install(
+    app: AppHandle,
+    manager: State<'_, InstallManager>,
+    project_dir: String,
+    package: String,
+) -> Result<InstallOutcome, String> {
+    run_opkg(&app, &manager, project_dir, package, "install").await
+}
+
+#[tauri::command]
+pub async fn opkg_uninstall(
+    app: AppHandle,
+    manager: State<'_, InstallManager>,
+    project_dir: String,
+    package: String,
+) -> Result<ExecResult, String> {
+    run_opkg(&app, &manager, project_dir, package, "remove")
+        .await
+        .map(|outcome| outcome.result)
+}
+
+/// Lists the workspace's OpenPackage packages. When the CLI output cannot be parsed,
+/// `raw` carries stdout for display instead.
+#[tauri::command]
+pub async fn opkg_list(app: AppHandle, project_dir: String) -> Result<OpkgPackageList, String> {
+    let project_dir = project_dir.trim().to_string();
+    if project_dir.is_empty() {
+        return Err("projectDir is required".to_string());
+    }
+
+    let command =
+        opkg_command(&app, &project_dir, &["list"]).ok_or_else(|| opkg_not_found().stderr)?;
+    let output = command
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run opkg list: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "opkg list failed: {}",
+            truncate_output(stderr.trim(), 2000)
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    Ok(match parse_opkg_list(&stdout) {
+        Some(packages) => OpkgPackageList {
+            packages,
+            raw: None,
+        },
+        None => OpkgPackageList {
+            packages: Vec::new(),
+            raw: Some(stdout),
+        },
+    })
 }
 
 #[tauri::command]
@@ -57,7 +142,7 @@ pub fn import_skill(
         }
     }
 
-    copy_dir_recursive(&src, &dest)?;
+    copy_dir_recursive(&src, &dest, false)?;
 
     Ok(ExecResult {
         ok: true,
@@ -66,3 +151,163 @@ pub fn import_skill(
         stderr: String::new(),
     })
 }
+
+/// Removes the temporary clone however the import exits.
+struct TempClone(PathBuf);
+
+impl Drop for TempClone {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn repo_name(repo_url: &str) -> Option<String> {
+    let name = repo_url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()?
+        .trim_end_matches(".git");
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// `subdir` when given, else the repo root, else the only folder in the repo with a
+/// SKILL.md.
+fn find_cloned_skill_dir(clone: &Path, subdir: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(subdir) = subdir {
+        let unsafe_path = Path::new(subdir)
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir));
+        if unsafe_path {
+            return Err("subdir must be a relative path inside the repository".to_string());
+        }
+        let dir = clone.join(subdir);
+        if !dir.join("SKILL.md").is_file() {
+            return Err(format!("No SKILL.md found in {subdir}"));
+        }
+        return Ok(dir);
+    }
+    if clone.join("SKILL.md").is_file() {
+        return Ok(clone.to_path_buf());
+    }
+
+    let mut found: Vec<PathBuf> = WalkDir::new(clone)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file() && entry.file_name() == "SKILL.md")
+        .filter_map(|entry| entry.path().parent().map(Path::to_path_buf))
+        .collect();
+    match found.len() {
+        0 => Err("No SKILL.md found in the repository".to_string()),
+        1 => Ok(found.remove(0)),
+        _ => {
+            let dirs: Vec<String> = found
+                .iter()
+                .filter_map(|dir| dir.strip_prefix(clone).ok())
+                .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+                .collect();
+            Err(format!(
+                "Repository contains several skills; pass subdir to pick one of: {}",
+                dirs.join(", ")
+            ))
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn import_skill_from_git(
+    app: AppHandle,
+    project_dir: String,
+    repo_url: String,
+    subdir: Option<String>,
+    overwrite: bool,
+) -> Result<ExecResult, String> {
+    let project_dir = project_dir.trim().to_string();
+    if project_dir.is_empty() {
+        return Err("projectDir is required".to_string());
+    }
+
+    let repo_url = repo_url.trim().to_string();
+    if repo_url.is_empty() {
+        return Err("repoUrl is required".to_string());
+    }
+    let subdir = subdir
+        .map(|value| value.trim().trim_matches('/').to_string())
+        .filter(|value| !value.is_empty());
+
+    let git = git_program()
+        .ok_or_else(|| "git was not found on PATH; install git to import skills".to_string())?;
+
+    let clone = TempClone(
+        std::env::temp_dir().join(format!("openwork-skill-git-{}", uuid::Uuid::new_v4())),
+    );
+    let clone_path = clone.0.to_string_lossy().to_string();
+    println!("[skills] clone {repo_url} -> {clone_path}");
+    let output = app
+        .shell()
+        .command(git)
+        .args([
+            "clone",
+            "--depth",
+            "1",
+            "--",
+            repo_url.as_str(),
+            clone_path.as_str(),
+        ])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git clone: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "git clone failed: {}",
+            truncate_output(stderr.trim(), 2000)
+        ));
+    }
+
+    let src = find_cloned_skill_dir(&clone.0, subdir.as_deref())?;
+    let name = if src == clone.0 {
+        repo_name(&repo_url)
+    } else {
+        src.file_name().and_then(|s| s.to_str()).map(str::to_string)
+    }
+    .ok_or_else(|| "Failed to infer skill name from repository".to_string())?;
+    let name = validate_skill_name(&name)
+        .map_err(|e| format!("Inferred skill name \"{name}\" is not usable: {e}"))?;
+
+    let dest = PathBuf::from(&project_dir)
+        .join(".opencode")
+        .join("skills")
+        .join(&name);
+
+    if dest.exists() {
+        if overwrite {
+            fs::remove_dir_all(&dest).map_err(|e| {
+                format!(
+                    "Failed to remove existing skill dir {}: {e}",
+                    dest.display()
+                )
+            })?;
+        } else {
+            return Err(format!("Skill already exists at {}", dest.display()));
+        }
+    }
+
+    if let Err(error) = copy_dir_recursive(&src, &dest, false) {
+        let _ = fs::remove_dir_all(&dest);
+        return Err(error);
+    }
+    let _ = fs::remove_dir_all(dest.join(".git"));
+
+    Ok(ExecResult {
+        ok: true,
+        status: 0,
+        stdout: format!(
+            "Imported skill {name} from {repo_url} to {}",
+            dest.display()
+        ),
+        stderr: String::new(),
+    })
+}
@@ -1,10 +1,12 @@
 use tauri::{AppHandle, State};
 use tauri_plugin_shell::process::CommandEvent;
 
+use crate::owpenbot::log::{append_owpenbot_log, owpenbot_log_file};
 use crate::owpenbot::manager::OwpenbotManager;
 use crate::owpenbot::spawn::{resolve_owpenbot_health_port, spawn_owpenbot, DEFAULT_OWPENBOT_HEALTH_PORT};
+use crate::preferences::log_buffer_chars;
 use crate::types::OwpenbotInfo;
-use crate::utils::truncate_output;
+use crate::utils::{now_ms, qr_png_base64, truncate_output};
 
 /// Check if owpenbot health endpoint is responding on given port
 fn check_health_endpoint(port: u16) -> Option<serde_json::Value> {
@@ -144,12 +146,20 @@ pub fn owpenbot_start(
     state.last_stderr = None;
 
     let state_handle = manager.inner.clone();
+    let log_chars = log_buffer_chars(&app);
+    let log_path = owpenbot_log_file(&app).ok();
+    let log = move |text: &str| {
+        if let Some(path) = log_path.as_ref() {
+            append_owpenbot_log(path, text);
+        }
+    };
 
     tauri::async_runtime::spawn(async move {
         while let Some(event) = rx.recv().await {
             match event {
                 CommandEvent::Stdout(line_bytes) => {
                     let line = String::from_utf8_lossy(&line_bytes).to_string();
+                    log(&line);
                     if let Ok(mut state) = state_handle.try_lock() {
                         let next = state
                             .last_stdout
@@ -157,7 +167,7 @@ pub fn owpenbot_start(
                             .unwrap_or_default()
                             .to_string()
                             + &line;
-                        state.last_stdout = Some(truncate_output(&next, 8000));
+                        state.last_stdout = Some(truncate_output(&next, log_chars));
 
                         // Check for WhatsApp linked status in output
                         if line.contains("WhatsApp linked") {
@@ -167,6 +177,7 @@ pub fn owpenbot_start(
                 }
                 CommandEvent::Stderr(line_bytes) => {
                     let line = String::from_utf8_lossy(&line_bytes).to_string();
+                    log(&line);
                     if let Ok(mut state) = state_handle.try_lock() {
                         let next = state
                             .last_stderr
@@ -174,19 +185,21 @@ pub fn owpenbot_start(
                             .unwrap_or_default()
                             .to_string()
                             + &line;
-                        state.last_stderr = Some(truncate_output(&next, 8000));
+                        state.last_stderr = Some(truncate_output(&next, log_chars));
                     }
                 }
                 CommandEvent::Terminated(payload) => {
+                    log(&format!("Owpenbot exited (code {:?}).\n", payload.code));
                     if let Ok(mut state) = state_handle.try_lock() {
                         state.child_exited = true;
                         if let Some(code) = payload.code {
                             let next = format!("Owpenbot exited (code {code}).");
-                            state.last_stderr = Some(truncate_output(&next, 8000));
+                            state.last_stderr = Some(truncate_output(&next, log_chars));
                         }
                     }
                 }
                 CommandEvent::Error(message) => {
+                    log(&format!("{message}\n"));
                     if let Ok(mut state) = state_handle.try_lock() {
                         state.child_exited = true;
                         let next = state
@@ -195,7 +208,7 @@ pub fn owpenbot_start(
                             .unwrap_or_default()
                             .to_string()
                             + &message;
-                        state.last_stderr = Some(truncate_output(&next, 8000));
+                        state.last_stderr = Some(truncate_output(&next, log_chars));
                     }
                 }
                 _ => {}
@@ -206,6 +219,11 @@ pub fn owpenbot_start(
     Ok(OwpenbotManager::snapshot_locked(&mut state))
 }
 
+#[tauri::command]
+pub fn owpenbot_log_path(app: AppHandle) -> Result<String, String> {
+    Ok(owpenbot_log_file(&app)?.to_string_lossy().to_string())
+}
+
 #[tauri::command]
 pub fn owpenbot_stop(manager: State<OwpenbotManager>) -> Result<OwpenbotInfo, String> {
     let mut state = manager
@@ -219,11 +237,6 @@ pub fn owpenbot_stop(manager: State<OwpenbotManager>) -> Result<OwpenbotInfo, St
 #[tauri::command]
 pub async fn owpenbot_qr(app: AppHandle) -> Result<String, String> {
     use tauri_plugin_shell::ShellExt;
-    use base64::engine::general_purpose;
-    use base64::Engine as _;
-    use image::{DynamicImage, ImageFormat, Luma};
-    use qrcode::QrCode;
-    use std::io::Cursor;
 
     let command = match app.shell().sidecar("owpenbot") {
         Ok(command) => command,
@@ -258,16 +271,7 @@ pub async fn owpenbot_qr(app: AppHandle) -> Result<String, String> {
     }
 
     let qr_data = response.qr.ok_or_else(|| "No QR code returned".to_string())?;
-    let code = QrCode::new(qr_data.as_bytes()).map_err(|e| format!("Failed to encode QR: {e}"))?;
-    let image = code
-        .render::<Luma<u8>>()
-        .min_dimensions(256, 256)
-        .build();
-    let mut buffer = Vec::new();
-    DynamicImage::ImageLuma8(image)
-        .write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
-        .map_err(|e| format!("Failed to encode QR image: {e}"))?;
-    Ok(general_purpose::STANDARD.encode(buffer))
+    qr_png_base64(&qr_data)
 }
 
 #[tauri::command]
@@ -369,6 +373,10 @@ pub async fn owpenbot_config_set(
     key: String,
     value: String,
 ) -> Result<(), String> {
+    set_owpenbot_config(&app, &key, &value).await
+}
+
+async fn set_owpenbot_config(app: &AppHandle, key: &str, value: &str) -> Result<(), String> {
     use tauri_plugin_shell::ShellExt;
 
     let command = match app.shell().sidecar("owpenbot") {
@@ -377,7 +385,7 @@ pub async fn owpenbot_config_set(
     };
 
     let output = command
-        .args(["config", "set", &key, &value])
+        .args(["config", "set", key, value])
         .output()
         .await
         .map_err(|e| format!("Failed to set config: {e}"))?;
@@ -390,6 +398,89 @@ pub async fn owpenbot_config_set(
     Ok(())
 }
 
+/// Config keys safe to carry across installs. WhatsApp session credentials and the
+/// Telegram token are deliberately absent and must be re-linked after an import.
+const OWPENBOT_PORTABLE_KEYS: &[&str] = &[
+    "groupsEnabled",
+    "channels.whatsapp.dmPolicy",
+    "channels.whatsapp.allowFrom",
+    "channels.whatsapp.selfChatMode",
+    "channels.telegram.enabled",
+];
+
+/// Writes the allow-list, DM policy and other non-secret owpenbot settings to `dest_path`.
+#[tauri::command]
+pub async fn owpenbot_export_state(
+    app: AppHandle,
+    dest_path: String,
+) -> Result<Vec<String>, String> {
+    let dest_path = dest_path.trim().to_string();
+    if dest_path.is_empty() {
+        return Err("destPath is required".to_string());
+    }
+
+    let config = owpenbot_json(&app, &["config", "get", "--json"], "read config").await?;
+    let mut settings = serde_json::Map::new();
+    for key in OWPENBOT_PORTABLE_KEYS {
+        let value = key
+            .split('.')
+            .try_fold(&config, |value, segment| value.get(segment));
+        if let Some(value) = value.filter(|value| !value.is_null()) {
+            settings.insert(key.to_string(), value.clone());
+        }
+    }
+
+    let exported: Vec<String> = settings.keys().cloned().collect();
+    let payload = serde_json::json!({
+        "version": 1,
+        "exportedAt": now_ms(),
+        "note": "WhatsApp sessions and the Telegram token are not exported. Re-link them after importing.",
+        "settings": settings,
+    });
+    let contents = serde_json::to_string_pretty(&payload).map_err(|e| e.to_string())?;
+    std::fs::write(&dest_path, contents)
+        .map_err(|e| format!("Failed to write {dest_path}: {e}"))?;
+
+    Ok(exported)
+}
+
+/// Re-applies settings written by `owpenbot_export_state` via `config set`. Unknown keys
+/// in the file are ignored.
+#[tauri::command]
+pub async fn owpenbot_import_state(
+    app: AppHandle,
+    src_path: String,
+) -> Result<Vec<String>, String> {
+    let src_path = src_path.trim().to_string();
+    if src_path.is_empty() {
+        return Err("srcPath is required".to_string());
+    }
+
+    let raw = std::fs::read_to_string(&src_path)
+        .map_err(|e| format!("Failed to read {src_path}: {e}"))?;
+    let payload: serde_json::Value =
+        serde_json::from_str(&raw).map_err(|e| format!("Failed to parse {src_path}: {e}"))?;
+    let settings = payload
+        .get("settings")
+        .and_then(|value| value.as_object())
+        .ok_or_else(|| format!("{src_path} has no settings to import"))?;
+
+    let mut applied = Vec::new();
+    for key in OWPENBOT_PORTABLE_KEYS {
+        let Some(value) = settings.get(*key) else {
+            continue;
+        };
+        let value = match value {
+            serde_json::Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        set_owpenbot_config(&app, key, &value).await?;
+        applied.push(key.to_string());
+    }
+
+    Ok(applied)
+}
+
 #[tauri::command]
 pub async fn owpenbot_pairing_list(app: AppHandle) -> Result<serde_json::Value, String> {
     owpenbot_json(&app, &["pairing", "list", "--json"], "list pairing requests").await
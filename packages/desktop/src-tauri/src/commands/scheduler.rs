@@ -2,18 +2,28 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::engine::manager::EngineManager;
+use crate::engine::paths::resolve_opencode_executable;
+use crate::fs::write_atomic;
 use crate::paths::home_dir;
-use crate::types::ScheduledJob;
+use crate::types::{
+  EngineRuntime, ExecResult, ParsedSchedule, ScheduledJob, ScheduledUnit, ScheduledUnitReport,
+  SchedulerRepairResult,
+};
+use crate::utils::truncate_output;
+use chrono::{DateTime, Datelike, Duration, Local, SecondsFormat, Timelike, Utc};
+use serde_json::{json, Map, Value};
+use tauri::State;
 
-fn scheduler_supported() -> bool {
-  cfg!(target_os = "macos") || cfg!(target_os = "linux")
+pub fn scheduler_supported() -> bool {
+  cfg!(target_os = "macos") || cfg!(target_os = "linux") || cfg!(windows)
 }
 
 fn require_scheduler_support() -> Result<(), String> {
   if scheduler_supported() {
     return Ok(());
   }
-  Err("Scheduler is supported only on macOS and Linux.".to_string())
+  Err("Scheduler is supported only on macOS, Linux, and Windows.".to_string())
 }
 
 fn opencode_jobs_dir() -> Result<PathBuf, String> {
@@ -112,6 +122,616 @@ fn delete_job_file(jobs_dir: &Path, slug: &str) -> Result<(), String> {
   Ok(())
 }
 
+const SUPPORTED_SCHEDULE_PHRASES: &[&str] = &[
+  "hourly",
+  "daily",
+  "every morning",
+  "every evening",
+  "weekdays",
+  "weekly",
+  "every day at 9am",
+  "weekdays at 9:30am",
+  "every monday at 17:00",
+];
+
+const WEEKDAY_NAMES: &[&str] = &[
+  "sunday",
+  "monday",
+  "tuesday",
+  "wednesday",
+  "thursday",
+  "friday",
+  "saturday",
+];
+
+// Expands one cron field (`*`, `*/n`, `a`, `a-b`, `a-b/n` and comma lists) into its values.
+fn expand_cron_field(value: &str, min: u32, max: u32) -> Result<Vec<u32>, String> {
+  let invalid = || format!("Invalid cron field \"{value}\"");
+  let mut values = Vec::new();
+  for part in value.split(',') {
+    let (range, step) = match part.split_once('/') {
+      Some((range, step)) => {
+        let step = step.parse::<u32>().map_err(|_| invalid())?;
+        if step == 0 {
+          return Err(invalid());
+        }
+        (range, Some(step))
+      }
+      None => (part, None),
+    };
+    let (start, end) = if range == "*" {
+      (min, max)
+    } else if let Some((start, end)) = range.split_once('-') {
+      (
+        start.parse::<u32>().map_err(|_| invalid())?,
+        end.parse::<u32>().map_err(|_| invalid())?,
+      )
+    } else {
+      let start = range.parse::<u32>().map_err(|_| invalid())?;
+      (start, if step.is_some() { max } else { start })
+    };
+    if start < min || end > max || start > end {
+      return Err(format!("Cron field \"{value}\" is out of range"));
+    }
+    values.extend((start..=end).step_by(step.unwrap_or(1) as usize));
+  }
+  values.sort_unstable();
+  values.dedup();
+  Ok(values)
+}
+
+struct CronMatcher {
+  minutes: Vec<u32>,
+  hours: Vec<u32>,
+  days: Vec<u32>,
+  months: Vec<u32>,
+  weekdays: Vec<u32>,
+  day_restricted: bool,
+  weekday_restricted: bool,
+}
+
+fn parse_cron_matcher(schedule: &str) -> Result<CronMatcher, String> {
+  let fields: Vec<&str> = schedule.split_whitespace().collect();
+  let [minute, hour, day, month, weekday] = fields.as_slice() else {
+    return Err(format!(
+      "Cron expressions need 5 fields (minute hour day month weekday), got \"{schedule}\""
+    ));
+  };
+  Ok(CronMatcher {
+    minutes: expand_cron_field(minute, 0, 59)?,
+    hours: expand_cron_field(hour, 0, 23)?,
+    days: expand_cron_field(day, 1, 31)?,
+    months: expand_cron_field(month, 1, 12)?,
+    weekdays: {
+      let mut weekdays: Vec<u32> = expand_cron_field(weekday, 0, 7)?
+        .into_iter()
+        .map(|value| value % 7)
+        .collect();
+      weekdays.sort_unstable();
+      weekdays.dedup();
+      weekdays
+    },
+    day_restricted: !day.starts_with('*'),
+    weekday_restricted: !weekday.starts_with('*'),
+  })
+}
+
+/// Restricted day-of-month and day-of-week values for one installed trigger.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+type DayTrigger<'a> = (Option<&'a [u32]>, Option<&'a [u32]>);
+
+/// The field's values, or `None` when they cover its whole range (i.e. it acts like `*`).
+fn restricted_values(values: &[u32], min: u32, max: u32) -> Option<&[u32]> {
+  if values.len() as u32 == max - min + 1 {
+    None
+  } else {
+    Some(values)
+  }
+}
+
+impl CronMatcher {
+  fn minutes(&self) -> Option<&[u32]> {
+    restricted_values(&self.minutes, 0, 59)
+  }
+
+  fn hours(&self) -> Option<&[u32]> {
+    restricted_values(&self.hours, 0, 23)
+  }
+
+  fn days(&self) -> Option<&[u32]> {
+    restricted_values(&self.days, 1, 31)
+  }
+
+  fn months(&self) -> Option<&[u32]> {
+    restricted_values(&self.months, 1, 12)
+  }
+
+  fn weekdays(&self) -> Option<&[u32]> {
+    restricted_values(&self.weekdays, 0, 6)
+  }
+
+  /// Day-of-month/day-of-week pairs to install. OS schedulers AND these fields, so
+  /// cron's "either one" rule for two restricted fields becomes two separate triggers.
+  #[cfg(any(target_os = "macos", target_os = "linux"))]
+  fn day_triggers(&self) -> Vec<DayTrigger<'_>> {
+    if self.day_restricted && self.weekday_restricted {
+      vec![(self.days(), None), (None, self.weekdays())]
+    } else {
+      vec![(self.days(), self.weekdays())]
+    }
+  }
+
+  fn matches(&self, at: &DateTime<Local>) -> bool {
+    let day = self.days.contains(&at.day());
+    let weekday = self.weekdays.contains(&at.weekday().num_days_from_sunday());
+    // Like cron, a restricted day-of-month and day-of-week match if either one does.
+    let day_matches = if self.day_restricted && self.weekday_restricted {
+      day || weekday
+    } else {
+      day && weekday
+    };
+    day_matches
+      && self.minutes.contains(&at.minute())
+      && self.hours.contains(&at.hour())
+      && self.months.contains(&at.month())
+  }
+
+  fn next_run_after(&self, from: DateTime<Local>) -> Option<DateTime<Local>> {
+    let mut candidate = from.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+    // Scan minute by minute; a year plus a day covers every valid five-field schedule
+    // except Feb 29 ones, which are rare enough to report without a next run.
+    for _ in 0..(366 * 24 * 60) {
+      if self.matches(&candidate) {
+        return Some(candidate);
+      }
+      candidate += Duration::minutes(1);
+    }
+    None
+  }
+}
+
+fn parse_time_of_day(value: &str) -> Option<(u32, u32)> {
+  let value = value.trim();
+  match value {
+    "noon" => return Some((12, 0)),
+    "midnight" => return Some((0, 0)),
+    _ => {}
+  }
+  let (clock, meridiem) = if let Some(clock) = value.strip_suffix("am") {
+    (clock.trim(), Some(false))
+  } else if let Some(clock) = value.strip_suffix("pm") {
+    (clock.trim(), Some(true))
+  } else {
+    (value, None)
+  };
+  let (hour, minute) = match clock.split_once(':') {
+    Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+    None => (clock.parse::<u32>().ok()?, 0),
+  };
+  if minute > 59 {
+    return None;
+  }
+  let hour = match meridiem {
+    Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+    Some(_) => return None,
+    None if hour <= 23 => hour,
+    None => return None,
+  };
+  Some((hour, minute))
+}
+
+fn natural_schedule_to_cron(input: &str) -> Option<String> {
+  let normalized = input
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ")
+    .to_lowercase();
+  let fixed = match normalized.as_str() {
+    "hourly" | "every hour" => Some("0 * * * *"),
+    "daily" | "every day" | "every morning" => Some("0 9 * * *"),
+    "every evening" => Some("0 18 * * *"),
+    "weekdays" | "every weekday" => Some("0 9 * * 1-5"),
+    "weekly" | "every week" => Some("0 9 * * 1"),
+    _ => None,
+  };
+  if let Some(fixed) = fixed {
+    return Some(fixed.to_string());
+  }
+
+  let (days, time) = normalized.split_once(" at ")?;
+  let weekday = match days {
+    "daily" | "every day" | "every morning" | "every evening" => "*".to_string(),
+    "weekdays" | "every weekday" => "1-5".to_string(),
+    "weekends" | "every weekend" => "0,6".to_string(),
+    _ => {
+      let name = days.strip_prefix("every ")?.trim_end_matches('s');
+      WEEKDAY_NAMES
+        .iter()
+        .position(|day| *day == name)?
+        .to_string()
+    }
+  };
+  let (hour, minute) = parse_time_of_day(time)?;
+  Some(format!("{minute} {hour} * * {weekday}"))
+}
+
+fn job_run_args(job: &ScheduledJob) -> Vec<String> {
+  let mut args = vec!["run".to_string()];
+  let run = job.run.as_ref();
+  let mut push_flag = |flag: &str, value: Option<&String>| {
+    if let Some(value) = value.filter(|value| !value.trim().is_empty()) {
+      args.push(flag.to_string());
+      args.push(value.clone());
+    }
+  };
+
+  push_flag("--command", run.and_then(|run| run.command.as_ref()));
+  push_flag("--agent", run.and_then(|run| run.agent.as_ref()));
+  push_flag("--model", run.and_then(|run| run.model.as_ref()));
+  push_flag("--variant", run.and_then(|run| run.variant.as_ref()));
+  push_flag("--title", run.and_then(|run| run.title.as_ref()));
+  push_flag("--session", run.and_then(|run| run.session.as_ref()));
+  push_flag("--format", run.and_then(|run| run.run_format.as_ref()));
+  push_flag(
+    "--attach",
+    run
+      .and_then(|run| run.attach_url.as_ref())
+      .or(job.attach_url.as_ref()),
+  );
+
+  if let Some(port) = run.and_then(|run| run.port) {
+    args.push("--port".to_string());
+    args.push(port.to_string());
+  }
+  for file in run.and_then(|run| run.files.as_ref()).into_iter().flatten() {
+    args.push("--file".to_string());
+    args.push(file.clone());
+  }
+  if run.and_then(|run| run.share).unwrap_or(false) {
+    args.push("--share".to_string());
+  }
+  if run.and_then(|run| run.continue_flag).unwrap_or(false) {
+    args.push("--continue".to_string());
+  }
+
+  let message = run
+    .and_then(|run| run.arguments.as_ref().or(run.prompt.as_ref()))
+    .or(job.prompt.as_ref());
+  if let Some(message) = message {
+    args.push(message.clone());
+  }
+
+  args
+}
+
+fn job_program_args(job: &ScheduledJob) -> Result<Vec<String>, String> {
+  let (program, _in_path, _notes) = resolve_opencode_executable();
+  let program = program.ok_or_else(|| "OpenCode CLI not found.".to_string())?;
+  let mut args = vec![program.to_string_lossy().to_string()];
+  args.extend(job_run_args(job));
+  Ok(args)
+}
+
+#[cfg(target_os = "macos")]
+fn escape_xml(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+// Keeps an expansion like `*/5 */2 * * 1-5` from producing a plist launchd chokes on.
+#[cfg(target_os = "macos")]
+const MAX_LAUNCHD_INTERVALS: usize = 1000;
+
+/// One `StartCalendarInterval` entry per combination of the restricted fields' values;
+/// unrestricted fields are left out, which launchd treats as a wildcard.
+#[cfg(target_os = "macos")]
+fn launchd_intervals(matcher: &CronMatcher) -> Result<Vec<Vec<(&'static str, u32)>>, String> {
+  let mut intervals = Vec::new();
+  for (days, weekdays) in matcher.day_triggers() {
+    let mut partial: Vec<Vec<(&'static str, u32)>> = vec![Vec::new()];
+    for (key, values) in [
+      ("Minute", matcher.minutes()),
+      ("Hour", matcher.hours()),
+      ("Day", days),
+      ("Month", matcher.months()),
+      ("Weekday", weekdays),
+    ] {
+      let Some(values) = values else {
+        continue;
+      };
+      partial = partial
+        .into_iter()
+        .flat_map(|entry| {
+          values.iter().map(move |value| {
+            let mut entry = entry.clone();
+            entry.push((key, *value));
+            entry
+          })
+        })
+        .collect();
+      if intervals.len() + partial.len() > MAX_LAUNCHD_INTERVALS {
+        return Err(format!(
+          "Schedule expands to more than {MAX_LAUNCHD_INTERVALS} launchd intervals"
+        ));
+      }
+    }
+    intervals.extend(partial);
+  }
+  Ok(intervals)
+}
+
+#[cfg(target_os = "macos")]
+fn install_job(job: &ScheduledJob) -> Result<(), String> {
+  let Some(home) = home_dir() else {
+    return Err("Failed to resolve home directory".to_string());
+  };
+
+  let intervals = launchd_intervals(&parse_cron_matcher(&job.schedule)?)?;
+  let label = format!("com.opencode.job.{}", job.slug);
+  let dir = home.join("Library").join("LaunchAgents");
+  fs::create_dir_all(&dir).map_err(|e| format!("Failed to create LaunchAgents: {e}"))?;
+  let plist = dir.join(format!("{label}.plist"));
+
+  let program_args = job_program_args(job)?
+    .iter()
+    .map(|arg| format!("    <string>{}</string>\n", escape_xml(arg)))
+    .collect::<String>();
+  let intervals = intervals
+    .iter()
+    .map(|entry| {
+      let keys = entry
+        .iter()
+        .map(|(key, value)| format!("      <key>{key}</key>\n      <integer>{value}</integer>\n"))
+        .collect::<String>();
+      format!("    <dict>\n{keys}    </dict>\n")
+    })
+    .collect::<String>();
+  let workdir = job
+    .workdir
+    .as_ref()
+    .map(|dir| {
+      format!(
+        "  <key>WorkingDirectory</key>\n  <string>{}</string>\n",
+        escape_xml(dir)
+      )
+    })
+    .unwrap_or_default();
+
+  let contents = format!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n<plist version=\"1.0\">\n<dict>\n  <key>Label</key>\n  <string>{label}</string>\n  <key>ProgramArguments</key>\n  <array>\n{program_args}  </array>\n{workdir}  <key>StartCalendarInterval</key>\n  <array>\n{intervals}  </array>\n</dict>\n</plist>\n"
+  );
+  fs::write(&plist, contents).map_err(|e| format!("Failed to write plist: {e}"))?;
+
+  let _ = Command::new("launchctl").arg("unload").arg(&plist).output();
+  Command::new("launchctl")
+    .arg("load")
+    .arg(&plist)
+    .output()
+    .map_err(|e| format!("Failed to load plist: {e}"))?;
+  Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn quote_systemd_arg(value: &str) -> String {
+  let escaped = value
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('%', "%%");
+  format!("\"{escaped}\"")
+}
+
+/// `OnCalendar=` values for the schedule, using systemd's comma lists for each field.
+#[cfg(target_os = "linux")]
+fn systemd_on_calendar(matcher: &CronMatcher) -> Vec<String> {
+  const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+  let list = |values: Option<&[u32]>| match values {
+    Some(values) => values
+      .iter()
+      .map(|value| format!("{value:02}"))
+      .collect::<Vec<_>>()
+      .join(","),
+    None => "*".to_string(),
+  };
+  matcher
+    .day_triggers()
+    .into_iter()
+    .map(|(days, weekdays)| {
+      let weekday = weekdays
+        .map(|days| {
+          let names = days
+            .iter()
+            .map(|day| WEEKDAYS[*day as usize])
+            .collect::<Vec<_>>();
+          format!("{} ", names.join(","))
+        })
+        .unwrap_or_default();
+      format!(
+        "{weekday}*-{}-{} {}:{}:00",
+        list(matcher.months()),
+        list(days),
+        list(matcher.hours()),
+        list(matcher.minutes())
+      )
+    })
+    .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn install_job(job: &ScheduledJob) -> Result<(), String> {
+  let Some(home) = home_dir() else {
+    return Err("Failed to resolve home directory".to_string());
+  };
+
+  let on_calendar = systemd_on_calendar(&parse_cron_matcher(&job.schedule)?)
+    .iter()
+    .map(|value| format!("OnCalendar={value}\n"))
+    .collect::<String>();
+  let base = home.join(".config").join("systemd").join("user");
+  fs::create_dir_all(&base).map_err(|e| format!("Failed to create systemd user dir: {e}"))?;
+  let service = base.join(format!("opencode-job-{}.service", job.slug));
+  let timer = base.join(format!("opencode-job-{}.timer", job.slug));
+
+  let exec_start = job_program_args(job)?
+    .iter()
+    .map(|arg| quote_systemd_arg(arg))
+    .collect::<Vec<_>>()
+    .join(" ");
+  let workdir = job
+    .workdir
+    .as_ref()
+    .map(|dir| format!("WorkingDirectory={dir}\n"))
+    .unwrap_or_default();
+  let service_contents = format!(
+    "[Unit]\nDescription=OpenCode job {}\n\n[Service]\nType=oneshot\n{workdir}ExecStart={exec_start}\n",
+    job.name
+  );
+
+  let timer_contents = format!(
+    "[Unit]\nDescription=OpenCode job {} timer\n\n[Timer]\n{on_calendar}Persistent=true\n\n[Install]\nWantedBy=timers.target\n",
+    job.name
+  );
+
+  fs::write(&service, service_contents).map_err(|e| format!("Failed to write service: {e}"))?;
+  fs::write(&timer, timer_contents).map_err(|e| format!("Failed to write timer: {e}"))?;
+
+  let timer_unit = format!("opencode-job-{}.timer", job.slug);
+  let _ = Command::new("systemctl").args(["--user", "daemon-reload"]).output();
+  command_status(
+    Command::new("systemctl").args(["--user", "enable", "--now", timer_unit.as_str()]),
+    "enable timer",
+  )
+}
+
+#[cfg(windows)]
+fn schtasks() -> Command {
+  use std::os::windows::process::CommandExt;
+  const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+  let mut command = Command::new("schtasks");
+  command.creation_flags(CREATE_NO_WINDOW);
+  command
+}
+
+#[cfg(windows)]
+fn windows_task_script(slug: &str) -> Result<PathBuf, String> {
+  let Some(home) = home_dir() else {
+    return Err("Failed to resolve home directory".to_string());
+  };
+  Ok(
+    home
+      .join(".config")
+      .join("opencode")
+      .join("scheduler")
+      .join(format!("opencode-job-{slug}.cmd")),
+  )
+}
+
+// Task Scheduler triggers are coarser than cron, so only the shapes that map onto a
+// single /SC trigger are accepted.
+#[cfg(windows)]
+fn schtasks_schedule_args(matcher: &CronMatcher) -> Result<Vec<String>, String> {
+  const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+  const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+  ];
+  let unsupported = || "Schedule is not supported by Windows Task Scheduler".to_string();
+  let at = |hour: u32, minute: u32| format!("{hour:02}:{minute:02}");
+  let names = |values: &[u32], names: &[&str], offset: u32| {
+    values
+      .iter()
+      .map(|value| names[(value - offset) as usize])
+      .collect::<Vec<_>>()
+      .join(",")
+  };
+
+  let args = match (
+    matcher.minutes(),
+    matcher.hours(),
+    matcher.days(),
+    matcher.months(),
+    matcher.weekdays(),
+  ) {
+    (None, None, None, None, None) => vec!["/SC".into(), "MINUTE".into(), "/MO".into(), "1".into()],
+    (Some(&[minute]), None, None, None, None) => {
+      vec!["/SC".into(), "HOURLY".into(), "/ST".into(), at(0, minute)]
+    }
+    (Some(&[minute]), Some(&[hour]), None, None, None) => {
+      vec!["/SC".into(), "DAILY".into(), "/ST".into(), at(hour, minute)]
+    }
+    (Some(&[minute]), Some(&[hour]), None, None, Some(weekdays)) => vec![
+      "/SC".into(),
+      "WEEKLY".into(),
+      "/D".into(),
+      names(weekdays, &WEEKDAYS, 0),
+      "/ST".into(),
+      at(hour, minute),
+    ],
+    (Some(&[minute]), Some(&[hour]), Some(&[day]), months, None) => {
+      let mut args = vec!["/SC".into(), "MONTHLY".into()];
+      if let Some(months) = months {
+        args.push("/M".into());
+        args.push(names(months, &MONTHS, 1));
+      }
+      args.extend(["/D".into(), day.to_string(), "/ST".into(), at(hour, minute)]);
+      args
+    }
+    _ => return Err(unsupported()),
+  };
+  Ok(args)
+}
+
+#[cfg(windows)]
+fn quote_cmd_arg(value: &str) -> String {
+  // A .cmd line cannot span lines, so multi-line prompts are flattened.
+  let flattened = value.replace("\r\n", " ").replace(['\r', '\n'], " ");
+  format!("\"{}\"", flattened.replace('"', "\\\"").replace('%', "%%"))
+}
+
+#[cfg(windows)]
+fn install_job(job: &ScheduledJob) -> Result<(), String> {
+  let schedule_args = schtasks_schedule_args(&parse_cron_matcher(&job.schedule)?)?;
+  let script = windows_task_script(&job.slug)?;
+  if let Some(parent) = script.parent() {
+    fs::create_dir_all(parent).map_err(|e| format!("Failed to create scheduler dir: {e}"))?;
+  }
+
+  let command_line = job_program_args(job)?
+    .iter()
+    .map(|arg| quote_cmd_arg(arg))
+    .collect::<Vec<_>>()
+    .join(" ");
+  let workdir = job
+    .workdir
+    .as_ref()
+    .map(|dir| format!("cd /d {}\r\n", quote_cmd_arg(dir)))
+    .unwrap_or_default();
+  let contents = format!("@echo off\r\n{workdir}{command_line}\r\n");
+  fs::write(&script, contents).map_err(|e| format!("Failed to write task script: {e}"))?;
+
+  let task_name = format!("opencode-job-{}", job.slug);
+  let output = schtasks()
+    .args(["/Create", "/F", "/TN", task_name.as_str(), "/TR"])
+    .arg(format!("\"{}\"", script.display()))
+    .args(&schedule_args)
+    .output()
+    .map_err(|e| format!("Failed to run schtasks: {e}"))?;
+  if !output.status.success() {
+    return Err(format!(
+      "Failed to create scheduled task: {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    ));
+  }
+  Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn install_job(_job: &ScheduledJob) -> Result<(), String> {
+  Err("Scheduler is supported only on macOS, Linux, and Windows.".to_string())
+}
+
 #[cfg(target_os = "macos")]
 fn uninstall_job(slug: &str) -> Result<(), String> {
   let Some(home) = home_dir() else {
@@ -161,16 +781,273 @@ fn uninstall_job(slug: &str) -> Result<(), String> {
   Ok(())
 }
 
-#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+#[cfg(windows)]
+fn uninstall_job(slug: &str) -> Result<(), String> {
+  let task_name = format!("opencode-job-{slug}");
+  // Deleting a task that is already gone fails; that is fine here.
+  let _ = schtasks()
+    .args(["/Delete", "/F", "/TN", task_name.as_str()])
+    .output();
+
+  let script = windows_task_script(slug)?;
+  if script.exists() {
+    fs::remove_file(&script).map_err(|e| format!("Failed to remove task script: {e}"))?;
+  }
+  Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
 fn uninstall_job(_slug: &str) -> Result<(), String> {
-  Err("Scheduler is supported only on macOS and Linux.".to_string())
+  Err("Scheduler is supported only on macOS, Linux, and Windows.".to_string())
+}
+
+fn command_status(command: &mut Command, action: &str) -> Result<(), String> {
+  let output = command
+    .output()
+    .map_err(|e| format!("Failed to {action}: {e}"))?;
+  if output.status.success() {
+    return Ok(());
+  }
+  Err(format!(
+    "Failed to {action}: {}",
+    String::from_utf8_lossy(&output.stderr).trim()
+  ))
+}
+
+// `-w` persists the disabled override so launchd does not reload the job at login.
+#[cfg(target_os = "macos")]
+fn set_unit_enabled(slug: &str, enabled: bool) -> Result<(), String> {
+  let Some(home) = home_dir() else {
+    return Err("Failed to resolve home directory".to_string());
+  };
+
+  let plist = home
+    .join("Library")
+    .join("LaunchAgents")
+    .join(format!("com.opencode.job.{slug}.plist"));
+  if !plist.is_file() {
+    return Err(format!("Job \"{slug}\" is not installed."));
+  }
+  let action = if enabled { "load" } else { "unload" };
+  command_status(
+    Command::new("launchctl").args([action, "-w"]).arg(&plist),
+    &format!("{action} plist"),
+  )
+}
+
+#[cfg(target_os = "linux")]
+fn set_unit_enabled(slug: &str, enabled: bool) -> Result<(), String> {
+  let timer_unit = format!("opencode-job-{slug}.timer");
+  let action = if enabled { "enable" } else { "disable" };
+  command_status(
+    Command::new("systemctl").args(["--user", action, "--now", timer_unit.as_str()]),
+    &format!("{action} timer"),
+  )
+}
+
+#[cfg(windows)]
+fn set_unit_enabled(slug: &str, enabled: bool) -> Result<(), String> {
+  let task_name = format!("opencode-job-{slug}");
+  let flag = if enabled { "/ENABLE" } else { "/DISABLE" };
+  command_status(
+    schtasks().args(["/Change", "/TN", task_name.as_str(), flag]),
+    "update scheduled task",
+  )
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn set_unit_enabled(_slug: &str, _enabled: bool) -> Result<(), String> {
+  Err("Scheduler is supported only on macOS, Linux, and Windows.".to_string())
+}
+
+// Installing always arms the unit, so paused jobs are switched back off afterwards.
+fn sync_job_unit(job: &ScheduledJob) -> Result<(), String> {
+  install_job(job)?;
+  if !job.enabled {
+    set_unit_enabled(&job.slug, false)?;
+  }
+  Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn list_installed_units() -> Result<Vec<ScheduledUnit>, String> {
+  let Some(home) = home_dir() else {
+    return Err("Failed to resolve home directory".to_string());
+  };
+
+  let dir = home.join("Library").join("LaunchAgents");
+  if !dir.is_dir() {
+    return Ok(Vec::new());
+  }
+
+  let mut units = Vec::new();
+  for entry in fs::read_dir(&dir).map_err(|e| format!("Failed to read LaunchAgents: {e}"))? {
+    let entry = entry.map_err(|e| e.to_string())?;
+    let path = entry.path();
+    let Some(label) = path
+      .file_name()
+      .and_then(|name| name.to_str())
+      .and_then(|name| name.strip_suffix(".plist"))
+    else {
+      continue;
+    };
+    let Some(slug) = label.strip_prefix("com.opencode.job.") else {
+      continue;
+    };
+
+    let enabled = Command::new("launchctl")
+      .args(["list", label])
+      .output()
+      .map(|output| output.status.success())
+      .unwrap_or(false);
+
+    units.push(ScheduledUnit {
+      slug: slug.to_string(),
+      label: label.to_string(),
+      path: path.to_string_lossy().to_string(),
+      enabled,
+      has_job: false,
+    });
+  }
+
+  Ok(units)
+}
+
+#[cfg(target_os = "linux")]
+fn list_installed_units() -> Result<Vec<ScheduledUnit>, String> {
+  let Some(home) = home_dir() else {
+    return Err("Failed to resolve home directory".to_string());
+  };
+
+  let dir = home.join(".config").join("systemd").join("user");
+  if !dir.is_dir() {
+    return Ok(Vec::new());
+  }
+
+  let mut units = Vec::new();
+  for entry in fs::read_dir(&dir).map_err(|e| format!("Failed to read systemd user dir: {e}"))? {
+    let entry = entry.map_err(|e| e.to_string())?;
+    let path = entry.path();
+    let Some(label) = path.file_name().and_then(|name| name.to_str()) else {
+      continue;
+    };
+    let Some(slug) = label
+      .strip_prefix("opencode-job-")
+      .and_then(|rest| rest.strip_suffix(".timer"))
+    else {
+      continue;
+    };
+
+    let enabled = Command::new("systemctl")
+      .args(["--user", "is-enabled", label])
+      .output()
+      .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "enabled")
+      .unwrap_or(false);
+
+    units.push(ScheduledUnit {
+      slug: slug.to_string(),
+      label: label.to_string(),
+      path: path.to_string_lossy().to_string(),
+      enabled,
+      has_job: false,
+    });
+  }
+
+  Ok(units)
+}
+
+#[cfg(windows)]
+fn list_installed_units() -> Result<Vec<ScheduledUnit>, String> {
+  let output = schtasks()
+    .args(["/Query", "/FO", "CSV", "/NH"])
+    .output()
+    .map_err(|e| format!("Failed to run schtasks: {e}"))?;
+  if !output.status.success() {
+    return Err(format!(
+      "Failed to query scheduled tasks: {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    ));
+  }
+
+  let mut units: Vec<ScheduledUnit> = Vec::new();
+  for line in String::from_utf8_lossy(&output.stdout).lines() {
+    // Rows look like "\opencode-job-<slug>","<next run>","<status>".
+    let fields: Vec<&str> = line
+      .split("\",\"")
+      .map(|field| field.trim().trim_matches('"'))
+      .collect();
+    let Some(label) = fields.first().map(|name| name.trim_start_matches('\\')) else {
+      continue;
+    };
+    let Some(slug) = label.strip_prefix("opencode-job-") else {
+      continue;
+    };
+    // A task with several triggers is listed once per trigger.
+    if units.iter().any(|unit| unit.slug == slug) {
+      continue;
+    }
+
+    let enabled = fields
+      .get(2)
+      .is_some_and(|status| !status.eq_ignore_ascii_case("Disabled"));
+    units.push(ScheduledUnit {
+      slug: slug.to_string(),
+      label: label.to_string(),
+      path: windows_task_script(slug)?.to_string_lossy().to_string(),
+      enabled,
+      has_job: false,
+    });
+  }
+
+  Ok(units)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn list_installed_units() -> Result<Vec<ScheduledUnit>, String> {
+  Err("Scheduler is supported only on macOS, Linux, and Windows.".to_string())
+}
+
+fn installed_unit_report(jobs_dir: &Path) -> Result<ScheduledUnitReport, String> {
+  let jobs = load_all_jobs(jobs_dir)?;
+  let mut units = list_installed_units()?;
+  for unit in units.iter_mut() {
+    unit.has_job = jobs.iter().any(|job| job.slug == unit.slug);
+  }
+  units.sort_by(|a, b| a.slug.cmp(&b.slug));
+
+  let orphaned_jobs = jobs
+    .iter()
+    .filter(|job| !units.iter().any(|unit| unit.slug == job.slug))
+    .map(|job| job.slug.clone())
+    .collect();
+
+  Ok(ScheduledUnitReport {
+    units,
+    orphaned_jobs,
+  })
+}
+
+// The installed units are generated from the same cron string, so it is the single
+// source for the next fire time on every platform.
+fn next_run_ms(job: &ScheduledJob) -> Option<u64> {
+  if !job.enabled {
+    return None;
+  }
+  let matcher = parse_cron_matcher(&job.schedule).ok()?;
+  matcher
+    .next_run_after(Local::now())
+    .map(|at| at.timestamp_millis() as u64)
 }
 
 #[tauri::command]
 pub fn scheduler_list_jobs() -> Result<Vec<ScheduledJob>, String> {
   require_scheduler_support()?;
   let jobs_dir = opencode_jobs_dir()?;
-  load_all_jobs(&jobs_dir)
+  let mut jobs = load_all_jobs(&jobs_dir)?;
+  for job in jobs.iter_mut() {
+    job.next_run_ms = next_run_ms(job);
+  }
+  Ok(jobs)
 }
 
 #[tauri::command]
@@ -189,3 +1066,428 @@ pub fn scheduler_delete_job(name: String) -> Result<ScheduledJob, String> {
   delete_job_file(&jobs_dir, &job.slug)?;
   Ok(job)
 }
+
+// Checks that the schedule can actually be installed here, not just that it is cron.
+fn validate_schedule(schedule: &str) -> Result<(), String> {
+  #[cfg(target_os = "macos")]
+  launchd_intervals(&parse_cron_matcher(schedule)?)?;
+  #[cfg(windows)]
+  schtasks_schedule_args(&parse_cron_matcher(schedule)?)?;
+  #[cfg(not(any(target_os = "macos", windows)))]
+  parse_cron_matcher(schedule)?;
+  Ok(())
+}
+
+fn set_job_prompt(job: &mut ScheduledJob, prompt: &str) {
+  job.prompt = Some(prompt.to_string());
+  if let Some(run) = job.run.as_mut().filter(|run| run.prompt.is_some()) {
+    run.prompt = Some(prompt.to_string());
+  }
+}
+
+fn write_job_file(jobs_dir: &Path, job: &ScheduledJob) -> Result<(), String> {
+  fs::create_dir_all(jobs_dir).map_err(|e| format!("Failed to create jobs dir: {e}"))?;
+  let path = jobs_dir.join(format!("{}.json", job.slug));
+  let serialized = serde_json::to_string_pretty(job).map_err(|e| e.to_string())?;
+  write_atomic(&path, format!("{serialized}\n").as_bytes())
+}
+
+/// Edits an existing job file as raw JSON so fields this app doesn't model, such as
+/// ones the opencode-scheduler plugin writes, survive the rewrite.
+fn edit_job_file(
+  jobs_dir: &Path,
+  slug: &str,
+  edit: impl FnOnce(&mut Map<String, Value>),
+) -> Result<(), String> {
+  let path = jobs_dir.join(format!("{slug}.json"));
+  let raw =
+    fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+  let mut value: Value =
+    serde_json::from_str(&raw).map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+  let Some(object) = value.as_object_mut() else {
+    return Err(format!("{} is not a JSON object", path.display()));
+  };
+  edit(object);
+
+  let serialized = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+  write_atomic(&path, format!("{serialized}\n").as_bytes())
+}
+
+/// Mirrors [`set_job_prompt`] onto the raw job JSON.
+fn insert_job_prompt(object: &mut Map<String, Value>, job: &ScheduledJob) {
+  object.insert("prompt".to_string(), json!(job.prompt));
+  let run_prompt = job.run.as_ref().and_then(|run| run.prompt.as_deref());
+  if let (Some(run), Some(prompt)) = (
+    object.get_mut("run").and_then(Value::as_object_mut),
+    run_prompt,
+  ) {
+    run.insert("prompt".to_string(), json!(prompt));
+  }
+}
+
+/// Writes the job JSON and installs its launchd plist, systemd timer, or scheduled task.
+/// Calling it again with a name that slugifies the same way updates the job in place.
+#[tauri::command]
+pub fn scheduler_create_job(
+  name: String,
+  prompt: String,
+  schedule: String,
+  workspace_path: String,
+) -> Result<ScheduledJob, String> {
+  require_scheduler_support()?;
+  let name = name.trim();
+  let prompt = prompt.trim();
+  let schedule = schedule.split_whitespace().collect::<Vec<_>>().join(" ");
+  let workspace_path = workspace_path.trim();
+  if name.is_empty() {
+    return Err("name is required".to_string());
+  }
+  if prompt.is_empty() {
+    return Err("prompt is required".to_string());
+  }
+  if workspace_path.is_empty() {
+    return Err("workspacePath is required".to_string());
+  }
+  let slug = slugify(name);
+  if slug.is_empty() {
+    return Err(format!("Job name \"{name}\" has no usable characters."));
+  }
+  validate_schedule(&schedule)?;
+  if !Path::new(workspace_path).is_dir() {
+    return Err(format!("{workspace_path} is not a directory"));
+  }
+
+  let jobs_dir = opencode_jobs_dir()?;
+  let existing = load_job_by_slug(&jobs_dir, &slug);
+  let now = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+  let is_new = existing.is_none();
+  let job = match existing {
+    Some(mut job) => {
+      job.name = name.to_string();
+      job.schedule = schedule;
+      set_job_prompt(&mut job, prompt);
+      job.workdir = Some(workspace_path.to_string());
+      job.updated_at = Some(now);
+      job
+    }
+    None => ScheduledJob {
+      slug: slug.clone(),
+      name: name.to_string(),
+      schedule,
+      prompt: Some(prompt.to_string()),
+      attach_url: None,
+      run: None,
+      source: None,
+      workdir: Some(workspace_path.to_string()),
+      created_at: now,
+      updated_at: None,
+      last_run_at: None,
+      last_run_exit_code: None,
+      last_run_error: None,
+      last_run_source: None,
+      last_run_status: None,
+      enabled: true,
+      next_run_ms: None,
+    },
+  };
+
+  if is_new {
+    write_job_file(&jobs_dir, &job)?;
+  } else {
+    edit_job_file(&jobs_dir, &slug, |object| {
+      object.insert("name".to_string(), json!(job.name));
+      object.insert("schedule".to_string(), json!(job.schedule));
+      insert_job_prompt(object, &job);
+      object.insert("workdir".to_string(), json!(job.workdir));
+      object.insert("updatedAt".to_string(), json!(job.updated_at));
+    })?;
+  }
+
+  if let Err(error) = sync_job_unit(&job) {
+    if is_new {
+      let _ = delete_job_file(&jobs_dir, &slug);
+    }
+    return Err(error);
+  }
+  Ok(job)
+}
+
+fn record_last_run(jobs_dir: &Path, slug: &str, result: &ExecResult) -> Result<(), String> {
+  let error = if result.ok {
+    Value::Null
+  } else {
+    json!(truncate_output(result.stderr.trim(), 2000))
+  };
+  edit_job_file(jobs_dir, slug, |object| {
+    object.insert(
+      "lastRunAt".to_string(),
+      json!(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)),
+    );
+    object.insert("lastRunExitCode".to_string(), json!(result.status));
+    object.insert("lastRunError".to_string(), error);
+    object.insert("lastRunSource".to_string(), json!("manual"));
+    object.insert(
+      "lastRunStatus".to_string(),
+      json!(if result.ok { "success" } else { "failed" }),
+    );
+  })
+}
+
+/// Edits a job's schedule and/or prompt in place, keeping its run history. The OS-level
+/// unit is only rewritten when one of them changes, since both are baked into it.
+#[tauri::command]
+pub fn scheduler_update_job(
+  name: String,
+  new_schedule: Option<String>,
+  new_prompt: Option<String>,
+) -> Result<ScheduledJob, String> {
+  require_scheduler_support()?;
+  let jobs_dir = opencode_jobs_dir()?;
+  let trimmed = name.trim();
+  if trimmed.is_empty() {
+    return Err("name is required".to_string());
+  }
+  let mut job =
+    find_job_by_name(&jobs_dir, trimmed).ok_or_else(|| format!("Job \"{trimmed}\" not found."))?;
+
+  let schedule = new_schedule
+    .map(|value| value.split_whitespace().collect::<Vec<_>>().join(" "))
+    .filter(|value| !value.is_empty());
+  let prompt = new_prompt
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty());
+  if let Some(schedule) = schedule.as_deref() {
+    validate_schedule(schedule)?;
+  }
+
+  let mut changed = false;
+  if let Some(schedule) = schedule.filter(|schedule| *schedule != job.schedule) {
+    job.schedule = schedule;
+    changed = true;
+  }
+  if let Some(prompt) = prompt.filter(|prompt| job.prompt.as_deref() != Some(prompt.as_str())) {
+    set_job_prompt(&mut job, &prompt);
+    changed = true;
+  }
+  if !changed {
+    return Ok(job);
+  }
+
+  job.updated_at = Some(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true));
+  edit_job_file(&jobs_dir, &job.slug, |object| {
+    object.insert("schedule".to_string(), json!(job.schedule));
+    insert_job_prompt(object, &job);
+    object.insert("updatedAt".to_string(), json!(job.updated_at));
+  })?;
+  sync_job_unit(&job)?;
+  Ok(job)
+}
+
+/// Pauses or resumes a job's OS-level timer while keeping its JSON file. The flag is
+/// stored in the job so it stays paused across restarts and repairs.
+#[tauri::command]
+pub fn scheduler_toggle_job(name: String, enabled: bool) -> Result<ScheduledJob, String> {
+  require_scheduler_support()?;
+  let jobs_dir = opencode_jobs_dir()?;
+  let trimmed = name.trim();
+  if trimmed.is_empty() {
+    return Err("name is required".to_string());
+  }
+  let mut job =
+    find_job_by_name(&jobs_dir, trimmed).ok_or_else(|| format!("Job \"{trimmed}\" not found."))?;
+
+  set_unit_enabled(&job.slug, enabled)?;
+  if job.enabled != enabled {
+    job.enabled = enabled;
+    job.updated_at = Some(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true));
+    edit_job_file(&jobs_dir, &job.slug, |object| {
+      object.insert("enabled".to_string(), json!(enabled));
+      object.insert("updatedAt".to_string(), json!(job.updated_at));
+    })?;
+  }
+  Ok(job)
+}
+
+/// Runs a job immediately with the same `opencode run` arguments its timer uses,
+/// attached to the running engine unless the job names its own attach URL.
+#[tauri::command]
+pub async fn scheduler_run_job_now(
+  manager: State<'_, EngineManager>,
+  name: String,
+) -> Result<ExecResult, String> {
+  require_scheduler_support()?;
+  let jobs_dir = opencode_jobs_dir()?;
+  let trimmed = name.trim();
+  if trimmed.is_empty() {
+    return Err("name is required".to_string());
+  }
+  let mut job =
+    find_job_by_name(&jobs_dir, trimmed).ok_or_else(|| format!("Job \"{trimmed}\" not found."))?;
+
+  let info = {
+    let mut state = manager
+      .inner
+      .lock()
+      .map_err(|_| "engine mutex poisoned".to_string())?;
+    EngineManager::snapshot_locked(&mut state)
+  };
+  let running = info.running || (info.runtime == EngineRuntime::Openwrk && info.base_url.is_some());
+  let Some(base_url) = info.base_url.filter(|_| running) else {
+    return Err(
+      "OpenCode engine is not running. Start the engine before running a job.".to_string(),
+    );
+  };
+
+  let has_attach_url = job
+    .run
+    .as_ref()
+    .and_then(|run| run.attach_url.as_ref())
+    .or(job.attach_url.as_ref())
+    .is_some_and(|url| !url.trim().is_empty());
+  if !has_attach_url {
+    job.attach_url = Some(base_url);
+  }
+
+  let args = job_program_args(&job)?;
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let mut command = Command::new(&args[0]);
+    command.args(&args[1..]);
+    if let Some(dir) = job.workdir.as_ref().filter(|dir| !dir.trim().is_empty()) {
+      command.current_dir(dir);
+    }
+    if let Some(username) = info.opencode_username.as_ref() {
+      command.env("OPENCODE_SERVER_USERNAME", username);
+    }
+    if let Some(password) = info.opencode_password.as_ref() {
+      command.env("OPENCODE_SERVER_PASSWORD", password);
+    }
+
+    let result = match command.output() {
+      Ok(output) => ExecResult {
+        ok: output.status.success(),
+        status: output.status.code().unwrap_or(-1),
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+      },
+      Err(e) => ExecResult {
+        ok: false,
+        status: -1,
+        stdout: String::new(),
+        stderr: format!("Failed to run opencode: {e}"),
+      },
+    };
+
+    if let Err(error) = record_last_run(&jobs_dir, &job.slug, &result) {
+      println!(
+        "[scheduler] failed to record last run for {}: {error}",
+        job.slug
+      );
+    }
+    result
+  })
+  .await
+  .map_err(|e| format!("Job run failed: {e}"))?;
+
+  Ok(result)
+}
+
+/// Accepts a cron expression or one of a few natural phrases and returns the canonical
+/// cron string with its next run in local time. The result is checked the same way
+/// `scheduler_create_job` checks schedules, so it can be installed as-is.
+#[tauri::command]
+pub fn scheduler_parse_schedule(input: String) -> Result<ParsedSchedule, String> {
+  let input = input.trim();
+  if input.is_empty() {
+    return Err("schedule is required".to_string());
+  }
+
+  let looks_like_cron = input.split_whitespace().count() == 5
+    && input
+      .chars()
+      .all(|c| c.is_ascii_digit() || c.is_whitespace() || "*/,-".contains(c));
+  let cron = if looks_like_cron {
+    input.split_whitespace().collect::<Vec<_>>().join(" ")
+  } else {
+    natural_schedule_to_cron(input).ok_or_else(|| {
+      format!(
+        "Unrecognized schedule \"{input}\". Use a 5-field cron expression or one of: {}",
+        SUPPORTED_SCHEDULE_PHRASES.join(", ")
+      )
+    })?
+  };
+
+  // Same check as create/update, so a parsed schedule is one this platform can install.
+  validate_schedule(&cron)?;
+  let matcher = parse_cron_matcher(&cron)?;
+  let next_run_at = matcher
+    .next_run_after(Local::now())
+    .map(|at| at.timestamp_millis() as u64);
+  Ok(ParsedSchedule { cron, next_run_at })
+}
+
+#[tauri::command]
+pub fn scheduler_list_installed_units() -> Result<ScheduledUnitReport, String> {
+  require_scheduler_support()?;
+  let jobs_dir = opencode_jobs_dir()?;
+  installed_unit_report(&jobs_dir)
+}
+
+#[tauri::command]
+pub fn scheduler_repair() -> Result<SchedulerRepairResult, String> {
+  require_scheduler_support()?;
+  let jobs_dir = opencode_jobs_dir()?;
+  let report = installed_unit_report(&jobs_dir)?;
+  let mut result = SchedulerRepairResult {
+    reinstalled: Vec::new(),
+    removed: Vec::new(),
+    errors: Vec::new(),
+  };
+
+  for slug in report.orphaned_jobs {
+    let Some(job) = load_job_by_slug(&jobs_dir, &slug) else {
+      continue;
+    };
+    match sync_job_unit(&job) {
+      Ok(()) => result.reinstalled.push(slug),
+      Err(error) => result.errors.push(format!("{slug}: {error}")),
+    }
+  }
+
+  for unit in report.units.into_iter().filter(|unit| !unit.has_job) {
+    match uninstall_job(&unit.slug) {
+      Ok(()) => result.removed.push(unit.slug),
+      Err(error) => result.errors.push(format!("{}: {error}", unit.slug)),
+    }
+  }
+
+  Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn natural_phrases_are_installable() {
+    let extra = [
+      "every hour",
+      "weekends at 10am",
+      "every friday at noon",
+      "every day at 6:15pm",
+    ];
+    for phrase in SUPPORTED_SCHEDULE_PHRASES.iter().chain(extra.iter()) {
+      let cron =
+        natural_schedule_to_cron(phrase).unwrap_or_else(|| panic!("\"{phrase}\" did not parse"));
+      validate_schedule(&cron).unwrap_or_else(|e| panic!("\"{phrase}\" -> {cron}: {e}"));
+    }
+  }
+
+  #[test]
+  fn cron_fields_expand_ranges_steps_and_lists() {
+    assert_eq!(expand_cron_field("1-5", 0, 7).unwrap(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(expand_cron_field("*/20", 0, 59).unwrap(), vec![0, 20, 40]);
+    assert_eq!(expand_cron_field("5,1,5", 0, 59).unwrap(), vec![1, 5]);
+    assert!(expand_cron_field("60", 0, 59).is_err());
+    assert!(expand_cron_field("mon", 0, 7).is_err());
+  }
+}
@@ -1,8 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
 
+use crate::fs::{copy_dir_recursive, io_error_message, write_atomic};
 use crate::paths::{candidate_xdg_config_dirs, home_dir};
 use crate::types::ExecResult;
 
@@ -26,8 +28,7 @@ fn ensure_project_skill_root(project_dir: &str) -> Result<PathBuf, String> {
         })?;
     }
 
-    fs::create_dir_all(&modern)
-        .map_err(|e| format!("Failed to create {}: {e}", modern.display()))?;
+    fs::create_dir_all(&modern).map_err(|e| io_error_message("create", &modern, &e))?;
     Ok(modern)
 }
 
@@ -61,14 +62,16 @@ fn collect_project_skill_roots(project_dir: &Path) -> Vec<PathBuf> {
     roots
 }
 
+fn global_opencode_skill_roots() -> Vec<PathBuf> {
+    candidate_xdg_config_dirs()
+        .into_iter()
+        .map(|dir| dir.join("opencode").join("skills"))
+        .filter(|root| root.is_dir())
+        .collect()
+}
+
 fn collect_global_skill_roots() -> Vec<PathBuf> {
-    let mut roots = Vec::new();
-    for dir in candidate_xdg_config_dirs() {
-        let opencode_root = dir.join("opencode").join("skills");
-        if opencode_root.is_dir() {
-            roots.push(opencode_root);
-        }
-    }
+    let mut roots = global_opencode_skill_roots();
 
     if let Some(home) = home_dir() {
         let claude_root = home.join(".claude").join("skills");
@@ -80,6 +83,38 @@ fn collect_global_skill_roots() -> Vec<PathBuf> {
     roots
 }
 
+fn ensure_global_skill_root() -> Result<PathBuf, String> {
+    if let Some(existing) = global_opencode_skill_roots().into_iter().next() {
+        return Ok(existing);
+    }
+
+    let base = candidate_xdg_config_dirs()
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Unable to resolve config directory".to_string())?;
+    let root = base.join("opencode").join("skills");
+    fs::create_dir_all(&root).map_err(|e| format!("Failed to create {}: {e}", root.display()))?;
+    Ok(root)
+}
+
+/// Renames the skill folder when both roots share a filesystem. Otherwise it is copied,
+/// following links that point outside it so their contents survive the move, and a
+/// failed copy is removed so the move can be retried.
+fn move_skill_dir(src: &Path, dest: &Path) -> Result<(), String> {
+    if dest.exists() {
+        return Err(format!("Skill already exists at {}", dest.display()));
+    }
+    if fs::rename(src, dest).is_ok() {
+        return Ok(());
+    }
+
+    if let Err(error) = copy_dir_recursive(src, dest, true) {
+        let _ = fs::remove_dir_all(dest);
+        return Err(error);
+    }
+    fs::remove_dir_all(src).map_err(|e| format!("Failed to remove {}: {e}", src.display()))
+}
+
 fn collect_skill_roots(project_dir: &str) -> Result<Vec<PathBuf>, String> {
     let project_dir = project_dir.trim();
     if project_dir.is_empty() {
@@ -103,7 +138,14 @@ fn collect_skill_roots(project_dir: &str) -> Result<Vec<PathBuf>, String> {
     Ok(unique)
 }
 
-fn validate_skill_name(name: &str) -> Result<String, String> {
+/// Disabled skills are parked in a sibling `<root>-disabled` folder (e.g.
+/// `.opencode/skills-disabled`), outside every directory OpenCode scans.
+fn disabled_skill_root(root: &Path) -> Option<PathBuf> {
+    let name = root.file_name()?.to_str()?;
+    Some(root.with_file_name(format!("{name}-disabled")))
+}
+
+pub fn validate_skill_name(name: &str) -> Result<String, String> {
     let trimmed = name.trim();
     if trimmed.is_empty() {
         return Err("skill name is required".to_string());
@@ -162,9 +204,19 @@ fn gather_skills(
 #[serde(rename_all = "camelCase")]
 pub struct LocalSkillCard {
     pub name: String,
+    pub display_name: Option<String>,
     pub path: String,
     pub description: Option<String>,
     pub trigger: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillContent {
+    pub name: String,
+    pub path: String,
+    pub content: String,
 }
 
 fn extract_frontmatter_value(raw: &str, keys: &[&str]) -> Option<String> {
@@ -206,6 +258,80 @@ fn extract_frontmatter_value(raw: &str, keys: &[&str]) -> Option<String> {
     None
 }
 
+// Rewrites the `name:` line of the frontmatter, keeping everything else byte for byte.
+fn replace_frontmatter_name(raw: &str, name: &str) -> Option<String> {
+    let mut lines = raw.split_inclusive('\n');
+    let first = lines.next()?;
+    if first.trim() != "---" {
+        return None;
+    }
+
+    let mut output = first.to_string();
+    let mut replaced = false;
+    let mut closed = false;
+    for line in lines.by_ref() {
+        let trimmed = line.trim();
+        if trimmed == "---" {
+            output.push_str(line);
+            closed = true;
+            break;
+        }
+        let is_name = !replaced
+            && trimmed
+                .split_once(':')
+                .is_some_and(|(key, _)| key.trim().eq_ignore_ascii_case("name"));
+        if is_name {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let ending = if line.ends_with("\r\n") {
+                "\r\n"
+            } else if line.ends_with('\n') {
+                "\n"
+            } else {
+                ""
+            };
+            output.push_str(&format!("{indent}name: {name}{ending}"));
+            replaced = true;
+        } else {
+            output.push_str(line);
+        }
+    }
+
+    if !replaced || !closed {
+        return None;
+    }
+    output.extend(lines);
+    Some(output)
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillValidation {
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+fn frontmatter_closed(raw: &str) -> bool {
+    let mut lines = raw.lines();
+    if lines.next().map(str::trim) != Some("---") {
+        return false;
+    }
+    lines.any(|line| line.trim() == "---")
+}
+
+fn skill_body(raw: &str) -> &str {
+    let Some(rest) = raw.trim_start().strip_prefix("---") else {
+        return raw;
+    };
+    match rest.find("\n---") {
+        Some(index) => {
+            let after = &rest[index + 4..];
+            after.split_once('\n').map(|(_, body)| body).unwrap_or("")
+        }
+        None => "",
+    }
+}
+
 fn extract_trigger(raw: &str) -> Option<String> {
     if let Some(frontmatter) = extract_frontmatter_value(raw, &["trigger", "when"]) {
         return Some(frontmatter);
@@ -283,6 +409,15 @@ fn extract_description(raw: &str) -> Option<String> {
     None
 }
 
+/// Declared `name` and `description` from the frontmatter, falling back to the first
+/// body line for skills written without one.
+fn skill_card_metadata(raw: &str) -> (Option<String>, Option<String>) {
+    let name = extract_frontmatter_value(raw, &["name"]);
+    let description =
+        extract_frontmatter_value(raw, &["description"]).or_else(|| extract_description(raw));
+    (name, description)
+}
+
 #[tauri::command]
 pub fn list_local_skills(project_dir: String) -> Result<Vec<LocalSkillCard>, String> {
     let project_dir = project_dir.trim();
@@ -293,26 +428,39 @@ pub fn list_local_skills(project_dir: String) -> Result<Vec<LocalSkillCard>, Str
     let skill_roots = collect_skill_roots(project_dir)?;
     let mut found: Vec<PathBuf> = Vec::new();
     let mut seen = HashSet::new();
-    for root in skill_roots {
-        gather_skills(&root, &mut seen, &mut found)?;
+    for root in &skill_roots {
+        gather_skills(root, &mut seen, &mut found)?;
+    }
+    let mut disabled: Vec<PathBuf> = Vec::new();
+    for root in skill_roots
+        .iter()
+        .filter_map(|root| disabled_skill_root(root))
+    {
+        gather_skills(&root, &mut seen, &mut disabled)?;
     }
 
+    let entries = found
+        .into_iter()
+        .map(|path| (path, true))
+        .chain(disabled.into_iter().map(|path| (path, false)));
     let mut out = Vec::new();
-    for path in found {
+    for (path, enabled) in entries {
         let Some(name) = path.file_name().and_then(|s| s.to_str()) else {
             continue;
         };
 
-        let (description, trigger) = match fs::read_to_string(path.join("SKILL.md")) {
-            Ok(raw) => (extract_description(&raw), extract_trigger(&raw)),
-            Err(_) => (None, None),
-        };
+        let raw = fs::read_to_string(path.join("SKILL.md")).ok();
+        let (display_name, description) =
+            raw.as_deref().map(skill_card_metadata).unwrap_or_default();
+        let trigger = raw.as_deref().and_then(extract_trigger);
 
         out.push(LocalSkillCard {
             name: name.to_string(),
+            display_name,
             path: path.to_string_lossy().to_string(),
             description,
             trigger,
+            enabled,
         });
     }
 
@@ -320,12 +468,46 @@ pub fn list_local_skills(project_dir: String) -> Result<Vec<LocalSkillCard>, Str
     Ok(out)
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillFile {
+    pub relative_path: String,
+    pub content: String,
+}
+
+/// Checks an extra skill file path stays inside the skill directory.
+fn validate_skill_file_path(relative_path: &str) -> Result<PathBuf, String> {
+    let relative = PathBuf::from(relative_path.trim());
+    if relative.as_os_str().is_empty() {
+        return Err("file relativePath is required".to_string());
+    }
+    if relative.is_absolute()
+        || relative.components().any(|component| {
+            matches!(
+                component,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_)
+            )
+        })
+    {
+        return Err(format!(
+            "{relative_path} must stay inside the skill directory"
+        ));
+    }
+    if relative == Path::new("SKILL.md") {
+        return Err("SKILL.md is written from content; do not pass it in files".to_string());
+    }
+    Ok(relative)
+}
+
+/// Installs a project skill from `content` (its SKILL.md) plus optional extra `files`
+/// such as references or scripts, given relative to the skill directory.
 #[tauri::command]
 pub fn install_skill_template(
     project_dir: String,
     name: String,
     content: String,
     overwrite: bool,
+    files: Option<Vec<SkillFile>>,
 ) -> Result<ExecResult, String> {
     let project_dir = project_dir.trim();
     if project_dir.is_empty() {
@@ -333,6 +515,12 @@ pub fn install_skill_template(
     }
 
     let name = validate_skill_name(&name)?;
+    // Validate every path before touching an existing install.
+    let files = files
+        .unwrap_or_default()
+        .into_iter()
+        .map(|file| Ok((validate_skill_file_path(&file.relative_path)?, file.content)))
+        .collect::<Result<Vec<_>, String>>()?;
     let skill_root = ensure_project_skill_root(project_dir)?;
     let dest = skill_root.join(&name);
 
@@ -354,9 +542,17 @@ pub fn install_skill_template(
         }
     }
 
-    fs::create_dir_all(&dest).map_err(|e| format!("Failed to create {}: {e}", dest.display()))?;
-    fs::write(dest.join("SKILL.md"), content)
-        .map_err(|e| format!("Failed to write SKILL.md: {e}"))?;
+    fs::create_dir_all(&dest).map_err(|e| io_error_message("create", &dest, &e))?;
+    let skill_path = dest.join("SKILL.md");
+    fs::write(&skill_path, content).map_err(|e| io_error_message("write", &skill_path, &e))?;
+    for (relative, file_content) in files {
+        let file_path = dest.join(relative);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| io_error_message("create", parent, &e))?;
+        }
+        fs::write(&file_path, file_content)
+            .map_err(|e| io_error_message("write", &file_path, &e))?;
+    }
 
     Ok(ExecResult {
         ok: true,
@@ -377,7 +573,11 @@ pub fn uninstall_skill(project_dir: String, name: String) -> Result<ExecResult,
     let skill_roots = collect_skill_roots(project_dir)?;
     let mut removed = false;
 
-    for root in skill_roots {
+    let disabled_roots: Vec<PathBuf> = skill_roots
+        .iter()
+        .filter_map(|root| disabled_skill_root(root))
+        .collect();
+    for root in skill_roots.into_iter().chain(disabled_roots) {
         let dest = root.join(&name);
         if !dest.exists() {
             continue;
@@ -404,3 +604,420 @@ pub fn uninstall_skill(project_dir: String, name: String) -> Result<ExecResult,
         stderr: String::new(),
     })
 }
+
+/// Returns the raw `SKILL.md` of the first skill named `name` across the project and
+/// global roots (disabled skills included), for previewing or editing.
+#[tauri::command]
+pub fn skill_read(project_dir: String, name: String) -> Result<SkillContent, String> {
+    let name = validate_skill_name(&name)?;
+    let roots = collect_skill_roots(&project_dir)?;
+    let disabled_roots: Vec<PathBuf> = roots
+        .iter()
+        .filter_map(|root| disabled_skill_root(root))
+        .collect();
+    let path = roots
+        .into_iter()
+        .chain(disabled_roots)
+        .map(|root| root.join(&name).join("SKILL.md"))
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| format!("Skill {name} not found"))?;
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+
+    Ok(SkillContent {
+        name,
+        path: path.to_string_lossy().to_string(),
+        content,
+    })
+}
+
+/// Turns a skill off without uninstalling it by moving it to the disabled folder next to
+/// its skill root, or moves it back when `enabled` is set.
+#[tauri::command]
+pub fn skill_set_enabled(
+    project_dir: String,
+    name: String,
+    enabled: bool,
+) -> Result<ExecResult, String> {
+    let name = validate_skill_name(&name)?;
+    let roots = collect_skill_roots(&project_dir)?;
+
+    let found = roots.iter().find_map(|root| {
+        let active = root.join(&name);
+        let disabled = disabled_skill_root(root)?.join(&name);
+        if active.join("SKILL.md").is_file() {
+            Some((active, disabled, true))
+        } else if disabled.join("SKILL.md").is_file() {
+            Some((disabled, active, false))
+        } else {
+            None
+        }
+    });
+    let Some((src, dest, currently_enabled)) = found else {
+        return Err(format!("Skill {name} not found"));
+    };
+    let state = if enabled { "enabled" } else { "disabled" };
+    if currently_enabled == enabled {
+        return Ok(ExecResult {
+            ok: true,
+            status: 0,
+            stdout: format!("Skill {name} is already {state}"),
+            stderr: String::new(),
+        });
+    }
+
+    if dest.exists() {
+        return Err(format!("Skill already exists at {}", dest.display()));
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| io_error_message("create", parent, &e))?;
+    }
+    fs::rename(&src, &dest).map_err(|e| io_error_message("move", &src, &e))?;
+
+    Ok(ExecResult {
+        ok: true,
+        status: 0,
+        stdout: format!("Skill {name} {state}"),
+        stderr: String::new(),
+    })
+}
+
+/// Renames a skill directory within its root and updates the frontmatter `name`.
+/// Disabled skills are renamed in place, and their names count as taken. Returns the
+/// new skill directory.
+#[tauri::command]
+pub fn skill_rename(
+    project_dir: String,
+    old_name: String,
+    new_name: String,
+) -> Result<String, String> {
+    let old_name = validate_skill_name(&old_name)?;
+    let new_name = validate_skill_name(&new_name)?;
+    if old_name == new_name {
+        return Err("new name must differ from the current name".to_string());
+    }
+
+    let mut roots = collect_skill_roots(&project_dir)?;
+    let disabled_roots: Vec<PathBuf> = roots
+        .iter()
+        .filter_map(|root| disabled_skill_root(root))
+        .collect();
+    roots.extend(disabled_roots);
+    if let Some(existing) = roots
+        .iter()
+        .map(|root| root.join(&new_name))
+        .find(|candidate| candidate.exists())
+    {
+        return Err(format!("Skill already exists at {}", existing.display()));
+    }
+
+    let src = roots
+        .iter()
+        .map(|root| root.join(&old_name))
+        .find(|candidate| candidate.join("SKILL.md").is_file())
+        .ok_or_else(|| format!("Skill {old_name} not found"))?;
+    let dest = src.with_file_name(&new_name);
+    fs::rename(&src, &dest).map_err(|e| io_error_message("rename", &src, &e))?;
+
+    let skill_path = dest.join("SKILL.md");
+    let updated = fs::read_to_string(&skill_path)
+        .map_err(|e| format!("Failed to read {}: {e}", skill_path.display()))
+        .map(|raw| replace_frontmatter_name(&raw, &new_name));
+    let result = match updated {
+        Ok(Some(content)) => write_atomic(&skill_path, content.as_bytes()),
+        Ok(None) => Ok(()),
+        Err(error) => Err(error),
+    };
+    if let Err(error) = result {
+        // Put the directory back so a failed rename leaves the skill untouched.
+        let _ = fs::rename(&dest, &src);
+        return Err(error);
+    }
+
+    Ok(dest.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub fn skill_promote(project_dir: String, name: String) -> Result<ExecResult, String> {
+    let project_dir = project_dir.trim();
+    if project_dir.is_empty() {
+        return Err("projectDir is required".to_string());
+    }
+
+    let name = validate_skill_name(&name)?;
+    let src = collect_project_skill_roots(Path::new(project_dir))
+        .into_iter()
+        .map(|root| root.join(&name))
+        .find(|candidate| candidate.join("SKILL.md").is_file())
+        .ok_or_else(|| format!("Project skill {name} not found"))?;
+
+    if let Some(existing) = collect_global_skill_roots()
+        .into_iter()
+        .map(|root| root.join(&name))
+        .find(|candidate| candidate.exists())
+    {
+        return Err(format!("Skill already exists at {}", existing.display()));
+    }
+
+    let dest = ensure_global_skill_root()?.join(&name);
+    move_skill_dir(&src, &dest)?;
+
+    Ok(ExecResult {
+        ok: true,
+        status: 0,
+        stdout: format!("Moved skill to {}", dest.display()),
+        stderr: String::new(),
+    })
+}
+
+#[tauri::command]
+pub fn skill_demote(project_dir: String, name: String) -> Result<ExecResult, String> {
+    let name = validate_skill_name(&name)?;
+    let src = global_opencode_skill_roots()
+        .into_iter()
+        .map(|root| root.join(&name))
+        .find(|candidate| candidate.join("SKILL.md").is_file())
+        .ok_or_else(|| format!("Global skill {name} not found"))?;
+
+    let dest = ensure_project_skill_root(&project_dir)?.join(&name);
+    move_skill_dir(&src, &dest)?;
+
+    Ok(ExecResult {
+        ok: true,
+        status: 0,
+        stdout: format!("Moved skill to {}", dest.display()),
+        stderr: String::new(),
+    })
+}
+
+#[tauri::command]
+pub fn validate_skill(content: String) -> SkillValidation {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    if !frontmatter_closed(&content) {
+        errors.push("SKILL.md must start with a --- frontmatter block".to_string());
+    } else {
+        match extract_frontmatter_value(&content, &["name"]) {
+            Some(name) => {
+                if let Err(message) = validate_skill_name(&name) {
+                    errors.push(message);
+                } else if name.len() > 64 {
+                    errors.push("skill name must be at most 64 characters".to_string());
+                }
+            }
+            None => errors.push("frontmatter is missing a name".to_string()),
+        }
+
+        match extract_frontmatter_value(&content, &["description"]) {
+            Some(description) => {
+                if description.len() > 1024 {
+                    errors.push("description must be at most 1024 characters".to_string());
+                }
+            }
+            None => errors.push("frontmatter is missing a description".to_string()),
+        }
+    }
+
+    if skill_body(&content).trim().is_empty() {
+        warnings.push("SKILL.md has no instructions after the frontmatter".to_string());
+    }
+    if extract_trigger(&content).is_none() {
+        warnings.push("No trigger or \"When to use\" section found".to_string());
+    }
+
+    SkillValidation {
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillZipInstall {
+    pub name: String,
+    pub warnings: Vec<String>,
+}
+
+const MAX_SKILL_ZIP_ENTRIES: usize = 1000;
+const MAX_SKILL_ZIP_BYTES: u64 = 50 * 1024 * 1024;
+
+fn extract_skill_zip(zip_path: &Path, dest: &Path) -> Result<(), String> {
+    let file = fs::File::open(zip_path)
+        .map_err(|e| format!("Failed to open {}: {e}", zip_path.display()))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to read archive: {e}"))?;
+    if archive.len() > MAX_SKILL_ZIP_ENTRIES {
+        return Err(format!(
+            "Archive has {} entries; skills may contain at most {MAX_SKILL_ZIP_ENTRIES}",
+            archive.len()
+        ));
+    }
+
+    let mut remaining = MAX_SKILL_ZIP_BYTES;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        if entry.size() > remaining {
+            return Err(format!(
+                "Archive expands to more than {} MB",
+                MAX_SKILL_ZIP_BYTES / (1024 * 1024)
+            ));
+        }
+        let Some(relative) = entry.enclosed_name().map(Path::to_path_buf) else {
+            return Err(format!("Archive contains an unsafe path: {}", entry.name()));
+        };
+        if relative.starts_with("__MACOSX") {
+            continue;
+        }
+
+        let out_path = dest.join(&relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path)
+                .map_err(|e| format!("Failed to create {}: {e}", out_path.display()))?;
+            continue;
+        }
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let mut out =
+            fs::File::create(&out_path).map_err(|e| io_error_message("write", &out_path, &e))?;
+        // The declared size can lie, so never read past what the budget allows.
+        let written = std::io::copy(&mut (&mut entry).take(remaining + 1), &mut out)
+            .map_err(|e| format!("Failed to extract {}: {e}", relative.display()))?;
+        if written > remaining {
+            return Err(format!(
+                "Archive expands to more than {} MB",
+                MAX_SKILL_ZIP_BYTES / (1024 * 1024)
+            ));
+        }
+        remaining -= written;
+    }
+
+    Ok(())
+}
+
+/// The archive root itself when it holds SKILL.md, otherwise its single top-level
+/// directory that does.
+fn find_extracted_skill_dir(root: &Path) -> Result<PathBuf, String> {
+    if root.join("SKILL.md").is_file() {
+        return Ok(root.to_path_buf());
+    }
+
+    let entries = fs::read_dir(root).map_err(|e| format!("Failed to read archive: {e}"))?;
+    let mut found: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join("SKILL.md").is_file())
+        .collect();
+    match found.len() {
+        0 => Err("Archive does not contain a SKILL.md".to_string()),
+        1 => Ok(found.remove(0)),
+        _ => Err("Archive contains more than one skill".to_string()),
+    }
+}
+
+fn install_extracted_skill(
+    project_dir: &str,
+    extracted: &Path,
+    overwrite: bool,
+) -> Result<SkillZipInstall, String> {
+    let skill_dir = find_extracted_skill_dir(extracted)?;
+    let content = fs::read_to_string(skill_dir.join("SKILL.md"))
+        .map_err(|e| format!("Failed to read SKILL.md: {e}"))?;
+    let validation = validate_skill(content.clone());
+    if !validation.valid {
+        return Err(format!("Invalid skill: {}", validation.errors.join("; ")));
+    }
+
+    let name = extract_frontmatter_value(&content, &["name"])
+        .ok_or_else(|| "frontmatter is missing a name".to_string())?;
+    let name = validate_skill_name(&name)?;
+    let dest = ensure_project_skill_root(project_dir)?.join(&name);
+    if dest.exists() {
+        if !overwrite {
+            return Err(format!("Skill already exists at {}", dest.display()));
+        }
+        fs::remove_dir_all(&dest).map_err(|e| {
+            format!(
+                "Failed to remove existing skill dir {}: {e}",
+                dest.display()
+            )
+        })?;
+    }
+
+    copy_dir_recursive(&skill_dir, &dest, false)?;
+    Ok(SkillZipInstall {
+        name,
+        warnings: validation.warnings,
+    })
+}
+
+#[tauri::command]
+pub fn install_skill_from_zip(
+    project_dir: String,
+    zip_path: String,
+    overwrite: bool,
+) -> Result<SkillZipInstall, String> {
+    let project_dir = project_dir.trim();
+    if project_dir.is_empty() {
+        return Err("projectDir is required".to_string());
+    }
+    let zip_path = zip_path.trim();
+    if zip_path.is_empty() {
+        return Err("zipPath is required".to_string());
+    }
+
+    let temp_dir = std::env::temp_dir().join(format!("openwork-skill-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&temp_dir)
+        .map_err(|e| format!("Failed to create {}: {e}", temp_dir.display()))?;
+
+    let result = extract_skill_zip(Path::new(zip_path), &temp_dir)
+        .and_then(|()| install_extracted_skill(project_dir, &temp_dir, overwrite));
+    let _ = fs::remove_dir_all(&temp_dir);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skill_card_prefers_frontmatter() {
+        let raw = concat!(
+            "---\n",
+            "name: workspace-guide\n",
+            "description: \"Workspace guide to onboard new users.\"\n",
+            "---\n",
+            "\n",
+            "# Welcome\n",
+            "Hi, this is the body.\n",
+        );
+        assert_eq!(
+            skill_card_metadata(raw),
+            (
+                Some("workspace-guide".to_string()),
+                Some("Workspace guide to onboard new users.".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn skill_card_falls_back_without_frontmatter() {
+        let raw = "# My skill\n\nUse `rg` to search the repo.\n";
+        assert_eq!(
+            skill_card_metadata(raw),
+            (None, Some("Use rg to search the repo.".to_string()))
+        );
+
+        let no_description = "---\nname: helper\n---\nFirst body line.\n";
+        assert_eq!(
+            skill_card_metadata(no_description),
+            (
+                Some("helper".to_string()),
+                Some("First body line.".to_string())
+            )
+        );
+    }
+}
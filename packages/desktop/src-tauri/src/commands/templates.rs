@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use crate::types::WorkspaceTemplate;
+use crate::utils::now_ms;
+use crate::workspace::templates::{
+    export_template_bundle, import_template_bundle, list_templates, read_template,
+    render_template_prompt, template_variables, unique_template_id, write_template,
+};
+
+#[derive(serde::Serialize)]
+pub struct TemplateImportResult {
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+#[tauri::command]
+pub fn workspace_template_from_prompt(
+    workspace_path: String,
+    title: String,
+    description: Option<String>,
+    prompt: String,
+) -> Result<WorkspaceTemplate, String> {
+    let workspace_path = workspace_path.trim().to_string();
+    if workspace_path.is_empty() {
+        return Err("workspacePath is required".to_string());
+    }
+
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        return Err("title is required".to_string());
+    }
+
+    let prompt = prompt.trim().to_string();
+    if prompt.is_empty() {
+        return Err("prompt is required".to_string());
+    }
+
+    let template = WorkspaceTemplate {
+        variables: template_variables(&prompt),
+        id: unique_template_id(&workspace_path, &title)?,
+        title,
+        description: description
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default(),
+        prompt,
+        created_at: now_ms(),
+    };
+
+    let path = write_template(&workspace_path, &template)?;
+    println!("[templates] created {}", path.display());
+
+    Ok(template)
+}
+
+#[tauri::command]
+pub fn workspace_template_list(workspace_path: String) -> Result<Vec<WorkspaceTemplate>, String> {
+    let workspace_path = workspace_path.trim().to_string();
+    if workspace_path.is_empty() {
+        return Err("workspacePath is required".to_string());
+    }
+
+    list_templates(&workspace_path)
+}
+
+#[tauri::command]
+pub fn workspace_template_read(
+    workspace_path: String,
+    template_id: String,
+) -> Result<WorkspaceTemplate, String> {
+    let workspace_path = workspace_path.trim().to_string();
+    if workspace_path.is_empty() {
+        return Err("workspacePath is required".to_string());
+    }
+
+    read_template(&workspace_path, &template_id)
+}
+
+/// Returns the template's prompt with `{{name}}` placeholders filled from `vars`.
+#[tauri::command]
+pub fn workspace_template_render(
+    workspace_path: String,
+    template_id: String,
+    vars: HashMap<String, String>,
+) -> Result<String, String> {
+    let workspace_path = workspace_path.trim().to_string();
+    if workspace_path.is_empty() {
+        return Err("workspacePath is required".to_string());
+    }
+
+    let template = read_template(&workspace_path, &template_id)?;
+    render_template_prompt(&template.prompt, &vars)
+}
+
+/// Bundles every template in the workspace into a single JSON string to share.
+#[tauri::command]
+pub fn workspace_templates_export(workspace_path: String) -> Result<String, String> {
+    let workspace_path = workspace_path.trim().to_string();
+    if workspace_path.is_empty() {
+        return Err("workspacePath is required".to_string());
+    }
+
+    export_template_bundle(&workspace_path)
+}
+
+#[tauri::command]
+pub fn workspace_templates_import(
+    workspace_path: String,
+    bundle: String,
+    overwrite: bool,
+) -> Result<TemplateImportResult, String> {
+    let workspace_path = workspace_path.trim().to_string();
+    if workspace_path.is_empty() {
+        return Err("workspacePath is required".to_string());
+    }
+
+    let (imported, skipped) = import_template_bundle(&workspace_path, &bundle, overwrite)?;
+    println!(
+        "[templates] imported {} templates ({} skipped)",
+        imported.len(),
+        skipped.len()
+    );
+    Ok(TemplateImportResult { imported, skipped })
+}
@@ -1,7 +1,95 @@
-use crate::types::UpdaterEnvironment;
+use serde_json::json;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+use crate::types::{UpdateCheck, UpdateInstall, UpdaterEnvironment};
 use crate::updater::updater_environment as updater_environment_inner;
 
+const UPDATE_PROGRESS_EVENT: &str = "openwork://update-progress";
+
+async fn check_update(app: &AppHandle) -> Result<Option<Update>, String> {
+    app.updater()
+        .map_err(|e| format!("Failed to initialize updater: {e}"))?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {e}"))
+}
+
 #[tauri::command]
 pub fn updater_environment(_app: tauri::AppHandle) -> UpdaterEnvironment {
     updater_environment_inner()
 }
+
+/// Asks the update endpoint whether a newer release exists without downloading it.
+/// Environments that cannot update (e.g. running from a DMG) return early with the
+/// reason instead of querying.
+#[tauri::command]
+pub async fn updater_check_for_update(app: AppHandle) -> Result<UpdateCheck, String> {
+    let environment = updater_environment_inner();
+    let mut result = UpdateCheck {
+        supported: environment.supported,
+        reason: environment.reason,
+        available: false,
+        current_version: app.package_info().version.to_string(),
+        version: None,
+        notes: None,
+        pub_date: None,
+    };
+    if !result.supported {
+        return Ok(result);
+    }
+
+    if let Some(update) = check_update(&app).await? {
+        result.available = true;
+        result.pub_date = update
+            .raw_json
+            .get("pub_date")
+            .and_then(|value| value.as_str())
+            .map(str::to_string);
+        result.version = Some(update.version);
+        result.notes = update.body;
+    }
+    Ok(result)
+}
+
+/// Downloads and installs the pending update, emitting `{ downloaded, total }` progress
+/// events. The app is not relaunched; the UI should prompt for a restart.
+#[tauri::command]
+pub async fn updater_download_and_install(app: AppHandle) -> Result<UpdateInstall, String> {
+    let environment = updater_environment_inner();
+    if !environment.supported {
+        return Err(environment
+            .reason
+            .unwrap_or_else(|| "Updates are not supported in this environment".to_string()));
+    }
+
+    let Some(update) = check_update(&app).await? else {
+        return Ok(UpdateInstall {
+            installed: false,
+            version: None,
+            relaunch_required: false,
+        });
+    };
+
+    let mut downloaded: u64 = 0;
+    update
+        .download_and_install(
+            |chunk_length, total| {
+                downloaded += chunk_length as u64;
+                let _ = app.emit(
+                    UPDATE_PROGRESS_EVENT,
+                    json!({ "downloaded": downloaded, "total": total }),
+                );
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| format!("Failed to install update {}: {e}", update.version))?;
+
+    println!("[updater] installed {}", update.version);
+    Ok(UpdateInstall {
+        installed: true,
+        version: Some(update.version),
+        relaunch_required: true,
+    })
+}
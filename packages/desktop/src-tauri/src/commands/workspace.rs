@@ -3,31 +3,63 @@ use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::fs::{copy_dir_recursive, write_atomic};
+use crate::paths::{git_program, home_dir};
 use crate::types::{
-    ExecResult, RemoteType, WorkspaceInfo, WorkspaceList, WorkspaceOpenworkConfig, WorkspaceType,
+    AuthorizedRootAudit, ExecResult, PresetDetails, RemoteReachability, RemoteTestStep, RemoteType,
+    WorkspaceDirEntry, WorkspaceInfo, WorkspaceList, WorkspaceOpenworkConfig, WorkspaceRecentFile,
+    WorkspaceType, WorkspaceWatchStatus,
 };
-use crate::workspace::files::ensure_workspace_files;
+use crate::utils::truncate_output;
+use crate::workspace::files::{
+    ensure_workspace_files, ensure_workspace_files_lenient, fix_skill_permissions,
+    migrate_workspace_preset, rebase_openwork_authorized_roots, set_workspace_openwork_name,
+    update_workspace_guide, workspace_config_fingerprint as compute_config_fingerprint,
+    write_openwork_config,
+};
+use crate::workspace::presets::PRESETS;
+use crate::workspace::remote::{check_remote_reachable, run_remote_handshake};
+use crate::workspace::scope::{
+    allowed_roots, audit_authorized_root, canonical_workspace_root, read_workspace_openwork_config,
+    registered_workspace_root, reject_system_dir, resolve_existing_path, resolve_writable_path,
+};
+use crate::workspace::setup_script::build_setup_script;
 use crate::workspace::state::{
-    ensure_starter_workspace, load_workspace_state, save_workspace_state, stable_workspace_id,
-    stable_workspace_id_for_openwork, stable_workspace_id_for_remote,
+    check_data_dir_status, ensure_starter_workspace, load_workspace_state, save_workspace_state,
+    stable_workspace_id, stable_workspace_id_for_openwork, stable_workspace_id_for_remote,
+};
+use crate::workspace::templates::templates_dir;
+use crate::workspace::watch::{
+    update_workspace_watch, workspace_watch_snapshot, WorkspaceWatchState,
 };
-use crate::workspace::watch::{update_workspace_watch, WorkspaceWatchState};
 use serde::Serialize;
-use tauri::State;
+use serde_json::json;
+use tauri::{Emitter, Manager, State};
+use tauri_plugin_shell::process::CommandEvent;
+use tauri_plugin_shell::ShellExt;
 use walkdir::WalkDir;
 use zip::write::FileOptions;
 use zip::{CompressionMethod, ZipArchive, ZipWriter};
 
+const GIT_CLONE_PROGRESS_EVENT: &str = "openwork://git-clone-progress";
+
 #[tauri::command]
 pub fn workspace_bootstrap(
     app: tauri::AppHandle,
     watch_state: State<WorkspaceWatchState>,
 ) -> Result<WorkspaceList, String> {
     println!("[workspace] bootstrap");
+    let data_dir = check_data_dir_status(&app);
+    if !data_dir.writable {
+        return Err(format!(
+            "OpenWork can't write its data directory: {}",
+            data_dir.error.unwrap_or_default()
+        ));
+    }
     let mut state = load_workspace_state(&app)?;
 
     let starter = ensure_starter_workspace(&app)?;
-    ensure_workspace_files(&starter.path, &starter.preset)?;
+    ensure_workspace_files_lenient(&starter.path, &starter.preset)?;
 
     if !state.workspaces.iter().any(|w| w.id == starter.id) {
         state.workspaces.push(starter.clone());
@@ -42,6 +74,42 @@ pub fn workspace_bootstrap(
     }
 
     save_workspace_state(&app, &state)?;
+    let active_workspace = state
+        .workspaces
+        .iter()
+        .find(|w| w.id == state.active_id && w.exists);
+    update_workspace_watch(&app, watch_state, active_workspace)?;
+
+    Ok(WorkspaceList {
+        active_id: state.active_id,
+        workspaces: state.workspaces,
+    })
+}
+
+#[tauri::command]
+pub fn workspace_reload(
+    app: tauri::AppHandle,
+    watch_state: State<WorkspaceWatchState>,
+) -> Result<WorkspaceList, String> {
+    println!("[workspace] reload");
+    let mut state = load_workspace_state(&app)?;
+    let mut changed = false;
+
+    if state.workspaces.is_empty() {
+        let starter = ensure_starter_workspace(&app)?;
+        ensure_workspace_files_lenient(&starter.path, &starter.preset)?;
+        state.workspaces.push(starter);
+        changed = true;
+    }
+
+    if !state.workspaces.iter().any(|w| w.id == state.active_id) {
+        state.active_id = state.workspaces[0].id.clone();
+        changed = true;
+    }
+
+    if changed {
+        save_workspace_state(&app, &state)?;
+    }
     let active_workspace = state.workspaces.iter().find(|w| w.id == state.active_id);
     update_workspace_watch(&app, watch_state, active_workspace)?;
 
@@ -51,6 +119,18 @@ pub fn workspace_bootstrap(
     })
 }
 
+/// Read-only listing for UI refreshes: unlike `workspace_bootstrap` it never seeds the
+/// starter workspace, writes state, or touches the watcher.
+#[tauri::command]
+pub fn workspace_list(app: tauri::AppHandle) -> Result<WorkspaceList, String> {
+    let state = load_workspace_state(&app)?;
+
+    Ok(WorkspaceList {
+        active_id: state.active_id,
+        workspaces: state.workspaces,
+    })
+}
+
 #[tauri::command]
 pub fn workspace_forget(
     app: tauri::AppHandle,
@@ -81,7 +161,7 @@ pub fn workspace_forget(
 
     if state.workspaces.is_empty() {
         let starter = ensure_starter_workspace(&app)?;
-        ensure_workspace_files(&starter.path, &starter.preset)?;
+        ensure_workspace_files_lenient(&starter.path, &starter.preset)?;
         state.active_id = starter.id.clone();
         state.workspaces.push(starter);
     }
@@ -97,6 +177,62 @@ pub fn workspace_forget(
     })
 }
 
+/// Forgets a workspace and, with `delete_files`, removes its local folder first. The
+/// starter workspace lives under the app data dir and is only deleted when
+/// `confirm_managed` is set.
+#[tauri::command]
+pub fn workspace_delete(
+    app: tauri::AppHandle,
+    workspace_id: String,
+    delete_files: bool,
+    confirm_managed: Option<bool>,
+    watch_state: State<WorkspaceWatchState>,
+) -> Result<WorkspaceList, String> {
+    println!("[workspace] delete request: {workspace_id} (files: {delete_files})");
+    let state = load_workspace_state(&app)?;
+    let id = workspace_id.trim();
+
+    if id.is_empty() {
+        return Err("workspaceId is required".to_string());
+    }
+
+    let workspace = state
+        .workspaces
+        .iter()
+        .find(|w| w.id == id)
+        .ok_or_else(|| "Unknown workspaceId".to_string())?;
+
+    if delete_files && workspace.workspace_type == WorkspaceType::Local {
+        let folder = PathBuf::from(workspace.path.trim());
+        if folder.exists() {
+            let folder = fs::canonicalize(&folder)
+                .map_err(|e| format!("Failed to resolve {}: {e}", folder.display()))?;
+            let contains_home = home_dir().is_some_and(|home| home.starts_with(&folder));
+            if contains_home || reject_system_dir(&folder).is_err() {
+                return Err(format!("Refusing to delete {}", folder.display()));
+            }
+
+            let data_dir = app
+                .path()
+                .app_data_dir()
+                .map_err(|e| format!("Failed to resolve app data dir: {e}"))?;
+            let data_dir = fs::canonicalize(&data_dir).unwrap_or(data_dir);
+            if folder.starts_with(&data_dir) && !confirm_managed.unwrap_or(false) {
+                return Err(format!(
+                    "{} is managed by OpenWork; pass confirmManaged to delete it",
+                    folder.display()
+                ));
+            }
+
+            fs::remove_dir_all(&folder)
+                .map_err(|e| format!("Failed to delete {}: {e}", folder.display()))?;
+            println!("[workspace] deleted folder {}", folder.display());
+        }
+    }
+
+    workspace_forget(app, id.to_string(), watch_state)
+}
+
 #[tauri::command]
 pub fn workspace_set_active(
     app: tauri::AppHandle,
@@ -127,6 +263,43 @@ pub fn workspace_set_active(
     })
 }
 
+#[tauri::command]
+pub fn workspace_change_preset(
+    app: tauri::AppHandle,
+    workspace_id: String,
+    new_preset: String,
+) -> Result<WorkspaceList, String> {
+    let mut state = load_workspace_state(&app)?;
+    let id = workspace_id.trim();
+    let new_preset = new_preset.trim();
+
+    if id.is_empty() {
+        return Err("workspaceId is required".to_string());
+    }
+    if new_preset.is_empty() {
+        return Err("newPreset is required".to_string());
+    }
+
+    let workspace = state
+        .workspaces
+        .iter_mut()
+        .find(|w| w.id == id)
+        .ok_or_else(|| "Unknown workspaceId".to_string())?;
+    if workspace.workspace_type != WorkspaceType::Local {
+        return Err("Only local workspaces have a preset".to_string());
+    }
+
+    migrate_workspace_preset(&workspace.path, new_preset)?;
+    workspace.preset = new_preset.to_string();
+    save_workspace_state(&app, &state)?;
+    println!("[workspace] changed preset: {id} -> {new_preset}");
+
+    Ok(WorkspaceList {
+        active_id: state.active_id,
+        workspaces: state.workspaces,
+    })
+}
+
 #[tauri::command]
 pub fn workspace_update_display_name(
     app: tauri::AppHandle,
@@ -162,6 +335,87 @@ pub fn workspace_update_display_name(
     })
 }
 
+/// Whether the config watcher for the active workspace is installed, what it covers,
+/// and the last error it hit. Without a watcher the UI gets no reload events.
+#[tauri::command]
+pub fn workspace_watch_status(watch_state: State<WorkspaceWatchState>) -> WorkspaceWatchStatus {
+    workspace_watch_snapshot(&watch_state)
+}
+
+/// Re-arms the watcher for the active workspace. The returned status carries the
+/// error if it still cannot be installed.
+#[tauri::command]
+pub fn workspace_watch_restart(
+    app: tauri::AppHandle,
+    watch_state: State<WorkspaceWatchState>,
+) -> Result<WorkspaceWatchStatus, String> {
+    let state = load_workspace_state(&app)?;
+    let active_workspace = state.workspaces.iter().find(|w| w.id == state.active_id);
+    if let Err(error) = update_workspace_watch(&app, watch_state.clone(), active_workspace) {
+        println!("[workspace] watch restart failed: {error}");
+    }
+    Ok(workspace_watch_snapshot(&watch_state))
+}
+
+/// Renames a local workspace entry. Only the stored name changes; the folder on disk
+/// is left alone.
+#[tauri::command]
+pub fn workspace_rename(
+    app: tauri::AppHandle,
+    workspace_id: String,
+    name: String,
+) -> Result<WorkspaceList, String> {
+    println!("[workspace] rename request: {workspace_id}");
+    let mut state = load_workspace_state(&app)?;
+    let id = workspace_id.trim();
+
+    if id.is_empty() {
+        return Err("workspaceId is required".to_string());
+    }
+
+    let workspace_name = name.trim().to_string();
+    if workspace_name.is_empty() {
+        return Err("name is required".to_string());
+    }
+
+    let Some(entry) = state.workspaces.iter_mut().find(|w| w.id == id) else {
+        return Err("Unknown workspaceId".to_string());
+    };
+    if entry.workspace_type != WorkspaceType::Local {
+        return Err("Use workspace_update_remote to rename remote workspaces".to_string());
+    }
+    entry.name = workspace_name;
+
+    save_workspace_state(&app, &state)?;
+    println!("[workspace] rename complete: {id}");
+
+    Ok(WorkspaceList {
+        active_id: state.active_id,
+        workspaces: state.workspaces,
+    })
+}
+
+#[tauri::command]
+pub fn list_presets_detailed() -> Vec<PresetDetails> {
+    PRESETS
+        .iter()
+        .map(|preset| PresetDetails {
+            id: preset.id.to_string(),
+            name: preset.name.to_string(),
+            required_plugins: preset
+                .required_plugins
+                .iter()
+                .map(|plugin| plugin.to_string())
+                .collect(),
+            template_titles: preset
+                .templates
+                .iter()
+                .map(|template| template.title.to_string())
+                .collect(),
+        })
+        .collect()
+}
+
 #[tauri::command]
 pub fn workspace_create(
     app: tauri::AppHandle,
@@ -181,20 +435,147 @@ pub fn workspace_create(
         return Err("name is required".to_string());
     }
 
-    let preset = preset.trim().to_string();
-    let preset = if preset.is_empty() {
-        "starter".to_string()
-    } else {
-        preset
-    };
+    let preset = normalize_preset(&preset);
 
     fs::create_dir_all(&folder).map_err(|e| format!("Failed to create workspace folder: {e}"))?;
 
+    let list = register_local_workspace(&app, folder, workspace_name, preset, watch_state)?;
+    println!("[workspace] create local complete: {}", list.active_id);
+    Ok(list)
+}
+
+/// Creates a new local workspace at `new_folder_path` seeded with the source workspace's
+/// OpenCode config, `.opencode` folder (skills, commands, openwork.json) and templates.
+/// Other files in the source folder are not copied.
+#[tauri::command]
+pub fn workspace_duplicate(
+    app: tauri::AppHandle,
+    source_workspace_id: String,
+    new_folder_path: String,
+    new_name: String,
+    watch_state: State<WorkspaceWatchState>,
+) -> Result<WorkspaceList, String> {
+    println!("[workspace] duplicate request: {source_workspace_id}");
+    let folder = new_folder_path.trim().to_string();
+    if folder.is_empty() {
+        return Err("newFolderPath is required".to_string());
+    }
+    let workspace_name = new_name.trim().to_string();
+    if workspace_name.is_empty() {
+        return Err("newName is required".to_string());
+    }
+
+    let state = load_workspace_state(&app)?;
+    let source = state
+        .workspaces
+        .iter()
+        .find(|w| w.id == source_workspace_id.trim())
+        .ok_or_else(|| "Unknown workspaceId".to_string())?;
+    if source.workspace_type != WorkspaceType::Local {
+        return Err("Only local workspaces can be duplicated".to_string());
+    }
+    let source_root = PathBuf::from(&source.path);
+    if !source_root.is_dir() {
+        return Err(format!(
+            "Workspace folder not found: {}",
+            source_root.display()
+        ));
+    }
+
+    let dest_root = PathBuf::from(&folder);
+    if dest_root.starts_with(source_root.join(".opencode"))
+        || dest_root.starts_with(source_root.join(".openwork"))
+    {
+        return Err("newFolderPath must not be inside the copied folders".to_string());
+    }
+    let dest_has_entries = fs::read_dir(&dest_root)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false);
+    if dest_has_entries {
+        return Err(format!(
+            "{} already exists and is not empty",
+            dest_root.display()
+        ));
+    }
+    let existed = dest_root.exists();
+    fs::create_dir_all(&dest_root)
+        .map_err(|e| format!("Failed to create workspace folder: {e}"))?;
+
+    let cleanup = || {
+        let _ = fs::remove_dir_all(&dest_root);
+        if existed {
+            let _ = fs::create_dir_all(&dest_root);
+        }
+    };
+
+    if let Err(error) = copy_workspace_config(&source.path, &folder, &workspace_name) {
+        cleanup();
+        return Err(error);
+    }
+
+    let preset = source.preset.clone();
+    match register_local_workspace(&app, folder, workspace_name, preset, watch_state) {
+        Ok(list) => {
+            println!("[workspace] duplicate complete: {}", list.active_id);
+            Ok(list)
+        }
+        Err(error) => {
+            cleanup();
+            Err(error)
+        }
+    }
+}
+
+/// Copies the config, `.opencode` folder and templates of `source` into `dest`, then
+/// points the copy's authorized roots and name at its new home.
+fn copy_workspace_config(source: &str, dest: &str, name: &str) -> Result<(), String> {
+    let source_root = Path::new(source);
+    let dest_root = Path::new(dest);
+    for file in ["opencode.jsonc", "opencode.json"] {
+        let from = source_root.join(file);
+        if from.is_file() {
+            let to = dest_root.join(file);
+            fs::copy(&from, &to).map_err(|e| format!("Failed to copy {}: {e}", from.display()))?;
+        }
+    }
+    let opencode_dir = source_root.join(".opencode");
+    if opencode_dir.is_dir() {
+        copy_dir_recursive(&opencode_dir, &dest_root.join(".opencode"), false)?;
+    }
+    let source_templates = templates_dir(source);
+    if source_templates.is_dir() {
+        copy_dir_recursive(&source_templates, &templates_dir(dest), false)?;
+    }
+
+    rebase_openwork_authorized_roots(dest, source)?;
+    if dest_root.join(".opencode").join("openwork.json").exists() {
+        set_workspace_openwork_name(dest, name)?;
+    }
+    Ok(())
+}
+
+fn normalize_preset(preset: &str) -> String {
+    let preset = preset.trim();
+    if preset.is_empty() {
+        "starter".to_string()
+    } else {
+        preset.to_string()
+    }
+}
+
+/// Seeds workspace files in an existing folder, then registers it as the active workspace.
+fn register_local_workspace(
+    app: &tauri::AppHandle,
+    folder: String,
+    workspace_name: String,
+    preset: String,
+    watch_state: State<WorkspaceWatchState>,
+) -> Result<WorkspaceList, String> {
     let id = stable_workspace_id(&folder);
 
     ensure_workspace_files(&folder, &preset)?;
 
-    let mut state = load_workspace_state(&app)?;
+    let mut state = load_workspace_state(app)?;
 
     state.workspaces.retain(|w| w.id != id);
     state.workspaces.push(WorkspaceInfo {
@@ -210,13 +591,13 @@ pub fn workspace_create(
         openwork_host_url: None,
         openwork_workspace_id: None,
         openwork_workspace_name: None,
+        exists: true,
     });
 
-    state.active_id = id.clone();
-    save_workspace_state(&app, &state)?;
+    state.active_id = id;
+    save_workspace_state(app, &state)?;
     let active_workspace = state.workspaces.iter().find(|w| w.id == state.active_id);
-    update_workspace_watch(&app, watch_state, active_workspace)?;
-    println!("[workspace] create local complete: {id}");
+    update_workspace_watch(app, watch_state, active_workspace)?;
 
     Ok(WorkspaceList {
         active_id: state.active_id,
@@ -224,6 +605,108 @@ pub fn workspace_create(
     })
 }
 
+/// Clones `repo_url` into `dest_folder` and registers it as a local workspace. Progress
+/// lines from git are emitted on `openwork://git-clone-progress`. On any failure the
+/// clone is removed and nothing is registered.
+#[tauri::command]
+pub async fn workspace_create_from_git(
+    app: tauri::AppHandle,
+    watch_state: State<'_, WorkspaceWatchState>,
+    repo_url: String,
+    dest_folder: String,
+    name: String,
+    preset: String,
+) -> Result<WorkspaceList, String> {
+    let repo_url = repo_url.trim().to_string();
+    let dest = dest_folder.trim().to_string();
+    let workspace_name = name.trim().to_string();
+    if repo_url.is_empty() {
+        return Err("repoUrl is required".to_string());
+    }
+    if dest.is_empty() {
+        return Err("destFolder is required".to_string());
+    }
+    if workspace_name.is_empty() {
+        return Err("name is required".to_string());
+    }
+    let preset = normalize_preset(&preset);
+    let git = git_program().ok_or_else(|| "git was not found on PATH".to_string())?;
+
+    let dest_path = PathBuf::from(&dest);
+    let existed = dest_path.exists();
+    if existed {
+        let mut entries = fs::read_dir(&dest_path)
+            .map_err(|e| format!("Failed to read {}: {e}", dest_path.display()))?;
+        if entries.next().is_some() {
+            return Err(format!("{} is not empty", dest_path.display()));
+        }
+    }
+
+    let cleanup = || {
+        let _ = fs::remove_dir_all(&dest_path);
+        if existed {
+            let _ = fs::create_dir_all(&dest_path);
+        }
+    };
+
+    println!("[workspace] clone {repo_url} -> {dest}");
+    let (mut rx, _child) = app
+        .shell()
+        .command(git)
+        .args([
+            "clone",
+            "--progress",
+            "--",
+            repo_url.as_str(),
+            dest.as_str(),
+        ])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .spawn()
+        .map_err(|e| format!("Failed to start git clone: {e}"))?;
+
+    let mut stderr = String::new();
+    let mut status = -1;
+    while let Some(event) = rx.recv().await {
+        let text = match event {
+            CommandEvent::Stdout(line) | CommandEvent::Stderr(line) => {
+                String::from_utf8_lossy(&line).to_string()
+            }
+            CommandEvent::Error(message) => message,
+            CommandEvent::Terminated(payload) => {
+                status = payload.code.unwrap_or(-1);
+                continue;
+            }
+            _ => continue,
+        };
+        stderr.push_str(&text);
+        // git redraws progress with carriage returns; only the latest state matters.
+        if let Some(line) = text
+            .rsplit('\r')
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+        {
+            let _ = app.emit(GIT_CLONE_PROGRESS_EVENT, json!({ "line": line }));
+        }
+    }
+
+    if status != 0 {
+        cleanup();
+        let detail = truncate_output(stderr.trim(), 2000);
+        return Err(format!("git clone failed (status {status}):\n{detail}"));
+    }
+
+    match register_local_workspace(&app, dest, workspace_name, preset, watch_state) {
+        Ok(list) => {
+            println!("[workspace] clone complete: {}", list.active_id);
+            Ok(list)
+        }
+        Err(error) => {
+            cleanup();
+            Err(error)
+        }
+    }
+}
+
 #[tauri::command]
 pub fn workspace_create_remote(
     app: tauri::AppHandle,
@@ -305,6 +788,7 @@ pub fn workspace_create_remote(
         openwork_host_url,
         openwork_workspace_id,
         openwork_workspace_name,
+        exists: true,
     });
     state.active_id = id.clone();
     save_workspace_state(&app, &state)?;
@@ -318,6 +802,55 @@ pub fn workspace_create_remote(
     })
 }
 
+#[tauri::command]
+pub async fn remote_workspace_test(
+    app: tauri::AppHandle,
+    workspace_id: String,
+    token: Option<String>,
+) -> Result<Vec<RemoteTestStep>, String> {
+    let id = workspace_id.trim();
+    if id.is_empty() {
+        return Err("workspaceId is required".to_string());
+    }
+
+    let state = load_workspace_state(&app)?;
+    let Some(workspace) = state.workspaces.into_iter().find(|w| w.id == id) else {
+        return Err("Unknown workspaceId".to_string());
+    };
+    if workspace.workspace_type != WorkspaceType::Remote {
+        return Err("workspaceId is not remote".to_string());
+    }
+
+    println!("[workspace] remote test: {id}");
+    tauri::async_runtime::spawn_blocking(move || run_remote_handshake(&workspace, token.as_deref()))
+        .await
+        .map_err(|e| format!("Remote test failed: {e}"))
+}
+
+/// Quick reachability probe for a remote workspace's server, for status indicators.
+#[tauri::command]
+pub async fn workspace_check_remote(
+    app: tauri::AppHandle,
+    workspace_id: String,
+) -> Result<RemoteReachability, String> {
+    let id = workspace_id.trim();
+    if id.is_empty() {
+        return Err("workspaceId is required".to_string());
+    }
+
+    let state = load_workspace_state(&app)?;
+    let Some(workspace) = state.workspaces.into_iter().find(|w| w.id == id) else {
+        return Err("Unknown workspaceId".to_string());
+    };
+    if workspace.workspace_type != WorkspaceType::Remote {
+        return Err("workspaceId is not remote".to_string());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || check_remote_reachable(&workspace))
+        .await
+        .map_err(|e| format!("Remote check failed: {e}"))
+}
+
 #[tauri::command]
 pub fn workspace_update_remote(
     app: tauri::AppHandle,
@@ -453,11 +986,7 @@ pub fn workspace_add_authorized_root(
         config.authorized_roots.push(folder_path);
     }
 
-    fs::write(
-        &openwork_path,
-        serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?,
-    )
-    .map_err(|e| format!("Failed to write {}: {e}", openwork_path.display()))?;
+    write_openwork_config(&openwork_path, &config)?;
 
     Ok(ExecResult {
         ok: true,
@@ -467,6 +996,70 @@ pub fn workspace_add_authorized_root(
     })
 }
 
+/// Authorizes several folders at once, writing `openwork.json` a single time.
+#[tauri::command]
+pub fn workspace_add_authorized_roots(
+    workspace_path: String,
+    folder_paths: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let root = canonical_workspace_root(&workspace_path)?;
+
+    let mut folders = Vec::new();
+    for folder in &folder_paths {
+        let trimmed = folder.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let canonical =
+            fs::canonicalize(trimmed).map_err(|e| format!("Failed to resolve {trimmed}: {e}"))?;
+        if !canonical.is_dir() {
+            return Err(format!("{} is not a directory", canonical.display()));
+        }
+        reject_system_dir(&canonical)?;
+        folders.push(canonical);
+    }
+
+    let openwork_path = root.join(".opencode").join("openwork.json");
+    let mut config = read_workspace_openwork_config(&root)?;
+    let mut existing: Vec<PathBuf> = config
+        .authorized_roots
+        .iter()
+        .map(|entry| fs::canonicalize(entry.trim()).unwrap_or_else(|_| PathBuf::from(entry)))
+        .collect();
+
+    for folder in folders {
+        if existing.contains(&folder) {
+            continue;
+        }
+        config
+            .authorized_roots
+            .push(folder.to_string_lossy().to_string());
+        existing.push(folder);
+    }
+
+    if let Some(parent) = openwork_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    write_openwork_config(&openwork_path, &config)?;
+
+    Ok(config.authorized_roots)
+}
+
+/// Reports, per authorized root, whether it still resolves somewhere reasonable: not a
+/// system or home root, not a credential store, and not a symlink escaping the workspace.
+#[tauri::command]
+pub fn audit_authorized_roots(workspace_path: String) -> Result<Vec<AuthorizedRootAudit>, String> {
+    let root = canonical_workspace_root(&workspace_path)?;
+    let config = read_workspace_openwork_config(&root)?;
+    Ok(config
+        .authorized_roots
+        .iter()
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| audit_authorized_root(&root, entry))
+        .collect())
+}
+
 #[tauri::command]
 pub fn workspace_openwork_read(
     _app: tauri::AppHandle,
@@ -494,6 +1087,37 @@ pub fn workspace_openwork_read(
         .map_err(|e| format!("Failed to parse {}: {e}", openwork_path.display()))
 }
 
+/// A write carrying the `updated_at` it read is authoritative, so removed roots stay
+/// removed; one from an older read is a conflict. An unstamped write over an unstamped
+/// (pre-stamp) file is authoritative too, since that is what a reader of such a file
+/// sends back. Only an unstamped write over a stamped file cannot be checked, so its
+/// roots are unioned with the file's and nothing is lost.
+fn merge_openwork_config(
+    existing: WorkspaceOpenworkConfig,
+    incoming: WorkspaceOpenworkConfig,
+) -> Result<WorkspaceOpenworkConfig, String> {
+    match (incoming.updated_at, existing.updated_at) {
+        (Some(read), Some(current)) if read != current => Err(format!(
+            "Conflict: openwork.json changed since it was read (read {read}, now {current}); reload and retry"
+        )),
+        (Some(_), _) | (None, None) => Ok(incoming),
+        (None, Some(_)) => {
+            let mut authorized_roots = existing.authorized_roots;
+            for root in incoming.authorized_roots {
+                if !authorized_roots.contains(&root) {
+                    authorized_roots.push(root);
+                }
+            }
+            Ok(WorkspaceOpenworkConfig {
+                version: incoming.version.max(existing.version),
+                workspace: incoming.workspace.or(existing.workspace),
+                authorized_roots,
+                updated_at: existing.updated_at,
+            })
+        }
+    }
+}
+
 #[tauri::command]
 pub fn workspace_openwork_write(
     _app: tauri::AppHandle,
@@ -509,16 +1133,22 @@ pub fn workspace_openwork_write(
         .join(".opencode")
         .join("openwork.json");
 
+    let config = if openwork_path.exists() {
+        let raw = fs::read_to_string(&openwork_path)
+            .map_err(|e| format!("Failed to read {}: {e}", openwork_path.display()))?;
+        let existing = serde_json::from_str::<WorkspaceOpenworkConfig>(&raw)
+            .map_err(|e| format!("Failed to parse {}: {e}", openwork_path.display()))?;
+        merge_openwork_config(existing, config)?
+    } else {
+        config
+    };
+
     if let Some(parent) = openwork_path.parent() {
         fs::create_dir_all(parent)
             .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
     }
 
-    fs::write(
-        &openwork_path,
-        serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?,
-    )
-    .map_err(|e| format!("Failed to write {}: {e}", openwork_path.display()))?;
+    write_openwork_config(&openwork_path, &config)?;
 
     Ok(ExecResult {
         ok: true,
@@ -528,6 +1158,233 @@ pub fn workspace_openwork_write(
     })
 }
 
+#[tauri::command]
+pub fn workspace_openwork_set_name(
+    workspace_path: String,
+    name: String,
+) -> Result<WorkspaceOpenworkConfig, String> {
+    let workspace_path = workspace_path.trim().to_string();
+    if workspace_path.is_empty() {
+        return Err("workspacePath is required".to_string());
+    }
+
+    set_workspace_openwork_name(&workspace_path, &name)
+}
+
+/// Stable digest of the workspace's config files; compare two values to detect changes.
+#[tauri::command]
+pub fn workspace_config_fingerprint(workspace_path: String) -> Result<String, String> {
+    let root = canonical_workspace_root(&workspace_path)?;
+    compute_config_fingerprint(&root)
+}
+
+/// Refreshes the bundled `workspace-guide` skill when it is older than this build (or
+/// always with `force`). Returns whether the guide was rewritten.
+#[tauri::command]
+pub fn workspace_update_guide(workspace_path: String, force: bool) -> Result<bool, String> {
+    let root = canonical_workspace_root(&workspace_path)?;
+    let updated = update_workspace_guide(&root, force)?;
+    if updated {
+        println!("[workspace] updated workspace guide in {}", root.display());
+    }
+    Ok(updated)
+}
+
+/// Restores execute bits on skill scripts (and plain modes on skill docs) after a copy
+/// or restore stripped them. Returns the files that changed; a no-op on Windows.
+#[tauri::command]
+pub fn workspace_fix_permissions(workspace_path: String) -> Result<Vec<String>, String> {
+    let root = canonical_workspace_root(&workspace_path)?;
+    let changed = fix_skill_permissions(&root)?;
+    println!("[workspace] fixed permissions on {} files", changed.len());
+    Ok(changed)
+}
+
+#[tauri::command]
+pub fn workspace_read_file(
+    app: tauri::AppHandle,
+    workspace_path: String,
+    relative_path: String,
+) -> Result<String, String> {
+    if relative_path.trim().is_empty() {
+        return Err("relativePath is required".to_string());
+    }
+
+    let root = registered_workspace_root(&app, &workspace_path)?;
+    let path = resolve_existing_path(&root, &relative_path)?;
+    if !path.is_file() {
+        return Err(format!("{} is not a file", path.display()));
+    }
+
+    fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {e}", path.display()))
+}
+
+#[tauri::command]
+pub fn workspace_write_file(
+    app: tauri::AppHandle,
+    workspace_path: String,
+    relative_path: String,
+    content: String,
+) -> Result<ExecResult, String> {
+    if relative_path.trim().is_empty() {
+        return Err("relativePath is required".to_string());
+    }
+
+    let root = registered_workspace_root(&app, &workspace_path)?;
+    let path = resolve_writable_path(&root, &relative_path)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    write_atomic(&path, content.as_bytes())?;
+
+    Ok(ExecResult {
+        ok: true,
+        status: 0,
+        stdout: format!("Wrote {}", path.display()),
+        stderr: String::new(),
+    })
+}
+
+#[tauri::command]
+pub fn workspace_list_dir(
+    app: tauri::AppHandle,
+    workspace_path: String,
+    relative_path: Option<String>,
+) -> Result<Vec<WorkspaceDirEntry>, String> {
+    let root = registered_workspace_root(&app, &workspace_path)?;
+    let roots = allowed_roots(&root)?;
+    let dir = resolve_existing_path(&root, relative_path.as_deref().unwrap_or(""))?;
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(|e| format!("Failed to read {}: {e}", dir.display()))? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+        let path = entry.path();
+
+        if file_type.is_symlink() {
+            let Ok(target) = fs::canonicalize(&path) else {
+                continue;
+            };
+            if !roots.iter().any(|root| target.starts_with(root)) {
+                continue;
+            }
+        }
+
+        // Follows symlinks that passed the containment check above.
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        let mtime = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as u64);
+
+        entries.push(WorkspaceDirEntry {
+            name: entry.file_name().to_string_lossy().to_string(),
+            is_dir: metadata.is_dir(),
+            size: if metadata.is_dir() { 0 } else { metadata.len() },
+            mtime,
+        });
+    }
+
+    entries.sort_by(|a, b| {
+        b.is_dir
+            .cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    Ok(entries)
+}
+
+const RECENT_FILES_SKIP_DIRS: [&str; 2] = [".git", "node_modules"];
+const RECENT_FILES_DEFAULT_LIMIT: usize = 50;
+const RECENT_FILES_MAX_LIMIT: usize = 500;
+const RECENT_FILES_LARGE_BYTES: u64 = 5 * 1024 * 1024;
+const RECENT_FILES_MAX_VISITED: usize = 50_000;
+
+/// Large files are only skipped when they look binary (a NUL byte in the first 8 KiB),
+/// so big logs and generated sources still show up.
+fn is_large_binary(path: &Path, size: u64) -> bool {
+    if size < RECENT_FILES_LARGE_BYTES {
+        return false;
+    }
+    let mut head = [0u8; 8192];
+    fs::File::open(path)
+        .and_then(|mut file| file.read(&mut head))
+        .map(|read| head[..read].contains(&0))
+        .unwrap_or(true)
+}
+
+/// Most recently modified files in the workspace, newest first, e.g. to show what an
+/// agent run touched. `.git`, `node_modules`, and large binaries are skipped.
+#[tauri::command]
+pub fn workspace_recent_files(
+    app: tauri::AppHandle,
+    workspace_path: String,
+    limit: Option<usize>,
+) -> Result<Vec<WorkspaceRecentFile>, String> {
+    let root = registered_workspace_root(&app, &workspace_path)?;
+    let roots = allowed_roots(&root)?;
+    let limit = limit
+        .unwrap_or(RECENT_FILES_DEFAULT_LIMIT)
+        .clamp(1, RECENT_FILES_MAX_LIMIT);
+
+    let walker = WalkDir::new(&root).into_iter().filter_entry(|entry| {
+        !(entry.file_type().is_dir()
+            && RECENT_FILES_SKIP_DIRS
+                .iter()
+                .any(|name| entry.file_name() == *name))
+    });
+
+    let mut files = Vec::new();
+    // Stop after a bounded walk so a huge tree cannot stall the command.
+    for entry in walker.take(RECENT_FILES_MAX_VISITED).filter_map(Result::ok) {
+        let path = entry.path();
+        if entry.file_type().is_symlink() {
+            let Ok(target) = fs::canonicalize(path) else {
+                continue;
+            };
+            if !roots.iter().any(|root| target.starts_with(root)) {
+                continue;
+            }
+        }
+
+        // Follows symlinks that passed the containment check above.
+        let Ok(metadata) = fs::metadata(path) else {
+            continue;
+        };
+        if !metadata.is_file() || is_large_binary(path, metadata.len()) {
+            continue;
+        }
+        let Some(mtime) = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as u64)
+        else {
+            continue;
+        };
+        let Ok(relative) = path.strip_prefix(&root) else {
+            continue;
+        };
+
+        files.push(WorkspaceRecentFile {
+            name: entry.file_name().to_string_lossy().to_string(),
+            path: normalize_zip_path(relative),
+            mtime,
+            size: metadata.len(),
+        });
+    }
+
+    files.sort_by(|a, b| b.mtime.cmp(&a.mtime).then_with(|| a.path.cmp(&b.path)));
+    files.truncate(limit);
+    Ok(files)
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WorkspaceExportSummary {
@@ -547,7 +1404,7 @@ fn normalize_zip_path(path: &Path) -> String {
     path.to_string_lossy().replace('\\', "/")
 }
 
-fn is_secret_name(name: &str) -> bool {
+pub fn is_secret_name(name: &str) -> bool {
     let lower = name.to_lowercase();
     if lower == ".env" || lower.starts_with(".env.") {
         return true;
@@ -579,12 +1436,14 @@ fn collect_workspace_entries(
     let mut entries: Vec<(PathBuf, String)> = Vec::new();
     let mut excluded: Vec<String> = Vec::new();
 
-    let config_path = workspace_root.join("opencode.json");
-    if config_path.exists() && config_path.is_file() {
-        if should_exclude(&config_path) {
-            excluded.push("opencode.json".to_string());
-        } else {
-            entries.push((config_path, "opencode.json".to_string()));
+    for name in ["opencode.jsonc", "opencode.json"] {
+        let config_path = workspace_root.join(name);
+        if config_path.exists() && config_path.is_file() {
+            if should_exclude(&config_path) {
+                excluded.push(name.to_string());
+            } else {
+                entries.push((config_path, name.to_string()));
+            }
         }
     }
 
@@ -707,6 +1566,58 @@ pub fn workspace_export_config(
     })
 }
 
+/// Shell script that reproduces a local workspace on another machine: installs
+/// OpenCode, recreates the folder with its config, skills and templates, and exports
+/// the engine environment. Secrets become `<PLACEHOLDER>` values.
+#[tauri::command]
+pub fn export_setup_script(app: tauri::AppHandle, workspace_id: String) -> Result<String, String> {
+    let workspace_id = workspace_id.trim().to_string();
+    if workspace_id.is_empty() {
+        return Err("workspaceId is required".to_string());
+    }
+
+    let state = load_workspace_state(&app)?;
+    let workspace = state
+        .workspaces
+        .iter()
+        .find(|w| w.id == workspace_id)
+        .ok_or_else(|| "Unknown workspaceId".to_string())?;
+
+    if workspace.workspace_type != WorkspaceType::Local {
+        return Err("Setup scripts are only supported for local workspaces".to_string());
+    }
+
+    let workspace_root = PathBuf::from(&workspace.path);
+    if !workspace_root.exists() {
+        return Err(format!(
+            "Workspace path not found: {}",
+            workspace_root.display()
+        ));
+    }
+
+    let (mut entries, _excluded) = collect_workspace_entries(&workspace_root)?;
+    // Installed plugin dependencies are restored by OpenCode itself.
+    entries.retain(|(_, relative)| !relative.split('/').any(|part| part == "node_modules"));
+
+    let templates = templates_dir(&workspace.path);
+    if templates.is_dir() {
+        for entry in WalkDir::new(&templates).sort_by_file_name() {
+            let entry = entry.map_err(|e| e.to_string())?;
+            if !entry.file_type().is_file() || should_exclude(entry.path()) {
+                continue;
+            }
+            let absolute = entry.path().to_path_buf();
+            let relative = absolute
+                .strip_prefix(&workspace_root)
+                .map_err(|e| format!("Failed to compute relative path: {e}"))?;
+            let relative = normalize_zip_path(relative);
+            entries.push((absolute, relative));
+        }
+    }
+
+    Ok(build_setup_script(workspace, &entries))
+}
+
 #[tauri::command]
 pub fn workspace_import_config(
     app: tauri::AppHandle,
@@ -755,7 +1666,8 @@ pub fn workspace_import_config(
         }) {
             return Err("Archive contains an unsafe path".to_string());
         }
-        if !(name == "opencode.json" || name.starts_with(".opencode/")) {
+        if !(name == "opencode.json" || name == "opencode.jsonc" || name.starts_with(".opencode/"))
+        {
             continue;
         }
         if let Some(file_name) = entry_path.file_name().and_then(|entry| entry.to_str()) {
@@ -808,11 +1720,7 @@ pub fn workspace_import_config(
                     }
                 }
             }
-            fs::write(
-                &openwork_path,
-                serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?,
-            )
-            .map_err(|e| format!("Failed to write {}: {e}", openwork_path.display()))?;
+            write_openwork_config(&openwork_path, &config)?;
         }
     } else {
         let config = WorkspaceOpenworkConfig::new(&target_dir, &preset, now_ms());
@@ -820,11 +1728,7 @@ pub fn workspace_import_config(
             fs::create_dir_all(parent)
                 .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
         }
-        fs::write(
-            &openwork_path,
-            serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?,
-        )
-        .map_err(|e| format!("Failed to write {}: {e}", openwork_path.display()))?;
+        write_openwork_config(&openwork_path, &config)?;
     }
 
     let name = workspace_name
@@ -855,6 +1759,7 @@ pub fn workspace_import_config(
         openwork_host_url: None,
         openwork_workspace_id: None,
         openwork_workspace_name: None,
+        exists: true,
     });
     state.active_id = id.clone();
     save_workspace_state(&app, &state)?;
@@ -867,3 +1772,41 @@ pub fn workspace_import_config(
         workspaces: state.workspaces,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn openwork_config(roots: &[&str], updated_at: Option<u64>) -> WorkspaceOpenworkConfig {
+        WorkspaceOpenworkConfig {
+            authorized_roots: roots.iter().map(|root| root.to_string()).collect(),
+            updated_at,
+            ..WorkspaceOpenworkConfig::default()
+        }
+    }
+
+    #[test]
+    fn unstamped_write_removes_root_from_legacy_file() {
+        let existing = openwork_config(&["/a", "/b"], None);
+        let incoming = openwork_config(&["/a"], None);
+        let merged = merge_openwork_config(existing, incoming).unwrap();
+        assert_eq!(merged.authorized_roots, vec!["/a".to_string()]);
+    }
+
+    #[test]
+    fn unstamped_write_over_stamped_file_is_unioned() {
+        let existing = openwork_config(&["/a", "/b"], Some(10));
+        let incoming = openwork_config(&["/c"], None);
+        let merged = merge_openwork_config(existing, incoming).unwrap();
+        assert_eq!(merged.authorized_roots, vec!["/a", "/b", "/c"]);
+        assert_eq!(merged.updated_at, Some(10));
+    }
+
+    #[test]
+    fn stale_stamped_write_conflicts() {
+        let existing = openwork_config(&["/a"], Some(20));
+        let incoming = openwork_config(&[], Some(10));
+        let error = merge_openwork_config(existing, incoming).unwrap_err();
+        assert!(error.starts_with("Conflict:"));
+    }
+}
@@ -1,8 +1,27 @@
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::types::{ExecResult, OpencodeConfigFile};
+use serde_json::Value;
+
+use crate::fs::{io_error_message, write_atomic};
+use crate::types::{
+    ConfigBackup, ConfigContentValidation, ConfigIssue, ConfigProfile, ConfigValidation,
+    ExecResult, OpencodeConfigFile,
+};
+use crate::utils::{now_ms, StableHasher};
+
+const MAX_CONFIG_BACKUPS: usize = 20;
+
+fn config_base_dir() -> Result<PathBuf, String> {
+    if let Ok(dir) = env::var("XDG_CONFIG_HOME") {
+        Ok(PathBuf::from(dir))
+    } else if let Ok(home) = env::var("HOME") {
+        Ok(PathBuf::from(home).join(".config"))
+    } else {
+        Err("Unable to resolve config directory".to_string())
+    }
+}
 
 fn opencode_config_candidates(
     scope: &str,
@@ -17,15 +36,7 @@ fn opencode_config_candidates(
             Ok((root.join("opencode.jsonc"), root.join("opencode.json")))
         }
         "global" => {
-            let base = if let Ok(dir) = env::var("XDG_CONFIG_HOME") {
-                PathBuf::from(dir)
-            } else if let Ok(home) = env::var("HOME") {
-                PathBuf::from(home).join(".config")
-            } else {
-                return Err("Unable to resolve config directory".to_string());
-            };
-
-            let root = base.join("opencode");
+            let root = config_base_dir()?.join("opencode");
             Ok((root.join("opencode.jsonc"), root.join("opencode.json")))
         }
         _ => Err("scope must be 'project' or 'global'".to_string()),
@@ -46,6 +57,22 @@ pub fn resolve_opencode_config_path(scope: &str, project_dir: &str) -> Result<Pa
     Ok(jsonc_path)
 }
 
+/// Parses opencode config content. Comments and trailing commas are accepted, as
+/// OpenCode accepts them in both opencode.json and opencode.jsonc.
+pub fn parse_opencode_config(raw: &str) -> Result<Value, String> {
+    json5::from_str(raw).map_err(|e| e.to_string())
+}
+
+/// Rewriting a config through serde_json drops comments, so content that is not plain
+/// JSON is first copied to `<path>.bak`.
+pub fn backup_jsonc_config(path: &Path, raw: &str) -> Result<(), String> {
+    if serde_json::from_str::<Value>(raw).is_ok() {
+        return Ok(());
+    }
+    let backup = PathBuf::from(format!("{}.bak", path.display()));
+    fs::write(&backup, raw).map_err(|e| io_error_message("write", &backup, &e))
+}
+
 pub fn read_opencode_config(scope: &str, project_dir: &str) -> Result<OpencodeConfigFile, String> {
     let path = resolve_opencode_config_path(scope.trim(), project_dir)?;
     let exists = path.exists();
@@ -66,6 +93,159 @@ pub fn read_opencode_config(scope: &str, project_dir: &str) -> Result<OpencodeCo
     })
 }
 
+pub fn validate_opencode_config(
+    scope: &str,
+    project_dir: &str,
+) -> Result<ConfigValidation, String> {
+    let config = read_opencode_config(scope, project_dir)?;
+    let mut result = ConfigValidation {
+        path: config.path,
+        exists: config.exists,
+        valid: true,
+        error: None,
+        line: None,
+        column: None,
+    };
+
+    let Some(content) = config.content else {
+        return Ok(result);
+    };
+
+    if let Some(issue) = check_opencode_config_content(&content, false)
+        .errors
+        .into_iter()
+        .next()
+    {
+        result.valid = false;
+        result.error = Some(issue.message);
+        result.line = issue.line;
+        result.column = issue.column;
+    }
+
+    Ok(result)
+}
+
+#[derive(Clone, Copy)]
+enum ConfigValueKind {
+    String,
+    Bool,
+    Array,
+    Object,
+    BoolOrObject,
+}
+
+/// Top-level keys of the opencode.ai config schema with their expected types. Keys not
+/// listed here are accepted as-is so newer OpenCode options are not rejected.
+const CONFIG_SCHEMA_KEYS: &[(&str, ConfigValueKind)] = &[
+    ("$schema", ConfigValueKind::String),
+    ("theme", ConfigValueKind::String),
+    ("model", ConfigValueKind::String),
+    ("small_model", ConfigValueKind::String),
+    ("username", ConfigValueKind::String),
+    ("share", ConfigValueKind::String),
+    ("autoupdate", ConfigValueKind::Bool),
+    ("snapshot", ConfigValueKind::Bool),
+    ("plugin", ConfigValueKind::Array),
+    ("instructions", ConfigValueKind::Array),
+    ("disabled_providers", ConfigValueKind::Array),
+    ("enabled_providers", ConfigValueKind::Array),
+    ("mcp", ConfigValueKind::Object),
+    ("agent", ConfigValueKind::Object),
+    ("mode", ConfigValueKind::Object),
+    ("command", ConfigValueKind::Object),
+    ("provider", ConfigValueKind::Object),
+    ("permission", ConfigValueKind::Object),
+    ("tools", ConfigValueKind::Object),
+    ("keybinds", ConfigValueKind::Object),
+    ("tui", ConfigValueKind::Object),
+    ("experimental", ConfigValueKind::Object),
+    ("formatter", ConfigValueKind::BoolOrObject),
+    ("lsp", ConfigValueKind::BoolOrObject),
+];
+
+fn config_value_matches(value: &Value, kind: ConfigValueKind) -> bool {
+    match kind {
+        ConfigValueKind::String => value.is_string(),
+        ConfigValueKind::Bool => value.is_boolean(),
+        ConfigValueKind::Array => value.is_array(),
+        ConfigValueKind::Object => value.is_object(),
+        ConfigValueKind::BoolOrObject => value.is_boolean() || value.is_object(),
+    }
+}
+
+fn config_kind_label(kind: ConfigValueKind) -> &'static str {
+    match kind {
+        ConfigValueKind::String => "a string",
+        ConfigValueKind::Bool => "a boolean",
+        ConfigValueKind::Array => "an array",
+        ConfigValueKind::Object => "an object",
+        ConfigValueKind::BoolOrObject => "a boolean or an object",
+    }
+}
+
+/// 1-based line and column of the first `"key"` in `content`; a best-effort position
+/// for schema errors, which the parsed value no longer carries.
+fn key_position(content: &str, key: &str) -> Option<(usize, usize)> {
+    let offset = content.find(&format!("\"{key}\""))?;
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    Some((line, before[line_start..].chars().count() + 1))
+}
+
+fn config_issue(
+    message: String,
+    key: Option<&str>,
+    position: Option<(usize, usize)>,
+) -> ConfigIssue {
+    ConfigIssue {
+        message,
+        key: key.map(str::to_string),
+        line: position.map(|(line, _)| line),
+        column: position.map(|(_, column)| column),
+    }
+}
+
+/// Parses `content` as JSON (comments and trailing commas are accepted, as in
+/// opencode.jsonc). With `check_schema`, known top-level keys are also type-checked
+/// against the opencode.ai config schema.
+pub fn check_opencode_config_content(content: &str, check_schema: bool) -> ConfigContentValidation {
+    let mut errors = Vec::new();
+    match json5::from_str::<Value>(content) {
+        Ok(Value::Object(map)) => {
+            if check_schema {
+                for (key, kind) in CONFIG_SCHEMA_KEYS {
+                    let Some(value) = map.get(*key) else {
+                        continue;
+                    };
+                    if !config_value_matches(value, *kind) {
+                        errors.push(config_issue(
+                            format!("\"{key}\" must be {}", config_kind_label(*kind)),
+                            Some(key),
+                            key_position(content, key),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(_) => errors.push(config_issue(
+            "Config must be a JSON object".to_string(),
+            None,
+            None,
+        )),
+        Err(json5::Error::Message { msg, location }) => errors.push(config_issue(
+            msg,
+            None,
+            location.map(|loc| (loc.line, loc.column)),
+        )),
+    }
+
+    ConfigContentValidation {
+        valid: errors.is_empty(),
+        errors,
+    }
+}
+
 pub fn write_opencode_config(
     scope: &str,
     project_dir: &str,
@@ -75,10 +255,10 @@ pub fn write_opencode_config(
 
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create config dir {}: {e}", parent.display()))?;
+            .map_err(|e| io_error_message("create config dir", parent, &e))?;
     }
 
-    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    write_atomic(&path, content.as_bytes())?;
 
     Ok(ExecResult {
         ok: true,
@@ -87,3 +267,301 @@ pub fn write_opencode_config(
         stderr: String::new(),
     })
 }
+
+pub fn profiles_dir() -> Result<PathBuf, String> {
+    Ok(config_base_dir()?.join("openwork").join("profiles"))
+}
+
+fn validate_profile_name(name: &str) -> Result<String, String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err("profileName is required".to_string());
+    }
+    if !trimmed
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("profileName may only contain letters, digits, '-' and '_'".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+pub fn save_config_profile(project_dir: &str, profile_name: &str) -> Result<ExecResult, String> {
+    let name = validate_profile_name(profile_name)?;
+    let config = read_opencode_config("project", project_dir)?;
+    let Some(content) = config.content else {
+        return Err(format!("No project config found at {}", config.path));
+    };
+
+    let dir = profiles_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    let dest = dir.join(format!("{name}.json"));
+    fs::write(&dest, content).map_err(|e| format!("Failed to write {}: {e}", dest.display()))?;
+
+    Ok(ExecResult {
+        ok: true,
+        status: 0,
+        stdout: format!("Saved profile to {}", dest.display()),
+        stderr: String::new(),
+    })
+}
+
+pub fn apply_config_profile(project_dir: &str, profile_name: &str) -> Result<ExecResult, String> {
+    let name = validate_profile_name(profile_name)?;
+    let source = profiles_dir()?.join(format!("{name}.json"));
+    if !source.is_file() {
+        return Err(format!("Profile {name} not found"));
+    }
+    let content = fs::read_to_string(&source)
+        .map_err(|e| format!("Failed to read {}: {e}", source.display()))?;
+
+    let path = resolve_opencode_config_path("project", project_dir)?;
+    if path.exists() {
+        let backup = PathBuf::from(format!("{}.bak", path.display()));
+        fs::copy(&path, &backup)
+            .map_err(|e| format!("Failed to back up {}: {e}", path.display()))?;
+    }
+
+    write_opencode_config("project", project_dir, &content)
+}
+
+pub fn list_config_profiles() -> Result<Vec<ConfigProfile>, String> {
+    let dir = profiles_dir()?;
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut out = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(|e| format!("Failed to read {}: {e}", dir.display()))? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let modified_at = entry
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as u64);
+        out.push(ConfigProfile {
+            name: name.to_string(),
+            modified_at,
+        });
+    }
+
+    out.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(out)
+}
+
+/// Backups live outside the project, keyed by a hash of the config path so project and
+/// global configs (and different projects) never share a folder.
+fn config_backups_dir(config_path: &Path) -> Result<PathBuf, String> {
+    let mut hasher = StableHasher::default();
+    hasher.write(config_path.to_string_lossy().as_bytes());
+    Ok(config_base_dir()?
+        .join("openwork")
+        .join("config-backups")
+        .join(format!("{:016x}", hasher.finish())))
+}
+
+/// Backup ids are `<created_at_ms>-<file name>`, e.g. `1760000000000-opencode.jsonc`.
+fn backup_created_at(id: &str) -> Option<u64> {
+    let (created_at, name) = id.split_once('-')?;
+    if !name.starts_with("opencode.json") {
+        return None;
+    }
+    created_at.parse().ok()
+}
+
+fn read_config_backups(dir: &Path) -> Result<Vec<ConfigBackup>, String> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut out = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {e}", dir.display()))? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let Some(id) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let Some(created_at) = backup_created_at(&id) else {
+            continue;
+        };
+        let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+        out.push(ConfigBackup {
+            id,
+            created_at,
+            size,
+        });
+    }
+
+    out.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    Ok(out)
+}
+
+pub fn backup_opencode_config(scope: &str, project_dir: &str) -> Result<ConfigBackup, String> {
+    let path = resolve_opencode_config_path(scope.trim(), project_dir)?;
+    if !path.is_file() {
+        return Err(format!(
+            "No {} config found at {}",
+            scope.trim(),
+            path.display()
+        ));
+    }
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("opencode.json");
+
+    let dir = config_backups_dir(&path)?;
+    fs::create_dir_all(&dir).map_err(|e| io_error_message("create", &dir, &e))?;
+    // Never go below the newest backup, or rotation could delete the one just made.
+    let newest = read_config_backups(&dir)?
+        .first()
+        .map(|backup| backup.created_at + 1);
+    let mut created_at = now_ms().max(newest.unwrap_or(0));
+    while dir.join(format!("{created_at}-{file_name}")).exists() {
+        created_at += 1;
+    }
+    let id = format!("{created_at}-{file_name}");
+    let dest = dir.join(&id);
+    let size = fs::copy(&path, &dest).map_err(|e| io_error_message("write", &dest, &e))?;
+
+    for stale in read_config_backups(&dir)?.iter().skip(MAX_CONFIG_BACKUPS) {
+        let _ = fs::remove_file(dir.join(&stale.id));
+    }
+
+    Ok(ConfigBackup {
+        id,
+        created_at,
+        size,
+    })
+}
+
+/// Newest first.
+pub fn list_opencode_config_backups(
+    scope: &str,
+    project_dir: &str,
+) -> Result<Vec<ConfigBackup>, String> {
+    let path = resolve_opencode_config_path(scope.trim(), project_dir)?;
+    read_config_backups(&config_backups_dir(&path)?)
+}
+
+/// The current config is backed up first, so a restore can itself be undone.
+pub fn restore_opencode_config(
+    scope: &str,
+    project_dir: &str,
+    backup_id: &str,
+) -> Result<ExecResult, String> {
+    let backup_id = backup_id.trim();
+    if backup_id.contains(['/', '\\']) || backup_created_at(backup_id).is_none() {
+        return Err(format!("Invalid backup id: {backup_id}"));
+    }
+    let path = resolve_opencode_config_path(scope.trim(), project_dir)?;
+    let source = config_backups_dir(&path)?.join(backup_id);
+    if !source.is_file() {
+        return Err(format!("Backup {backup_id} not found"));
+    }
+    let content = fs::read_to_string(&source)
+        .map_err(|e| format!("Failed to read {}: {e}", source.display()))?;
+
+    let previous = if path.is_file() {
+        Some(backup_opencode_config(scope, project_dir)?.id)
+    } else {
+        None
+    };
+    let mut result = write_opencode_config(scope, project_dir, &content)?;
+    result.stdout = match previous {
+        Some(previous) => format!(
+            "Restored {backup_id} to {} (previous config saved as {previous})",
+            path.display()
+        ),
+        None => format!("Restored {backup_id} to {}", path.display()),
+    };
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn unique_temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("openwork-{name}-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn reports_position_of_invalid_jsonc() {
+        let content = "{\n  // comment\n  \"model\": \"x\",\n  \"theme\": ,\n}";
+        let result = check_opencode_config_content(content, false);
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line, Some(4));
+        assert_eq!(result.errors[0].column, Some(12));
+
+        let jsonc = "{\n  // comment\n  \"plugin\": [\"a\",],\n}";
+        assert!(check_opencode_config_content(jsonc, true).valid);
+    }
+
+    #[test]
+    fn rejects_malformed_backup_ids() {
+        assert_eq!(
+            backup_created_at("1760000000000-opencode.jsonc"),
+            Some(1760000000000)
+        );
+        assert_eq!(backup_created_at("opencode.json"), None);
+        assert_eq!(backup_created_at("abc-opencode.json"), None);
+        assert_eq!(backup_created_at("1760000000000-notes.txt"), None);
+
+        let project = unique_temp_dir("config-restore");
+        let project_dir = project.to_string_lossy();
+        for id in [
+            "../1760000000000-opencode.json",
+            "1760000000000-opencode.json/../../secret",
+            "..\\1760000000000-opencode.json",
+            "not-a-backup",
+        ] {
+            let err = restore_opencode_config("project", &project_dir, id).unwrap_err();
+            assert!(err.starts_with("Invalid backup id"), "{id}: {err}");
+        }
+
+        let _ = fs::remove_dir_all(&project);
+    }
+
+    #[test]
+    fn keeps_only_newest_backups() {
+        let base = unique_temp_dir("config-backups");
+        let original = env::var("XDG_CONFIG_HOME").ok();
+        env::set_var("XDG_CONFIG_HOME", base.join("config"));
+
+        let project = base.join("project");
+        fs::create_dir_all(&project).unwrap();
+        let project_dir = project.to_string_lossy();
+        fs::write(project.join("opencode.json"), "{}").unwrap();
+
+        let created: Vec<String> = (0..MAX_CONFIG_BACKUPS + 5)
+            .map(|_| backup_opencode_config("project", &project_dir).unwrap().id)
+            .collect();
+        let listed: Vec<String> = list_opencode_config_backups("project", &project_dir)
+            .unwrap()
+            .into_iter()
+            .map(|backup| backup.id)
+            .collect();
+
+        match original {
+            Some(value) => env::set_var("XDG_CONFIG_HOME", value),
+            None => env::remove_var("XDG_CONFIG_HOME"),
+        }
+        let _ = fs::remove_dir_all(&base);
+
+        let mut newest: Vec<String> = created[created.len() - MAX_CONFIG_BACKUPS..].to_vec();
+        newest.reverse();
+        assert_eq!(listed, newest);
+    }
+}
@@ -0,0 +1,70 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde_json::json;
+use tauri::{AppHandle, Emitter};
+
+use crate::paths::candidate_xdg_data_dirs;
+
+const AUTH_DETECTED_EVENT: &str = "openwork://auth-detected";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tracks the active auth.json watch. Bumping the generation stops any running watcher.
+#[derive(Default)]
+pub struct AuthWatchState {
+    generation: Arc<AtomicU64>,
+}
+
+fn auth_json_candidates() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(data_home) = std::env::var_os("XDG_DATA_HOME") {
+        dirs.push(PathBuf::from(data_home));
+    }
+    dirs.extend(candidate_xdg_data_dirs());
+    dirs.into_iter()
+        .map(|dir| dir.join("opencode").join("auth.json"))
+        .collect()
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).ok()?.modified().ok()
+}
+
+impl AuthWatchState {
+    /// Polls the candidate data dirs until an `opencode/auth.json` appears or changes,
+    /// emitting `openwork://auth-detected` once, or until `timeout` elapses.
+    pub fn start(&self, app: &AppHandle, timeout: Duration) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let current = self.generation.clone();
+        let app = app.clone();
+
+        std::thread::spawn(move || {
+            let candidates = auth_json_candidates();
+            let baseline: Vec<Option<SystemTime>> =
+                candidates.iter().map(|path| modified_at(path)).collect();
+            let deadline = Instant::now() + timeout;
+
+            while Instant::now() < deadline && current.load(Ordering::SeqCst) == generation {
+                std::thread::sleep(POLL_INTERVAL);
+                let detected = candidates.iter().zip(&baseline).find(|(path, before)| {
+                    let now = modified_at(path);
+                    now.is_some() && now != **before
+                });
+                if let Some((path, _)) = detected {
+                    println!("[auth-watch] detected {}", path.display());
+                    let _ = app.emit(
+                        AUTH_DETECTED_EVENT,
+                        json!({ "path": path.to_string_lossy().to_string() }),
+                    );
+                    return;
+                }
+            }
+        });
+    }
+
+    pub fn stop(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+}
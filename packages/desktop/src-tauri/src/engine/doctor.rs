@@ -1,4 +1,5 @@
 use std::ffi::OsStr;
+use std::io::Read;
 use std::path::Path;
 
 use crate::engine::paths::{
@@ -9,6 +10,8 @@ use crate::engine::paths::{
 use crate::platform::command_for_program;
 use crate::utils::truncate_output;
 
+pub const MIN_OPENCODE_VERSION: &str = "1.0.0";
+
 pub fn opencode_version(program: &OsStr) -> Option<String> {
     let output = command_for_program(Path::new(program))
         .arg("--version")
@@ -27,6 +30,27 @@ pub fn opencode_version(program: &OsStr) -> Option<String> {
     None
 }
 
+fn parse_version(raw: &str) -> Option<Vec<u64>> {
+    let token = raw
+        .split_whitespace()
+        .map(|part| part.trim_start_matches('v'))
+        .find(|part| part.starts_with(|c: char| c.is_ascii_digit()))?;
+
+    token
+        .split(['.', '-', '+'])
+        .take(3)
+        .map(|part| part.parse::<u64>().ok())
+        .collect()
+}
+
+/// Unparseable version output is treated as acceptable so odd builds are not blocked.
+pub fn version_meets_minimum(raw: &str, minimum: &str) -> bool {
+    match (parse_version(raw), parse_version(minimum)) {
+        (Some(version), Some(minimum)) => version >= minimum,
+        _ => true,
+    }
+}
+
 pub fn opencode_serve_help(program: &OsStr) -> (bool, Option<i32>, Option<String>, Option<String>) {
     match command_for_program(Path::new(program))
         .arg("serve")
@@ -56,6 +80,147 @@ pub fn opencode_serve_help(program: &OsStr) -> (bool, Option<i32>, Option<String
     }
 }
 
+/// Subcommand names listed under `Commands:` in yargs-style `--help` output, e.g.
+/// `  opencode serve    starts a headless opencode server` yields `serve`.
+pub fn parse_help_subcommands(help: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    let mut in_commands = false;
+    for line in help.lines() {
+        let trimmed = line.trim();
+        if !line.starts_with(char::is_whitespace) {
+            in_commands = trimmed.eq_ignore_ascii_case("commands:");
+            continue;
+        }
+        if !in_commands || trimmed.is_empty() {
+            continue;
+        }
+        let mut tokens = trimmed.split_whitespace();
+        let mut name = tokens.next().unwrap_or_default();
+        if name == "opencode" {
+            name = tokens.next().unwrap_or_default();
+        }
+        if name.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+            commands.push(name.to_string());
+        }
+    }
+    commands
+}
+
+pub fn opencode_subcommands(program: &OsStr) -> Vec<String> {
+    let Ok(output) = command_for_program(Path::new(program))
+        .arg("--help")
+        .output()
+    else {
+        return Vec::new();
+    };
+    let help = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    parse_help_subcommands(&help)
+}
+
+/// `build.rs` writes a bash stub in place of a missing sidecar for dev builds.
+pub fn is_sidecar_stub(path: &Path) -> bool {
+    let mut head = [0u8; 256];
+    let Ok(read) = std::fs::File::open(path).and_then(|mut file| file.read(&mut head)) else {
+        return false;
+    };
+    let head = String::from_utf8_lossy(&head[..read]);
+    head.starts_with("#!") && head.contains("Sidecar missing")
+}
+
+fn read_u16(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u16> {
+    let raw: [u8; 2] = bytes.get(offset..offset + 2)?.try_into().ok()?;
+    Some(if big_endian {
+        u16::from_be_bytes(raw)
+    } else {
+        u16::from_le_bytes(raw)
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let raw: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+    Some(if big_endian {
+        u32::from_be_bytes(raw)
+    } else {
+        u32::from_le_bytes(raw)
+    })
+}
+
+fn mach_cpu_arch(cpu_type: u32) -> Option<&'static str> {
+    match cpu_type {
+        0x0100_0007 => Some("x86_64"),
+        0x0100_000c => Some("aarch64"),
+        _ => None,
+    }
+}
+
+/// Architectures an executable header declares: ELF, PE, thin Mach-O, or every slice of a
+/// universal binary. Empty for scripts and anything unrecognised.
+pub fn executable_archs(header: &[u8]) -> Vec<&'static str> {
+    if header.starts_with(b"\x7fELF") {
+        let big_endian = header.get(5) == Some(&2);
+        let arch = match read_u16(header, 18, big_endian) {
+            Some(0x3e) => Some("x86_64"),
+            Some(0xb7) => Some("aarch64"),
+            Some(0x03) => Some("x86"),
+            Some(0x28) => Some("arm"),
+            _ => None,
+        };
+        return arch.into_iter().collect();
+    }
+
+    if header.starts_with(b"MZ") {
+        let arch = read_u32(header, 0x3c, false)
+            .map(|offset| offset as usize)
+            .filter(|offset| header.get(*offset..*offset + 4) == Some(b"PE\0\0".as_slice()))
+            .and_then(|offset| read_u16(header, offset + 4, false))
+            .and_then(|machine| match machine {
+                0x8664 => Some("x86_64"),
+                0xaa64 => Some("aarch64"),
+                0x014c => Some("x86"),
+                _ => None,
+            });
+        return arch.into_iter().collect();
+    }
+
+    match read_u32(header, 0, true) {
+        Some(0xcafe_babe) => {
+            let count = read_u32(header, 4, true).unwrap_or(0) as usize;
+            (0..count.min(8))
+                .filter_map(|index| read_u32(header, 8 + index * 20, true))
+                .filter_map(mach_cpu_arch)
+                .collect()
+        }
+        Some(0xcffa_edfe) => read_u32(header, 4, false)
+            .and_then(mach_cpu_arch)
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Reports when `path` is a native binary that can't run natively on this host, e.g. an
+/// x86_64 sidecar on Apple Silicon that only works under Rosetta.
+pub fn sidecar_arch_mismatch(path: &Path) -> Option<String> {
+    let mut header = [0u8; 4096];
+    let read = std::fs::File::open(path)
+        .and_then(|mut file| file.read(&mut header))
+        .ok()?;
+    let archs = executable_archs(&header[..read]);
+    let host = std::env::consts::ARCH;
+    if archs.is_empty() || archs.contains(&host) {
+        return None;
+    }
+    Some(format!(
+        "{} is built for {} but this host is {host}",
+        path.display(),
+        archs.join("/")
+    ))
+}
+
 pub fn resolve_sidecar_candidate(
     prefer_sidecar: bool,
     resource_dir: Option<&Path>,
@@ -88,6 +253,14 @@ pub fn resolve_sidecar_candidate(
     );
 
     for candidate in candidates {
+        if candidate.is_file() && is_sidecar_stub(&candidate) {
+            notes.push(format!(
+                "Bundled sidecar is a build stub, skipping: {}",
+                candidate.display()
+            ));
+            continue;
+        }
+
         if candidate.is_file() {
             notes.push(format!("Using bundled sidecar: {}", candidate.display()));
             return (Some(candidate), notes);
@@ -191,6 +364,30 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    #[cfg(not(windows))]
+    fn skips_build_stub_sidecar() {
+        let dir = unique_temp_dir("sidecar-stub-test");
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+
+        let sidecar_path = dir.join(crate::engine::paths::opencode_executable_name());
+        std::fs::write(
+            &sidecar_path,
+            b"#!/usr/bin/env bash\necho 'Sidecar missing. Install the binary or set the *_BIN_PATH env var.'\nexit 1\n",
+        )
+        .expect("create stub sidecar");
+
+        let (resolved, notes) = resolve_sidecar_candidate(true, None, Some(dir.as_path()));
+        assert_ne!(resolved.as_ref(), Some(&sidecar_path));
+        assert!(
+            notes.iter().any(|note| note.contains("build stub")),
+            "missing stub note: {:?}",
+            notes
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     #[cfg(not(windows))]
     fn resolve_engine_path_prefers_sidecar() {
@@ -233,4 +430,47 @@ mod tests {
         let _ = std::fs::remove_dir_all(&override_dir);
         let _ = std::fs::remove_dir_all(&sidecar_dir);
     }
+
+    #[test]
+    fn reads_executable_arch_from_headers() {
+        let mut elf = vec![0u8; 64];
+        elf[..4].copy_from_slice(b"\x7fELF");
+        elf[5] = 1;
+        elf[18] = 0xb7;
+        assert_eq!(executable_archs(&elf), vec!["aarch64"]);
+
+        let mut macho = vec![0u8; 32];
+        macho[..4].copy_from_slice(&0xfeed_facf_u32.to_le_bytes());
+        macho[4..8].copy_from_slice(&0x0100_0007_u32.to_le_bytes());
+        assert_eq!(executable_archs(&macho), vec!["x86_64"]);
+
+        let mut fat = vec![0u8; 48];
+        fat[..4].copy_from_slice(&0xcafe_babe_u32.to_be_bytes());
+        fat[4..8].copy_from_slice(&2u32.to_be_bytes());
+        fat[8..12].copy_from_slice(&0x0100_0007_u32.to_be_bytes());
+        fat[28..32].copy_from_slice(&0x0100_000c_u32.to_be_bytes());
+        assert_eq!(executable_archs(&fat), vec!["x86_64", "aarch64"]);
+
+        assert!(executable_archs(b"#!/bin/bash\necho hi\n").is_empty());
+    }
+
+    #[test]
+    fn parses_subcommands_from_help() {
+        let help = concat!(
+            "opencode [project]\n\n",
+            "Commands:\n",
+            "  opencode completion    generate completion script\n",
+            "  opencode [project]     start opencode tui  [default]\n",
+            "  opencode serve         starts a headless opencode server\n",
+            "  opencode debug <cmd>   debugging and troubleshooting tools\n\n",
+            "Positionals:\n",
+            "  project  path to start opencode in\n\n",
+            "Options:\n",
+            "  -h, --help  show help\n",
+        );
+        assert_eq!(
+            parse_help_subcommands(help),
+            vec!["completion", "serve", "debug"]
+        );
+    }
 }
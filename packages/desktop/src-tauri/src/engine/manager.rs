@@ -1,8 +1,15 @@
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use tauri_plugin_shell::process::CommandChild;
 
-use crate::types::{EngineInfo, EngineRuntime};
+use crate::engine::pidfile::remove_engine_pidfile;
+use crate::platform::terminate_gracefully;
+use crate::types::{EngineCommand, EngineInfo, EngineRuntime};
+
+/// How long OpenCode gets to flush sessions and close sockets after SIGTERM.
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(3);
 
 #[derive(Default)]
 pub struct EngineManager {
@@ -15,6 +22,8 @@ pub struct EngineState {
     pub child: Option<CommandChild>,
     pub child_exited: bool,
     pub project_dir: Option<String>,
+    pub workspace_paths: Vec<String>,
+    pub prefer_sidecar: bool,
     pub hostname: Option<String>,
     pub port: Option<u16>,
     pub base_url: Option<String>,
@@ -22,9 +31,55 @@ pub struct EngineState {
     pub opencode_password: Option<String>,
     pub last_stdout: Option<String>,
     pub last_stderr: Option<String>,
+    pub started_at: Option<u64>,
+    pub restart_count: u32,
+    pub last_command: Option<EngineCommand>,
+    pub pidfile: Option<PathBuf>,
+    pub starting: bool,
+    /// Set by `engine_stop(keep_daemon)` so the next `engine_info` re-adopts the daemon.
+    pub daemon_kept: bool,
+}
+
+/// Held for the duration of an `engine_start` call; clears `EngineState::starting` on
+/// every return path.
+pub struct StartingGuard(Arc<Mutex<EngineState>>);
+
+impl Drop for StartingGuard {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.0.lock() {
+            state.starting = false;
+        }
+    }
+}
+
+/// The child taken out of `EngineState` by [`EngineManager::stop_locked`]. Call
+/// [`EngineShutdown::finish`] once the engine lock is released, since it can wait up to
+/// `STOP_GRACE_PERIOD` for the process to exit.
+#[must_use]
+pub struct EngineShutdown(Option<(CommandChild, bool)>);
+
+impl EngineShutdown {
+    pub fn finish(self) {
+        if let Some((child, exited)) = self.0 {
+            if exited || !terminate_gracefully(child.pid(), STOP_GRACE_PERIOD) {
+                let _ = child.kill();
+            }
+        }
+    }
 }
 
 impl EngineManager {
+    /// Marks a start as in progress, rejecting overlapping `engine_start` calls (e.g. a
+    /// double click) that would otherwise race to spawn and orphan a child.
+    pub fn begin_start(&self) -> Result<StartingGuard, String> {
+        let mut state = self.inner.lock().expect("engine mutex poisoned");
+        if state.starting {
+            return Err("Engine is already starting".to_string());
+        }
+        state.starting = true;
+        Ok(StartingGuard(self.inner.clone()))
+    }
+
     pub fn snapshot_locked(state: &mut EngineState) -> EngineInfo {
         let (running, pid) = match state.child.as_ref() {
             None => (false, None),
@@ -50,19 +105,25 @@ impl EngineManager {
         }
     }
 
-    pub fn stop_locked(state: &mut EngineState) {
-        if let Some(child) = state.child.take() {
-            let _ = child.kill();
+    pub fn stop_locked(state: &mut EngineState) -> EngineShutdown {
+        let shutdown = EngineShutdown(state.child.take().map(|child| (child, state.child_exited)));
+        if let Some(pidfile) = state.pidfile.take() {
+            remove_engine_pidfile(&pidfile);
         }
         state.child_exited = true;
         state.runtime = EngineRuntime::Direct;
         state.base_url = None;
         state.project_dir = None;
+        state.workspace_paths.clear();
+        state.prefer_sidecar = false;
         state.hostname = None;
         state.port = None;
         state.opencode_username = None;
         state.opencode_password = None;
         state.last_stdout = None;
         state.last_stderr = None;
+        state.started_at = None;
+        state.daemon_kept = false;
+        shutdown
     }
 }
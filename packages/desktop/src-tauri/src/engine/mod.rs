@@ -1,4 +1,6 @@
+pub mod auth_watch;
 pub mod doctor;
 pub mod manager;
 pub mod paths;
+pub mod pidfile;
 pub mod spawn;
@@ -1,6 +1,9 @@
 use std::path::PathBuf;
+use std::sync::Mutex;
 
-use crate::paths::{home_dir, resolve_in_path};
+use crate::paths::{home_dir, path_entries, resolve_in_path};
+
+static PREFERRED_OPENCODE_BIN: Mutex<Option<PathBuf>> = Mutex::new(None);
 
 #[cfg(windows)]
 const OPENCODE_EXECUTABLE: &str = "opencode.exe";
@@ -60,6 +63,39 @@ pub fn candidate_opencode_paths() -> Vec<PathBuf> {
     candidates
 }
 
+/// Every existing opencode binary on PATH or in a known install location, PATH first.
+pub fn all_opencode_paths() -> Vec<PathBuf> {
+    #[cfg(windows)]
+    let names: &[&str] = &[OPENCODE_EXECUTABLE, OPENCODE_CMD];
+    #[cfg(not(windows))]
+    let names: &[&str] = &[OPENCODE_EXECUTABLE];
+
+    let on_path = path_entries()
+        .into_iter()
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)));
+
+    let mut found: Vec<PathBuf> = Vec::new();
+    for candidate in on_path.chain(candidate_opencode_paths()) {
+        if candidate.is_file() && !found.contains(&candidate) {
+            found.push(candidate);
+        }
+    }
+    found
+}
+
+pub fn set_preferred_opencode_binary(path: Option<PathBuf>) {
+    if let Ok(mut preferred) = PREFERRED_OPENCODE_BIN.lock() {
+        *preferred = path;
+    }
+}
+
+pub fn preferred_opencode_binary() -> Option<PathBuf> {
+    PREFERRED_OPENCODE_BIN
+        .lock()
+        .ok()
+        .and_then(|preferred| preferred.clone())
+}
+
 pub(crate) fn resolve_opencode_env_override() -> (Option<PathBuf>, Vec<String>) {
     let mut notes = Vec::new();
 
@@ -78,6 +114,20 @@ pub(crate) fn resolve_opencode_env_override() -> (Option<PathBuf>, Vec<String>)
         }
     }
 
+    if let Some(candidate) = preferred_opencode_binary() {
+        if candidate.is_file() {
+            notes.push(format!(
+                "Using preferred opencode binary: {}",
+                candidate.display()
+            ));
+            return (Some(candidate), notes);
+        }
+        notes.push(format!(
+            "Preferred opencode binary missing: {}",
+            candidate.display()
+        ));
+    }
+
     (None, notes)
 }
 
@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::Manager;
+
+use crate::fs::write_atomic;
+use crate::platform::{kill_process, terminate_gracefully};
+
+const REAP_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EnginePidfile {
+    pid: u32,
+    port: u16,
+    /// The app process that spawned the engine; the engine is only stale once it exits.
+    #[serde(default)]
+    owner_pid: Option<u32>,
+    /// Engine start time in seconds since the epoch, to tell a reused pid apart.
+    #[serde(default)]
+    started_at: Option<u64>,
+}
+
+pub fn engine_pidfile_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {e}"))?;
+    Ok(data_dir.join("opencode-engine.pid"))
+}
+
+pub fn write_engine_pidfile(path: &Path, pid: u32, port: u16) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    let entry = EnginePidfile {
+        pid,
+        port,
+        owner_pid: Some(std::process::id()),
+        started_at: process_start_time(&load_processes(&[pid]), pid),
+    };
+    let raw = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize engine pidfile: {e}"))?;
+    write_atomic(path, raw.as_bytes())
+}
+
+pub fn remove_engine_pidfile(path: &Path) {
+    let _ = fs::remove_file(path);
+}
+
+fn load_processes(pids: &[u32]) -> System {
+    let pids: Vec<Pid> = pids.iter().copied().map(Pid::from_u32).collect();
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&pids),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+    system
+}
+
+fn process_start_time(system: &System, pid: u32) -> Option<u64> {
+    system
+        .process(Pid::from_u32(pid))
+        .map(|process| process.start_time())
+}
+
+/// Terminates an `opencode serve` left behind by a crashed or force-quit session, then
+/// clears the pidfile. Engines whose owning app is still running (e.g. a second app
+/// instance) are left alone, as are pids that now belong to a different process.
+pub fn reap_stale_engine(path: &Path) -> Option<String> {
+    let raw = fs::read_to_string(path).ok()?;
+    let Ok(entry) = serde_json::from_str::<EnginePidfile>(&raw) else {
+        remove_engine_pidfile(path);
+        return None;
+    };
+
+    let owner = entry.owner_pid.unwrap_or(entry.pid);
+    let system = load_processes(&[entry.pid, owner]);
+    if entry
+        .owner_pid
+        .is_some_and(|owner| system.process(Pid::from_u32(owner)).is_some())
+    {
+        return None;
+    }
+    remove_engine_pidfile(path);
+    // Pidfiles from older builds carry no start time, so their pid cannot be trusted.
+    let started_at = entry.started_at?;
+    if process_start_time(&system, entry.pid) != Some(started_at) {
+        return None;
+    }
+
+    if !terminate_gracefully(entry.pid, REAP_GRACE_PERIOD) {
+        kill_process(entry.pid);
+    }
+    Some(format!(
+        "Stopped orphaned OpenCode engine (pid {}, port {})",
+        entry.pid, entry.port
+    ))
+}
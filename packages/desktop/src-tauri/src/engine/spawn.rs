@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::time::Duration;
 
 use tauri::async_runtime::Receiver;
 use tauri::AppHandle;
@@ -6,6 +7,7 @@ use tauri_plugin_shell::process::{CommandChild, CommandEvent};
 use tauri_plugin_shell::ShellExt;
 
 use crate::paths::{candidate_xdg_config_dirs, candidate_xdg_data_dirs, maybe_infer_xdg_home};
+use crate::types::EngineCommand;
 
 pub fn find_free_port() -> Result<u16, String> {
     let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).map_err(|e| e.to_string())?;
@@ -13,6 +15,31 @@ pub fn find_free_port() -> Result<u16, String> {
     Ok(port)
 }
 
+const DEFAULT_READY_TIMEOUT_MS: u64 = 15_000;
+
+/// How long `engine_start` waits for the engine to answer HTTP. Override with
+/// `OPENWORK_ENGINE_READY_TIMEOUT_MS`.
+pub fn engine_ready_timeout() -> Duration {
+    let ms = std::env::var("OPENWORK_ENGINE_READY_TIMEOUT_MS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|ms| *ms > 0)
+        .unwrap_or(DEFAULT_READY_TIMEOUT_MS);
+    Duration::from_millis(ms)
+}
+
+/// Any HTTP response counts, including a 401 from an auth-protected engine; only
+/// connection-level failures mean the server is not listening yet.
+pub fn engine_responding(base_url: &str) -> bool {
+    let response = ureq::get(&format!("{base_url}/config"))
+        .timeout(Duration::from_millis(500))
+        .call();
+    match response {
+        Ok(_) | Err(ureq::Error::Status(_, _)) => true,
+        Err(ureq::Error::Transport(_)) => false,
+    }
+}
+
 pub fn build_engine_args(bind_host: &str, port: u16) -> Vec<String> {
     vec![
         "serve".to_string(),
@@ -27,34 +54,18 @@ pub fn build_engine_args(bind_host: &str, port: u16) -> Vec<String> {
     ]
 }
 
-pub fn spawn_engine(
-    app: &AppHandle,
-    program: &Path,
-    hostname: &str,
-    port: u16,
-    project_dir: &str,
-    use_sidecar: bool,
+pub fn engine_env(
     opencode_username: Option<&str>,
     opencode_password: Option<&str>,
-) -> Result<(Receiver<CommandEvent>, CommandChild), String> {
-    let args = build_engine_args(hostname, port);
-
-    let command = if use_sidecar {
-        app.shell()
-            .sidecar("opencode")
-            .map_err(|e| format!("Failed to locate bundled OpenCode sidecar: {e}"))?
-    } else {
-        app.shell().command(program)
-    };
-
-    let mut command = command.args(args).current_dir(project_dir);
+) -> Vec<(&'static str, String)> {
+    let mut env = Vec::new();
 
     if let Some(xdg_data_home) = maybe_infer_xdg_home(
         "XDG_DATA_HOME",
         candidate_xdg_data_dirs(),
         Path::new("opencode/auth.json"),
     ) {
-        command = command.env("XDG_DATA_HOME", xdg_data_home);
+        env.push(("XDG_DATA_HOME", xdg_data_home));
     }
 
     let xdg_config_home = maybe_infer_xdg_home(
@@ -71,24 +82,101 @@ pub fn spawn_engine(
     });
 
     if let Some(xdg_config_home) = xdg_config_home {
-        command = command.env("XDG_CONFIG_HOME", xdg_config_home);
+        env.push(("XDG_CONFIG_HOME", xdg_config_home));
     }
 
-    command = command.env("OPENCODE_CLIENT", "openwork");
-    command = command.env("OPENWORK", "1");
+    env.push(("OPENCODE_CLIENT", "openwork".to_string()));
+    env.push(("OPENWORK", "1".to_string()));
 
     if let Some(username) = opencode_username {
         if !username.trim().is_empty() {
-            command = command.env("OPENCODE_SERVER_USERNAME", username);
+            env.push(("OPENCODE_SERVER_USERNAME", username.to_string()));
         }
     }
 
     if let Some(password) = opencode_password {
         if !password.trim().is_empty() {
-            command = command.env("OPENCODE_SERVER_PASSWORD", password);
+            env.push(("OPENCODE_SERVER_PASSWORD", password.to_string()));
         }
     }
 
+    env
+}
+
+pub fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+".contains(c));
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Describes the invocation `spawn_engine` would run, with the server password
+/// redacted, so it can be replayed from a terminal.
+pub fn describe_engine_command(
+    program: &Path,
+    hostname: &str,
+    port: u16,
+    working_dir: &str,
+    opencode_username: Option<&str>,
+    opencode_password: Option<&str>,
+) -> EngineCommand {
+    let program = program.to_string_lossy().to_string();
+    let args = build_engine_args(hostname, port);
+    let env: Vec<String> = engine_env(opencode_username, opencode_password)
+        .into_iter()
+        .map(|(key, value)| {
+            if key == "OPENCODE_SERVER_PASSWORD" {
+                format!("{key}=[redacted]")
+            } else {
+                format!("{key}={}", shell_quote(&value))
+            }
+        })
+        .collect();
+
+    let mut parts = vec![format!("cd {} &&", shell_quote(working_dir))];
+    parts.extend(env.iter().cloned());
+    parts.push(shell_quote(&program));
+    parts.extend(args.iter().map(|arg| shell_quote(arg)));
+
+    EngineCommand {
+        program,
+        args,
+        cwd: working_dir.to_string(),
+        env,
+        command_line: parts.join(" "),
+    }
+}
+
+pub fn spawn_engine(
+    app: &AppHandle,
+    program: &Path,
+    hostname: &str,
+    port: u16,
+    working_dir: &str,
+    use_sidecar: bool,
+    opencode_username: Option<&str>,
+    opencode_password: Option<&str>,
+) -> Result<(Receiver<CommandEvent>, CommandChild), String> {
+    let args = build_engine_args(hostname, port);
+
+    let command = if use_sidecar {
+        app.shell()
+            .sidecar("opencode")
+            .map_err(|e| format!("Failed to locate bundled OpenCode sidecar: {e}"))?
+    } else {
+        app.shell().command(program)
+    };
+
+    let mut command = command.args(args).current_dir(working_dir);
+    for (key, value) in engine_env(opencode_username, opencode_password) {
+        command = command.env(key, value);
+    }
+
     command
         .spawn()
         .map_err(|e| format!("Failed to start opencode: {e}"))
@@ -1,13 +1,104 @@
 use std::fs;
-use std::path::Path;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
-pub fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<(), String> {
+use uuid::Uuid;
+
+#[cfg(windows)]
+const DISK_FULL_CODES: &[i32] = &[39, 112];
+#[cfg(target_os = "linux")]
+const DISK_FULL_CODES: &[i32] = &[28, 122];
+#[cfg(all(unix, not(target_os = "linux")))]
+const DISK_FULL_CODES: &[i32] = &[28, 69];
+
+#[cfg(windows)]
+const READ_ONLY_CODES: &[i32] = &[19];
+#[cfg(unix)]
+const READ_ONLY_CODES: &[i32] = &[30];
+
+/// Names the write failures a user can act on: a full disk (or exhausted quota), missing
+/// permissions, or a read-only volume.
+pub fn classify_io_error(error: &io::Error) -> Option<&'static str> {
+    if error.kind() == io::ErrorKind::PermissionDenied {
+        return Some("Permission denied");
+    }
+    let code = error.raw_os_error()?;
+    if DISK_FULL_CODES.contains(&code) {
+        return Some("Disk full");
+    }
+    if READ_ONLY_CODES.contains(&code) {
+        return Some("Read-only file system");
+    }
+    None
+}
+
+/// Formats a failed filesystem `action` on `path`, leading with the classification from
+/// [`classify_io_error`] when there is one.
+pub fn io_error_message(action: &str, path: &Path, error: &io::Error) -> String {
+    match classify_io_error(error) {
+        Some(reason) => format!("{reason}: failed to {action} {}: {error}", path.display()),
+        None => format!("Failed to {action} {}: {error}", path.display()),
+    }
+}
+
+/// Copies `src` into `dest`, keeping file permissions. Symlinks that resolve inside
+/// `src` are recreated as symlinks; ones pointing outside it are skipped unless
+/// `follow_external_links` is set, in which case the contents behind them are copied.
+pub fn copy_dir_recursive(
+    src: &Path,
+    dest: &Path,
+    follow_external_links: bool,
+) -> Result<(), String> {
     if !src.is_dir() {
         return Err(format!("Source is not a directory: {}", src.display()));
     }
+    let root = src
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {e}", src.display()))?;
+    let mut visited = vec![root.clone()];
+    copy_tree(
+        &root,
+        dest,
+        &root,
+        dest,
+        follow_external_links,
+        &mut visited,
+    )
+}
 
-    fs::create_dir_all(dest)
-        .map_err(|e| format!("Failed to create dir {}: {e}", dest.display()))?;
+fn copy_file(from: &Path, to: &Path) -> Result<(), String> {
+    fs::copy(from, to).map_err(|e| io_error_message("copy to", to, &e))?;
+    let permissions = fs::metadata(from)
+        .map_err(|e| format!("Failed to read {}: {e}", from.display()))?
+        .permissions();
+    fs::set_permissions(to, permissions).map_err(|e| io_error_message("set permissions on", to, &e))
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, link: &Path, _is_dir: bool) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn create_symlink(target: &Path, link: &Path, is_dir: bool) -> io::Result<()> {
+    if is_dir {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+/// `visited` holds the canonical directories being copied, so a followed link back
+/// into one of them cannot recurse forever.
+fn copy_tree(
+    src_root: &Path,
+    dest_root: &Path,
+    src: &Path,
+    dest: &Path,
+    follow_external_links: bool,
+    visited: &mut Vec<PathBuf>,
+) -> Result<(), String> {
+    fs::create_dir_all(dest).map_err(|e| io_error_message("create dir", dest, &e))?;
 
     for entry in
         fs::read_dir(src).map_err(|e| format!("Failed to read dir {}: {e}", src.display()))?
@@ -19,19 +110,190 @@ pub fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<(), String> {
         let to = dest.join(entry.file_name());
 
         if file_type.is_dir() {
-            copy_dir_recursive(&from, &to)?;
+            copy_tree(
+                src_root,
+                dest_root,
+                &from,
+                &to,
+                follow_external_links,
+                visited,
+            )?;
             continue;
         }
 
         if file_type.is_file() {
-            fs::copy(&from, &to).map_err(|e| {
-                format!("Failed to copy {} -> {}: {e}", from.display(), to.display())
-            })?;
+            copy_file(&from, &to)?;
             continue;
         }
 
-        // Skip symlinks and other non-regular entries.
+        if !file_type.is_symlink() {
+            // Sockets, fifos and other special files are not copied.
+            continue;
+        }
+
+        // Dangling links are skipped.
+        let Ok(resolved) = from.canonicalize() else {
+            continue;
+        };
+        if let Ok(relative) = resolved.strip_prefix(src_root) {
+            let link = fs::read_link(&from)
+                .map_err(|e| format!("Failed to read link {}: {e}", from.display()))?;
+            let target = if link.is_relative() && from.starts_with(src_root) {
+                link
+            } else {
+                dest_root.join(relative)
+            };
+            create_symlink(&target, &to, resolved.is_dir())
+                .map_err(|e| io_error_message("create link", &to, &e))?;
+            continue;
+        }
+
+        if !follow_external_links {
+            continue;
+        }
+        if resolved.is_dir() {
+            if visited
+                .iter()
+                .any(|dir| resolved.starts_with(dir) || dir.starts_with(&resolved))
+            {
+                return Err(format!("Symlink loop detected at {}", from.display()));
+            }
+            visited.push(resolved.clone());
+            copy_tree(
+                src_root,
+                dest_root,
+                &resolved,
+                &to,
+                follow_external_links,
+                visited,
+            )?;
+            visited.pop();
+        } else {
+            copy_file(&resolved, &to)?;
+        }
     }
 
     Ok(())
 }
+
+/// Writes `contents` to a sibling temp file and renames it over `path`, so readers
+/// never observe a partially written file.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| format!("Invalid path: {}", path.display()))?;
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| format!("Invalid path: {}", path.display()))?;
+    let temp_path = parent.join(format!(".{file_name}.{}.tmp", Uuid::new_v4()));
+
+    let result = (|| {
+        let mut file =
+            fs::File::create(&temp_path).map_err(|e| io_error_message("create", &temp_path, &e))?;
+        file.write_all(contents)
+            .map_err(|e| io_error_message("write", &temp_path, &e))?;
+        file.sync_all()
+            .map_err(|e| io_error_message("flush", &temp_path, &e))?;
+        fs::rename(&temp_path, path).map_err(|e| {
+            format!(
+                "Failed to move {} -> {}: {e}",
+                temp_path.display(),
+                path.display()
+            )
+        })
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    result
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::{symlink, PermissionsExt};
+
+    fn unique_temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("openwork-{name}-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn copy_keeps_executable_bit() {
+        let base = unique_temp_dir("copy-mode");
+        let src = base.join("src");
+        fs::create_dir_all(src.join("scripts")).unwrap();
+        let script = src.join("scripts").join("run.sh");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let dest = base.join("dest");
+        copy_dir_recursive(&src, &dest, false).unwrap();
+
+        let mode = fs::metadata(dest.join("scripts").join("run.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn copy_recreates_internal_symlinks() {
+        let base = unique_temp_dir("copy-links");
+        let src = base.join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("helper.sh"), "echo hi\n").unwrap();
+        symlink("helper.sh", src.join("alias.sh")).unwrap();
+        symlink(src.join("helper.sh"), src.join("absolute.sh")).unwrap();
+
+        let dest = base.join("dest");
+        copy_dir_recursive(&src, &dest, false).unwrap();
+
+        assert_eq!(
+            fs::read_link(dest.join("alias.sh")).unwrap(),
+            Path::new("helper.sh")
+        );
+        assert_eq!(
+            fs::read_link(dest.join("absolute.sh")).unwrap(),
+            dest.join("helper.sh")
+        );
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn external_symlinks_follow_flag() {
+        let base = unique_temp_dir("copy-external");
+        let src = base.join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(base.join("outside.txt"), "outside\n").unwrap();
+        symlink(base.join("outside.txt"), src.join("outside.txt")).unwrap();
+
+        let skipped = base.join("skipped");
+        copy_dir_recursive(&src, &skipped, false).unwrap();
+        assert!(fs::symlink_metadata(skipped.join("outside.txt")).is_err());
+
+        let followed = base.join("followed");
+        copy_dir_recursive(&src, &followed, true).unwrap();
+        let copied = followed.join("outside.txt");
+        assert!(!fs::symlink_metadata(&copied).unwrap().is_symlink());
+        assert_eq!(fs::read_to_string(copied).unwrap(), "outside\n");
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn following_a_link_to_an_ancestor_fails() {
+        let base = unique_temp_dir("copy-loop");
+        let src = base.join("src");
+        fs::create_dir_all(&src).unwrap();
+        symlink(&base, src.join("up")).unwrap();
+
+        let err = copy_dir_recursive(&src, &base.join("dest"), true).unwrap_err();
+        assert!(err.contains("Symlink loop"), "{err}");
+        let _ = fs::remove_dir_all(&base);
+    }
+}
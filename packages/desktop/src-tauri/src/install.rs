@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde_json::json;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_shell::process::{Command, CommandChild, CommandEvent};
+use uuid::Uuid;
+
+use crate::types::{ExecResult, InstallOutcome};
+
+const INSTALL_STARTED_EVENT: &str = "openwork://install-started";
+
+#[derive(Default)]
+pub struct InstallManager {
+    pub inner: Arc<Mutex<HashMap<String, CommandChild>>>,
+}
+
+impl InstallManager {
+    /// Returns false when no install with `id` is running.
+    pub fn cancel(&self, id: &str) -> bool {
+        let child = self.inner.lock().ok().and_then(|mut map| map.remove(id));
+        match child {
+            Some(child) => {
+                let _ = child.kill();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn emit_progress(app: &AppHandle, event: Option<&str>, id: &str, stream: &str, text: &str) {
+    let Some(event) = event else {
+        return;
+    };
+    let line = text.trim_end();
+    if !line.is_empty() {
+        let _ = app.emit(event, json!({ "id": id, "stream": stream, "line": line }));
+    }
+}
+
+/// Spawns `command`, registering it under a fresh install id until it exits. The id is
+/// announced on `openwork://install-started` so the UI can cancel before the result arrives.
+/// With a `progress_event`, each output line is also emitted as it arrives.
+pub async fn run_install(
+    app: &AppHandle,
+    manager: &InstallManager,
+    kind: &str,
+    command: Command,
+    progress_event: Option<&str>,
+) -> Result<InstallOutcome, String> {
+    let (mut rx, child) = command
+        .spawn()
+        .map_err(|e| format!("Failed to start {kind} install: {e}"))?;
+
+    let install_id = Uuid::new_v4().to_string();
+    manager
+        .inner
+        .lock()
+        .map_err(|_| "install mutex poisoned".to_string())?
+        .insert(install_id.clone(), child);
+    let _ = app.emit(
+        INSTALL_STARTED_EVENT,
+        json!({ "id": install_id, "kind": kind }),
+    );
+
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    let mut status = -1;
+    while let Some(event) = rx.recv().await {
+        match event {
+            CommandEvent::Stdout(line) => {
+                let text = String::from_utf8_lossy(&line);
+                emit_progress(app, progress_event, &install_id, "stdout", &text);
+                stdout.push_str(&text);
+            }
+            CommandEvent::Stderr(line) => {
+                let text = String::from_utf8_lossy(&line);
+                emit_progress(app, progress_event, &install_id, "stderr", &text);
+                stderr.push_str(&text);
+            }
+            CommandEvent::Error(message) => {
+                stderr.push_str(&message);
+                stderr.push('\n');
+            }
+            CommandEvent::Terminated(payload) => {
+                status = payload.code.unwrap_or(-1);
+            }
+            _ => {}
+        }
+    }
+
+    // A cancelled install has already been removed from the map by `cancel`.
+    let cancelled = manager
+        .inner
+        .lock()
+        .map(|mut map| map.remove(&install_id).is_none())
+        .unwrap_or(false);
+    if cancelled {
+        stderr.push_str("Install cancelled\n");
+    }
+
+    Ok(InstallOutcome {
+        install_id,
+        result: ExecResult {
+            ok: status == 0 && !cancelled,
+            status,
+            stdout,
+            stderr,
+        },
+    })
+}
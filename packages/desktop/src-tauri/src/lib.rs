@@ -2,12 +2,14 @@ mod commands;
 mod config;
 mod engine;
 mod fs;
+mod install;
 mod opkg;
 mod openwrk;
 mod openwork_server;
 mod owpenbot;
 mod paths;
 mod platform;
+mod preferences;
 mod types;
 mod updater;
 mod utils;
@@ -15,28 +17,69 @@ mod workspace;
 
 pub use types::*;
 
+use commands::app_data::{export_app_data, import_app_data};
 use commands::command_files::{
     opencode_command_delete, opencode_command_list, opencode_command_write,
 };
-use commands::config::{read_opencode_config, write_opencode_config};
-use commands::engine::{engine_doctor, engine_info, engine_install, engine_start, engine_stop};
-use commands::misc::{opencode_mcp_auth, reset_opencode_cache, reset_openwork_state};
+use commands::config::{
+    config_apply_profile, config_list_profiles, config_save_profile, config_validate,
+    opencode_config_backup, opencode_config_list_backups, opencode_config_restore,
+    read_opencode_config, validate_opencode_config, write_opencode_config,
+};
+use commands::engine::{
+    auth_watch_start, auth_watch_stop, crash_snapshot, engine_attach_openwrk, engine_compact_output,
+    engine_connect_url, engine_current_workspace, engine_doctor, engine_info, engine_install,
+    engine_last_command, engine_metrics, engine_restart, engine_start, engine_stop,
+    get_default_runtime, list_opencode_candidates, process_stats, set_default_runtime,
+    set_log_buffer_chars, set_opencode_binary,
+};
+use commands::install::cancel_install;
+use commands::misc::{
+    check_data_dir_writable, cleanup_backups, diagnose_missing_plugins, list_cors_origins,
+    opencode_mcp_auth, opencode_self_diagnose, platform_capabilities, reset_opencode_cache,
+    reset_openwork_state, system_time_status,
+};
 use commands::openwrk::{openwrk_instance_dispose, openwrk_status, openwrk_workspace_activate};
-use commands::openwork_server::openwork_server_info;
-use commands::scheduler::{scheduler_delete_job, scheduler_list_jobs};
-use commands::opkg::{import_skill, opkg_install};
+use commands::openwork_server::{
+    openwork_server_clients, openwork_server_info, openwork_server_qr, openwork_server_reset,
+    openwork_server_rotate_tokens, openwork_server_start, openwork_server_stop,
+};
+use commands::scheduler::{
+    scheduler_create_job, scheduler_delete_job, scheduler_list_installed_units, scheduler_list_jobs,
+    scheduler_parse_schedule, scheduler_repair, scheduler_run_job_now, scheduler_toggle_job,
+    scheduler_update_job,
+};
+use commands::opkg::{import_skill, import_skill_from_git, opkg_install, opkg_list, opkg_uninstall};
 use commands::owpenbot::{
-    owpenbot_config_set, owpenbot_info, owpenbot_pairing_approve, owpenbot_pairing_deny,
-    owpenbot_pairing_list, owpenbot_qr, owpenbot_start, owpenbot_status, owpenbot_stop,
+    owpenbot_config_set, owpenbot_export_state, owpenbot_import_state, owpenbot_info,
+    owpenbot_log_path, owpenbot_pairing_approve, owpenbot_pairing_deny, owpenbot_pairing_list,
+    owpenbot_qr, owpenbot_start, owpenbot_status, owpenbot_stop,
+};
+use commands::skills::{
+    install_skill_from_zip, install_skill_template, list_local_skills, skill_demote, skill_promote,
+    skill_read, skill_rename, skill_set_enabled, uninstall_skill, validate_skill,
+};
+use commands::templates::{
+    workspace_template_from_prompt, workspace_template_list, workspace_template_read,
+    workspace_template_render, workspace_templates_export, workspace_templates_import,
+};
+use commands::updater::{
+    updater_check_for_update, updater_download_and_install, updater_environment,
 };
-use commands::skills::{install_skill_template, list_local_skills, uninstall_skill};
-use commands::updater::updater_environment;
 use commands::workspace::{
-    workspace_add_authorized_root, workspace_bootstrap, workspace_create, workspace_create_remote,
-    workspace_export_config, workspace_forget, workspace_import_config, workspace_openwork_read,
-    workspace_openwork_write, workspace_set_active, workspace_update_display_name, workspace_update_remote,
+    audit_authorized_roots, export_setup_script, list_presets_detailed, remote_workspace_test,
+    workspace_add_authorized_root, workspace_add_authorized_roots, workspace_bootstrap,
+    workspace_change_preset, workspace_check_remote, workspace_config_fingerprint, workspace_create,
+    workspace_create_from_git, workspace_create_remote, workspace_delete, workspace_duplicate,
+    workspace_export_config, workspace_fix_permissions, workspace_forget, workspace_import_config,
+    workspace_list, workspace_list_dir, workspace_openwork_read, workspace_openwork_set_name,
+    workspace_openwork_write, workspace_read_file, workspace_recent_files, workspace_reload,
+    workspace_rename, workspace_set_active, workspace_update_display_name, workspace_update_guide,
+    workspace_update_remote, workspace_watch_restart, workspace_watch_status, workspace_write_file,
 };
+use engine::auth_watch::AuthWatchState;
 use engine::manager::EngineManager;
+use install::InstallManager;
 use openwrk::manager::OpenwrkManager;
 use openwork_server::manager::OpenworkServerManager;
 use owpenbot::manager::OwpenbotManager;
@@ -60,52 +103,158 @@ pub fn run() {
         .manage(OpenworkServerManager::default())
         .manage(OwpenbotManager::default())
         .manage(WorkspaceWatchState::default())
+        .manage(InstallManager::default())
+        .manage(AuthWatchState::default())
+        .setup(|app| {
+            if let Ok(pidfile) = engine::pidfile::engine_pidfile_path(app.handle()) {
+                if let Some(note) = engine::pidfile::reap_stale_engine(&pidfile) {
+                    println!("[engine] {note}");
+                }
+            }
+            if let Ok(preferences) = preferences::load_preferences(app.handle()) {
+                engine::paths::set_preferred_opencode_binary(
+                    preferences.opencode_bin_path.map(std::path::PathBuf::from),
+                );
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             engine_start,
+            export_app_data,
+            import_app_data,
+            engine_attach_openwrk,
             engine_stop,
+            engine_restart,
             engine_info,
+            engine_last_command,
+            engine_current_workspace,
+            engine_connect_url,
+            engine_metrics,
+            process_stats,
+            engine_compact_output,
             engine_doctor,
+            auth_watch_start,
+            auth_watch_stop,
             engine_install,
+            cancel_install,
+            crash_snapshot,
+            get_default_runtime,
+            set_default_runtime,
+            set_log_buffer_chars,
+            list_opencode_candidates,
+            set_opencode_binary,
             openwrk_status,
             openwrk_workspace_activate,
             openwrk_instance_dispose,
             openwork_server_info,
+            openwork_server_qr,
+            openwork_server_clients,
+            openwork_server_reset,
+            openwork_server_rotate_tokens,
+            openwork_server_start,
+            openwork_server_stop,
             owpenbot_info,
             owpenbot_start,
             owpenbot_stop,
+            owpenbot_log_path,
             owpenbot_qr,
             owpenbot_status,
             owpenbot_config_set,
+            owpenbot_export_state,
+            owpenbot_import_state,
             owpenbot_pairing_list,
             owpenbot_pairing_approve,
             owpenbot_pairing_deny,
             workspace_bootstrap,
+            workspace_list,
+            workspace_reload,
+            workspace_watch_status,
+            workspace_watch_restart,
+            list_presets_detailed,
+            workspace_change_preset,
             workspace_set_active,
             workspace_create,
+            workspace_create_from_git,
             workspace_create_remote,
             workspace_update_display_name,
+            workspace_update_guide,
+            workspace_rename,
             workspace_update_remote,
+            remote_workspace_test,
+            workspace_check_remote,
             workspace_forget,
+            workspace_delete,
+            workspace_duplicate,
             workspace_add_authorized_root,
+            workspace_add_authorized_roots,
+            audit_authorized_roots,
             workspace_export_config,
+            workspace_fix_permissions,
+            export_setup_script,
             workspace_import_config,
+            workspace_template_from_prompt,
+            workspace_template_list,
+            workspace_template_read,
+            workspace_template_render,
+            workspace_templates_export,
+            workspace_templates_import,
             opencode_command_list,
             opencode_command_write,
             opencode_command_delete,
             workspace_openwork_read,
             workspace_openwork_write,
+            workspace_openwork_set_name,
+            workspace_config_fingerprint,
+            workspace_read_file,
+            workspace_write_file,
+            workspace_list_dir,
+            workspace_recent_files,
             opkg_install,
+            opkg_list,
+            opkg_uninstall,
             import_skill,
+            import_skill_from_git,
             install_skill_template,
+            install_skill_from_zip,
             list_local_skills,
             uninstall_skill,
+            skill_promote,
+            skill_demote,
+            skill_read,
+            skill_rename,
+            skill_set_enabled,
+            validate_skill,
             read_opencode_config,
             write_opencode_config,
+            config_validate,
+            validate_opencode_config,
+            config_save_profile,
+            config_apply_profile,
+            config_list_profiles,
+            opencode_config_backup,
+            opencode_config_list_backups,
+            opencode_config_restore,
             updater_environment,
+            updater_check_for_update,
+            updater_download_and_install,
             reset_openwork_state,
             reset_opencode_cache,
+            cleanup_backups,
+            platform_capabilities,
+            system_time_status,
+            list_cors_origins,
+            check_data_dir_writable,
             opencode_mcp_auth,
+            opencode_self_diagnose,
+            diagnose_missing_plugins,
             scheduler_list_jobs,
+            scheduler_list_installed_units,
+            scheduler_repair,
+            scheduler_parse_schedule,
+            scheduler_run_job_now,
+            scheduler_update_job,
+            scheduler_toggle_job,
+            scheduler_create_job,
             scheduler_delete_job
         ])
         .run(tauri::generate_context!())
@@ -9,6 +9,16 @@ pub struct OpenworkServerManager {
     pub inner: Arc<Mutex<OpenworkServerState>>,
 }
 
+/// What the server was last started with, so it can be respawned with new tokens.
+#[derive(Clone)]
+pub struct OpenworkServerLaunch {
+    pub workspace_paths: Vec<String>,
+    pub opencode_base_url: Option<String>,
+    pub opencode_username: Option<String>,
+    pub opencode_password: Option<String>,
+    pub owpenbot_health_port: Option<u16>,
+}
+
 #[derive(Default)]
 pub struct OpenworkServerState {
     pub child: Option<CommandChild>,
@@ -23,6 +33,7 @@ pub struct OpenworkServerState {
     pub host_token: Option<String>,
     pub last_stdout: Option<String>,
     pub last_stderr: Option<String>,
+    pub launch: Option<OpenworkServerLaunch>,
 }
 
 impl OpenworkServerManager {
@@ -67,5 +78,6 @@ impl OpenworkServerManager {
         state.host_token = None;
         state.last_stdout = None;
         state.last_stderr = None;
+        state.launch = None;
     }
 }
@@ -1,16 +1,17 @@
-use gethostname::gethostname;
-use local_ip_address::local_ip;
 use tauri::AppHandle;
-use tauri_plugin_shell::process::CommandEvent;
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
 use uuid::Uuid;
 
+use crate::preferences::log_buffer_chars;
 use crate::types::OpenworkServerInfo;
 use crate::utils::truncate_output;
 
 pub mod manager;
+pub mod network;
 pub mod spawn;
 
-use manager::OpenworkServerManager;
+use manager::{OpenworkServerLaunch, OpenworkServerManager, OpenworkServerState};
+use network::{lan_ipv4, mdns_hostname};
 use spawn::{resolve_openwork_port, spawn_openwork_server};
 
 fn generate_token() -> String {
@@ -18,18 +19,8 @@ fn generate_token() -> String {
 }
 
 fn build_urls(port: u16) -> (Option<String>, Option<String>, Option<String>) {
-    let hostname = gethostname().to_string_lossy().trim().to_string();
-    let mdns_url = if hostname.is_empty() {
-        None
-    } else {
-        let trimmed = hostname.trim_end_matches(".local");
-        Some(format!("http://{trimmed}.local:{port}"))
-    };
-
-    let lan_url = local_ip()
-        .ok()
-        .map(|ip| format!("http://{ip}:{port}"));
-
+    let mdns_url = mdns_hostname().map(|host| format!("http://{host}:{port}"));
+    let lan_url = lan_ipv4().map(|ip| format!("http://{ip}:{port}"));
     let connect_url = lan_url.clone().or(mdns_url.clone());
 
     (connect_url, mdns_url, lan_url)
@@ -49,8 +40,44 @@ pub fn start_openwork_server(
     opencode_password: Option<&str>,
     owpenbot_health_port: Option<u16>,
 ) -> Result<OpenworkServerInfo, String> {
+    let launch = OpenworkServerLaunch {
+        workspace_paths: workspace_paths.to_vec(),
+        opencode_base_url: opencode_base_url.map(str::to_string),
+        opencode_username: opencode_username.map(str::to_string),
+        opencode_password: opencode_password.map(str::to_string),
+        owpenbot_health_port,
+    };
     let mut state = manager.inner.lock().map_err(|_| "openwork server mutex poisoned".to_string())?;
-    OpenworkServerManager::stop_locked(&mut state);
+    start_locked(app, manager, &mut state, launch)
+}
+
+/// Restarts the running server with fresh client and host tokens, so previously shared
+/// links stop working and connected clients have to pair again. The lock is held
+/// throughout; if the respawn fails the server stays stopped rather than running with
+/// the old tokens.
+pub fn rotate_openwork_tokens(
+    app: &AppHandle,
+    manager: &OpenworkServerManager,
+) -> Result<OpenworkServerInfo, String> {
+    let mut state = manager.inner.lock().map_err(|_| "openwork server mutex poisoned".to_string())?;
+    let running = OpenworkServerManager::snapshot_locked(&mut state).running;
+    let launch = state
+        .launch
+        .clone()
+        .filter(|_| running)
+        .ok_or_else(|| "OpenWork server is not running".to_string())?;
+    start_locked(app, manager, &mut state, launch)
+}
+
+/// Stops any running server and spawns a new one with freshly generated tokens.
+fn start_locked(
+    app: &AppHandle,
+    manager: &OpenworkServerManager,
+    state: &mut OpenworkServerState,
+    launch: OpenworkServerLaunch,
+) -> Result<OpenworkServerInfo, String> {
+    OpenworkServerManager::stop_locked(state);
+    let workspace_paths = launch.workspace_paths.as_slice();
 
     let host = "0.0.0.0".to_string();
     let port = resolve_openwork_port()?;
@@ -68,15 +95,15 @@ pub fn start_openwork_server(
         workspace_paths,
         &client_token,
         &host_token,
-        opencode_base_url,
+        launch.opencode_base_url.as_deref(),
         if active_workspace.is_empty() {
             None
         } else {
             Some(active_workspace)
         },
-        opencode_username,
-        opencode_password,
-        owpenbot_health_port,
+        launch.opencode_username.as_deref(),
+        launch.opencode_password.as_deref(),
+        launch.owpenbot_health_port,
     )?;
 
     state.child = Some(child);
@@ -92,46 +119,48 @@ pub fn start_openwork_server(
     state.host_token = Some(host_token);
     state.last_stdout = None;
     state.last_stderr = None;
+    state.launch = Some(launch);
 
     let state_handle = manager.inner.clone();
+    let log_chars = log_buffer_chars(app);
 
+    // Output from a previous, already replaced server must not touch the new state.
+    let pid = state.child.as_ref().map(CommandChild::pid);
     tauri::async_runtime::spawn(async move {
         while let Some(event) = rx.recv().await {
+            let Ok(mut state) = state_handle.try_lock() else {
+                continue;
+            };
+            if state.child.as_ref().map(CommandChild::pid) != pid {
+                continue;
+            }
             match event {
                 CommandEvent::Stdout(line_bytes) => {
                     let line = String::from_utf8_lossy(&line_bytes).to_string();
-                    if let Ok(mut state) = state_handle.try_lock() {
-                        let next = state.last_stdout.as_deref().unwrap_or_default().to_string() + &line;
-                        state.last_stdout = Some(truncate_output(&next, 8000));
-                    }
+                    let next = state.last_stdout.as_deref().unwrap_or_default().to_string() + &line;
+                    state.last_stdout = Some(truncate_output(&next, log_chars));
                 }
                 CommandEvent::Stderr(line_bytes) => {
                     let line = String::from_utf8_lossy(&line_bytes).to_string();
-                    if let Ok(mut state) = state_handle.try_lock() {
-                        let next = state.last_stderr.as_deref().unwrap_or_default().to_string() + &line;
-                        state.last_stderr = Some(truncate_output(&next, 8000));
-                    }
+                    let next = state.last_stderr.as_deref().unwrap_or_default().to_string() + &line;
+                    state.last_stderr = Some(truncate_output(&next, log_chars));
                 }
                 CommandEvent::Terminated(payload) => {
-                    if let Ok(mut state) = state_handle.try_lock() {
-                        state.child_exited = true;
-                        if let Some(code) = payload.code {
-                            let next = format!("OpenWork server exited (code {code}).");
-                            state.last_stderr = Some(truncate_output(&next, 8000));
-                        }
+                    state.child_exited = true;
+                    if let Some(code) = payload.code {
+                        let next = format!("OpenWork server exited (code {code}).");
+                        state.last_stderr = Some(truncate_output(&next, log_chars));
                     }
                 }
                 CommandEvent::Error(message) => {
-                    if let Ok(mut state) = state_handle.try_lock() {
-                        state.child_exited = true;
-                        let next = state.last_stderr.as_deref().unwrap_or_default().to_string() + &message;
-                        state.last_stderr = Some(truncate_output(&next, 8000));
-                    }
+                    state.child_exited = true;
+                    let next = state.last_stderr.as_deref().unwrap_or_default().to_string() + &message;
+                    state.last_stderr = Some(truncate_output(&next, log_chars));
                 }
                 _ => {}
             }
         }
     });
 
-    Ok(OpenworkServerManager::snapshot_locked(&mut state))
+    Ok(OpenworkServerManager::snapshot_locked(state))
 }
@@ -0,0 +1,168 @@
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+
+use local_ip_address::list_afinet_netifas;
+
+/// Documentation-only address (RFC 5737); connecting a UDP socket to it sends nothing
+/// but makes the OS pick the source address of the default route.
+const ROUTE_PROBE_ADDR: (Ipv4Addr, u16) = (Ipv4Addr::new(192, 0, 2, 1), 9);
+const VIRTUAL_INTERFACE_PREFIXES: &[&str] = &[
+    "docker",
+    "br-",
+    "veth",
+    "virbr",
+    "vmnet",
+    "utun",
+    "tun",
+    "tap",
+    "wg",
+    "tailscale",
+];
+
+fn is_lan_candidate(ip: &Ipv4Addr) -> bool {
+    !ip.is_loopback() && !ip.is_link_local() && !ip.is_unspecified() && !ip.is_multicast()
+}
+
+fn default_route_ipv4() -> Option<Ipv4Addr> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect(ROUTE_PROBE_ADDR).ok()?;
+    match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(ip) if is_lan_candidate(&ip) => Some(ip),
+        _ => None,
+    }
+}
+
+/// Private addresses are preferred over public ones, and VPN/container bridges are
+/// skipped when a regular interface is available.
+fn pick_interface_ipv4(interfaces: &[(String, IpAddr)]) -> Option<Ipv4Addr> {
+    let is_virtual = |name: &str| {
+        let name = name.to_ascii_lowercase();
+        VIRTUAL_INTERFACE_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+    };
+    let candidates: Vec<(&str, Ipv4Addr)> = interfaces
+        .iter()
+        .filter_map(|(name, ip)| match ip {
+            IpAddr::V4(ip) if is_lan_candidate(ip) => Some((name.as_str(), *ip)),
+            _ => None,
+        })
+        .collect();
+    candidates
+        .iter()
+        .min_by_key(|(name, ip)| (is_virtual(name), !ip.is_private()))
+        .map(|(_, ip)| *ip)
+}
+
+/// The IPv4 address other devices on the network should use to reach this machine:
+/// the default route's source address, falling back to scanning interfaces when there
+/// is no default route (e.g. an offline LAN).
+pub fn lan_ipv4() -> Option<Ipv4Addr> {
+    default_route_ipv4().or_else(|| {
+        list_afinet_netifas()
+            .ok()
+            .and_then(|interfaces| pick_interface_ipv4(&interfaces))
+    })
+}
+
+/// Reduces a hostname to the single DNS label the OS advertises over mDNS
+/// (Bonjour, Avahi), e.g. `Dana's MacBook.lan` -> `dana-s-macbook`.
+#[cfg(any(target_os = "macos", target_os = "linux", test))]
+fn mdns_label(hostname: &str) -> Option<String> {
+    let first = hostname.trim().split('.').next().unwrap_or_default();
+    let mut label = String::new();
+    for c in first.chars() {
+        if c.is_ascii_alphanumeric() {
+            label.push(c.to_ascii_lowercase());
+        } else if !label.ends_with('-') {
+            label.push('-');
+        }
+    }
+    let label = label.trim_matches('-');
+    if label.is_empty() || label == "localhost" {
+        None
+    } else {
+        Some(label.chars().take(63).collect())
+    }
+}
+
+/// Bonjour always advertises the LocalHostName, which can differ from the hostname.
+#[cfg(target_os = "macos")]
+fn advertised_mdns_label() -> Option<String> {
+    let output = std::process::Command::new("scutil")
+        .args(["--get", "LocalHostName"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    mdns_label(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Linux only answers for `{hostname}.local` while avahi-daemon is running.
+#[cfg(target_os = "linux")]
+fn advertised_mdns_label() -> Option<String> {
+    let running = ["/run/avahi-daemon/pid", "/var/run/avahi-daemon/pid"]
+        .iter()
+        .any(|path| std::path::Path::new(path).exists());
+    if !running {
+        return None;
+    }
+    mdns_label(&gethostname::gethostname().to_string_lossy())
+}
+
+/// Windows does not reliably answer for its own `.local` name.
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn advertised_mdns_label() -> Option<String> {
+    None
+}
+
+/// The `.local` name the OS already advertises for this machine, or `None` when nothing
+/// answers for one. OpenWork runs no mDNS responder of its own, so it cannot claim a
+/// name like `openwork-{hostname}.local` and only offers the system's.
+pub fn mdns_hostname() -> Option<String> {
+    advertised_mdns_label().map(|label| format!("{label}.local"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn netif(name: &str, ip: [u8; 4]) -> (String, IpAddr) {
+        (name.to_string(), IpAddr::V4(Ipv4Addr::from(ip)))
+    }
+
+    #[test]
+    fn mdns_label_keeps_first_label_only() {
+        assert_eq!(mdns_label("devbox.local").as_deref(), Some("devbox"));
+        assert_eq!(
+            mdns_label("Devbox.corp.example.com").as_deref(),
+            Some("devbox")
+        );
+        assert_eq!(
+            mdns_label("Dana's MacBook").as_deref(),
+            Some("dana-s-macbook")
+        );
+        assert_eq!(mdns_label("localhost"), None);
+        assert_eq!(mdns_label("  "), None);
+    }
+
+    #[test]
+    fn pick_interface_prefers_physical_private_addresses() {
+        let interfaces = vec![
+            netif("lo", [127, 0, 0, 1]),
+            netif("docker0", [172, 17, 0, 1]),
+            netif("eth1", [169, 254, 3, 4]),
+            netif("eth0", [203, 0, 113, 7]),
+            netif("wlan0", [192, 168, 1, 20]),
+        ];
+        assert_eq!(
+            pick_interface_ipv4(&interfaces),
+            Some(Ipv4Addr::new(192, 168, 1, 20))
+        );
+        assert_eq!(
+            pick_interface_ipv4(&interfaces[..2]),
+            Some(Ipv4Addr::new(172, 17, 0, 1))
+        );
+        assert_eq!(pick_interface_ipv4(&interfaces[..1]), None);
+    }
+}
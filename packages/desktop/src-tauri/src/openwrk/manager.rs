@@ -12,6 +12,7 @@ pub struct OpenwrkState {
     pub child: Option<CommandChild>,
     pub child_exited: bool,
     pub data_dir: Option<String>,
+    pub daemon_base_url: Option<String>,
     pub last_stdout: Option<String>,
     pub last_stderr: Option<String>,
 }
@@ -22,6 +23,7 @@ impl OpenwrkManager {
             let _ = child.kill();
         }
         state.child_exited = true;
+        state.daemon_base_url = None;
         state.last_stdout = None;
         state.last_stderr = None;
     }
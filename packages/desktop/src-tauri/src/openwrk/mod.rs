@@ -8,7 +8,8 @@ use tauri::AppHandle;
 use tauri_plugin_shell::process::{CommandChild, CommandEvent};
 use tauri_plugin_shell::ShellExt;
 
-use crate::paths::home_dir;
+use crate::engine::doctor::is_sidecar_stub;
+use crate::paths::{home_dir, resolve_in_path};
 use crate::types::{
     OpenwrkBinaryState,
     OpenwrkDaemonState,
@@ -138,6 +139,18 @@ pub fn wait_for_openwrk(base_url: &str, timeout_ms: u64) -> Result<OpenwrkHealth
     Err(last_error.unwrap_or_else(|| "Timed out waiting for openwrk".to_string()))
 }
 
+/// Resolves the binary `spawn_openwrk_daemon` will run: the sidecar next to the app binary,
+/// or `openwrk` on PATH only when that directory is unknown. A missing or stub sidecar
+/// resolves to `None`, since the spawn would not fall back to PATH for it either.
+pub fn resolve_openwrk_binary(current_bin_dir: Option<&Path>) -> Option<PathBuf> {
+    let name = if cfg!(windows) { "openwrk.exe" } else { "openwrk" };
+    match current_bin_dir {
+        Some(dir) => Some(dir.join(name))
+            .filter(|candidate| candidate.is_file() && !is_sidecar_stub(candidate)),
+        None => resolve_in_path(name),
+    }
+}
+
 pub fn spawn_openwrk_daemon(
     app: &AppHandle,
     options: &OpenwrkSpawnOptions,
@@ -223,12 +236,20 @@ pub fn openwrk_status_from_state(data_dir: &str, last_error: Option<String>) ->
     }
 }
 
-pub fn resolve_openwrk_status(data_dir: &str, last_error: Option<String>) -> OpenwrkStatus {
+/// `daemon_base_url` overrides the daemon recorded in `data_dir`, for daemons that were
+/// attached rather than spawned.
+pub fn resolve_openwrk_status(
+    data_dir: &str,
+    daemon_base_url: Option<&str>,
+    last_error: Option<String>,
+) -> OpenwrkStatus {
     let fallback = openwrk_status_from_state(data_dir, last_error);
-    let base_url = fallback
-        .daemon
-        .as_ref()
-        .map(|daemon| daemon.base_url.clone());
+    let base_url = daemon_base_url.map(str::to_string).or_else(|| {
+        fallback
+            .daemon
+            .as_ref()
+            .map(|daemon| daemon.base_url.clone())
+    });
     let Some(base_url) = base_url else {
         return fallback;
     };
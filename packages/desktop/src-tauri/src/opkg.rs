@@ -1,88 +1,106 @@
-use std::process::{Command, Stdio};
+use serde_json::Value;
+use tauri::AppHandle;
+use tauri_plugin_shell::process::Command;
+use tauri_plugin_shell::ShellExt;
 
-use crate::platform::configure_hidden;
-use crate::types::ExecResult;
+use crate::paths::resolve_in_path;
+use crate::types::{ExecResult, OpkgPackage};
 
-pub fn run_capture_optional(command: &mut Command) -> Result<Option<ExecResult>, String> {
-    match command.output() {
-        Ok(output) => {
-            let status = output.status.code().unwrap_or(-1);
-            Ok(Some(ExecResult {
-                ok: output.status.success(),
-                status,
-                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-            }))
-        }
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
-        Err(e) => Err(format!(
-            "Failed to run {}: {e}",
-            command.get_program().to_string_lossy()
-        )),
-    }
-}
+/// Builds an `opkg <args>` command using the first available OpenPackage launcher.
+pub fn opkg_command(app: &AppHandle, project_dir: &str, args: &[&str]) -> Option<Command> {
+    let launchers: [(&str, &[&str]); 4] = [
+        ("opkg", &[]),
+        ("openpackage", &[]),
+        ("pnpm", &["dlx", "opkg"]),
+        ("npx", &["opkg"]),
+    ];
 
-pub fn opkg_install(project_dir: &str, package: &str) -> Result<ExecResult, String> {
-    let mut opkg = Command::new("opkg");
-    configure_hidden(&mut opkg);
-    opkg.arg("install")
-        .arg(package)
-        .current_dir(project_dir)
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
+    launchers.iter().find_map(|(program, prefix)| {
+        let program = resolve_in_path(program)
+            .or_else(|| resolve_in_path(&format!("{program}.cmd")).filter(|_| cfg!(windows)))?;
+        Some(
+            app.shell()
+                .command(program)
+                .args(prefix.iter().copied())
+                .args(args.iter().copied())
+                .current_dir(project_dir),
+        )
+    })
+}
 
-    if let Some(result) = run_capture_optional(&mut opkg)? {
-        return Ok(result);
-    }
+pub fn opkg_not_found() -> ExecResult {
+    ExecResult {
+    ok: false,
+    status: -1,
+    stdout: String::new(),
+    stderr: "OpenPackage CLI not found. Install with `npm install -g opkg` (or `openpackage`), or ensure pnpm/npx is available.".to_string(),
+  }
+}
 
-    let mut openpackage = Command::new("openpackage");
-    configure_hidden(&mut openpackage);
-    openpackage
-        .arg("install")
-        .arg(package)
-        .current_dir(project_dir)
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
+fn package_from_json(value: &Value) -> Option<OpkgPackage> {
+    let name = value.get("name")?.as_str()?.to_string();
+    let version = value
+        .get("version")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    Some(OpkgPackage { name, version })
+}
 
-    if let Some(result) = run_capture_optional(&mut openpackage)? {
-        return Ok(result);
+/// Accepts `[{ "name", "version" }]`, `{ "packages": [...] }` or a `{ name: version }`
+/// map.
+fn parse_list_json(stdout: &str) -> Option<Vec<OpkgPackage>> {
+    let value: Value = serde_json::from_str(stdout.trim()).ok()?;
+    let value = value.get("packages").cloned().unwrap_or(value);
+    match value {
+        Value::Array(items) => items.iter().map(package_from_json).collect(),
+        Value::Object(map) => Some(
+            map.into_iter()
+                .map(|(name, version)| OpkgPackage {
+                    name,
+                    version: version.as_str().map(str::to_string),
+                })
+                .collect(),
+        ),
+        _ => None,
     }
+}
 
-    let mut pnpm = Command::new("pnpm");
-    configure_hidden(&mut pnpm);
-    pnpm.arg("dlx")
-        .arg("opkg")
-        .arg("install")
-        .arg(package)
-        .current_dir(project_dir)
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
+fn looks_like_version(value: &str) -> bool {
+    value
+        .trim_start_matches('v')
+        .starts_with(|c: char| c.is_ascii_digit())
+}
 
-    if let Some(result) = run_capture_optional(&mut pnpm)? {
-        return Ok(result);
+/// Parses a `name@version` or `name version` line, ignoring tree-drawing prefixes.
+fn parse_list_line(line: &str) -> Option<OpkgPackage> {
+    let line = line.trim_start_matches(|c: char| {
+        c.is_whitespace() || matches!(c, '├' | '└' | '│' | '─' | '-' | '*' | '+' | '`')
+    });
+    let mut parts = line.split_whitespace();
+    let first = parts.next()?;
+    let (name, version) = match first.rsplit_once('@') {
+        Some((name, version)) if !name.is_empty() => (name, version),
+        _ => (first, parts.next()?),
+    };
+    if !looks_like_version(version) {
+        return None;
     }
+    Some(OpkgPackage {
+        name: name.to_string(),
+        version: Some(version.to_string()),
+    })
+}
 
-    let mut npx = Command::new("npx");
-    configure_hidden(&mut npx);
-    npx.arg("opkg")
-        .arg("install")
-        .arg(package)
-        .current_dir(project_dir)
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    if let Some(result) = run_capture_optional(&mut npx)? {
-        return Ok(result);
+/// Structured packages from `opkg list` output, or `None` when some line is not
+/// recognisable. Blank lines and headings ending in `:` are skipped.
+pub fn parse_opkg_list(stdout: &str) -> Option<Vec<OpkgPackage>> {
+    if let Some(packages) = parse_list_json(stdout) {
+        return Some(packages);
     }
-
-    Ok(ExecResult {
-    ok: false,
-    status: -1,
-    stdout: String::new(),
-    stderr: "OpenPackage CLI not found. Install with `npm install -g opkg` (or `openpackage`), or ensure pnpm/npx is available.".to_string(),
-  })
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.ends_with(':'))
+        .map(parse_list_line)
+        .collect()
 }
@@ -0,0 +1,33 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use tauri::Manager;
+
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+pub fn owpenbot_log_file(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {e}"))?;
+    Ok(data_dir.join("logs").join("owpenbot.log"))
+}
+
+/// Appends to the log, rotating it to `owpenbot.log.1` once it grows past the size cap.
+pub fn append_owpenbot_log(path: &Path, text: &str) {
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+
+    let too_large = fs::metadata(path)
+        .map(|meta| meta.len() >= MAX_LOG_BYTES)
+        .unwrap_or(false);
+    if too_large {
+        let _ = fs::rename(path, path.with_extension("log.1"));
+    }
+
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+        let _ = file.write_all(text.as_bytes());
+    }
+}
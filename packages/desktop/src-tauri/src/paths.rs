@@ -90,3 +90,11 @@ pub fn resolve_in_path(name: &str) -> Option<PathBuf> {
     }
     None
 }
+
+pub fn git_program() -> Option<PathBuf> {
+    #[cfg(windows)]
+    let names: &[&str] = &["git.exe", "git"];
+    #[cfg(not(windows))]
+    let names: &[&str] = &["git"];
+    names.iter().find_map(|name| resolve_in_path(name))
+}
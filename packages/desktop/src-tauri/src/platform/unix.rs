@@ -1,8 +1,43 @@
 use std::path::Path;
 use std::process::Command;
+use std::time::{Duration, Instant};
 
 pub fn command_for_program(program: &Path) -> Command {
     Command::new(program)
 }
 
-pub fn configure_hidden(_command: &mut Command) {}
+fn process_gone(pid: libc::pid_t) -> bool {
+    // SAFETY: signal 0 only checks that `pid` exists; nothing is delivered.
+    let result = unsafe { libc::kill(pid, 0) };
+    result != 0 && std::io::Error::last_os_error().raw_os_error() == Some(libc::ESRCH)
+}
+
+/// Sends SIGTERM and waits up to `grace` for the process to exit. Returns `false` if it
+/// is still running, so the caller can fall back to a hard kill.
+pub fn terminate_gracefully(pid: u32, grace: Duration) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: plain signal delivery to a pid we spawned.
+    if unsafe { libc::kill(pid, libc::SIGTERM) } != 0 {
+        return process_gone(pid);
+    }
+
+    let deadline = Instant::now() + grace;
+    while Instant::now() < deadline {
+        if process_gone(pid) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    process_gone(pid)
+}
+
+pub fn kill_process(pid: u32) {
+    if let Ok(pid) = libc::pid_t::try_from(pid) {
+        // SAFETY: plain signal delivery; failures (e.g. already exited) are ignored.
+        unsafe {
+            libc::kill(pid, libc::SIGKILL);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::os::windows::process::CommandExt;
 use std::path::Path;
 use std::process::Command;
+use std::time::Duration;
 
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
@@ -24,6 +25,14 @@ pub fn command_for_program(program: &Path) -> Command {
     command
 }
 
-pub fn configure_hidden(command: &mut Command) {
-    command.creation_flags(CREATE_NO_WINDOW);
+/// Windows has no SIGTERM; callers fall back to `kill`, which terminates immediately.
+pub fn terminate_gracefully(_pid: u32, _grace: Duration) -> bool {
+    false
+}
+
+pub fn kill_process(pid: u32) {
+    let _ = Command::new("taskkill")
+        .args(["/F", "/T", "/PID", &pid.to_string()])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
 }
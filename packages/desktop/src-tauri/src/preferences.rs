@@ -0,0 +1,60 @@
+use std::fs;
+use std::path::PathBuf;
+
+use tauri::Manager;
+
+use crate::fs::{io_error_message, write_atomic};
+use crate::types::Preferences;
+
+pub const LOG_BUFFER_CHARS_MIN: usize = 2_000;
+pub const LOG_BUFFER_CHARS_MAX: usize = 100_000;
+
+pub fn clamp_log_buffer_chars(chars: usize) -> usize {
+    chars.clamp(LOG_BUFFER_CHARS_MIN, LOG_BUFFER_CHARS_MAX)
+}
+
+/// Size of the stdout/stderr buffers kept by the sidecar reader loops.
+pub fn log_buffer_chars(app: &tauri::AppHandle) -> usize {
+    clamp_log_buffer_chars(load_preferences(app).unwrap_or_default().log_buffer_chars)
+}
+
+pub fn preferences_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {e}"))?;
+    Ok(data_dir.join("openwork-preferences.json"))
+}
+
+pub fn load_preferences(app: &tauri::AppHandle) -> Result<Preferences, String> {
+    let path = preferences_path(app)?;
+    if !path.exists() {
+        return Ok(Preferences::default());
+    }
+
+    let raw =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    serde_json::from_str(&raw).map_err(|e| format!("Failed to parse {}: {e}", path.display()))
+}
+
+/// Defaults plus a warning when the file is corrupt or half-written, for callers that
+/// must keep working (e.g. starting the engine) rather than fail on a bad preferences file.
+pub fn load_preferences_or_default(app: &tauri::AppHandle) -> (Preferences, Option<String>) {
+    match load_preferences(app) {
+        Ok(preferences) => (preferences, None),
+        Err(error) => {
+            let warning = format!("Using default preferences: {error}");
+            println!("[preferences] {warning}");
+            (Preferences::default(), Some(warning))
+        }
+    }
+}
+
+pub fn save_preferences(app: &tauri::AppHandle, preferences: &Preferences) -> Result<(), String> {
+    let path = preferences_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| io_error_message("create", parent, &e))?;
+    }
+    let serialized = serde_json::to_string_pretty(preferences).map_err(|e| e.to_string())?;
+    write_atomic(&path, serialized.as_bytes())
+}
@@ -7,6 +7,8 @@ pub struct WorkspaceOpenworkConfig {
     pub workspace: Option<WorkspaceOpenworkWorkspace>,
     #[serde(default, alias = "authorizedRoots")]
     pub authorized_roots: Vec<String>,
+    #[serde(default)]
+    pub updated_at: Option<u64>,
 }
 
 impl Default for WorkspaceOpenworkConfig {
@@ -15,6 +17,7 @@ impl Default for WorkspaceOpenworkConfig {
             version: 1,
             workspace: None,
             authorized_roots: Vec::new(),
+            updated_at: None,
         }
     }
 }
@@ -46,6 +49,7 @@ impl WorkspaceOpenworkConfig {
                 preset: Some(preset.to_string()),
             }),
             authorized_roots: vec![workspace_path.to_string()],
+            updated_at: None,
         }
     }
 }
@@ -79,6 +83,46 @@ pub struct EngineInfo {
     pub last_stderr: Option<String>,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineConnectInfo {
+    pub base_url: Option<String>,
+    pub connect_url: Option<String>,
+    pub has_credentials: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: String,
+    pub env: Vec<String>,
+    pub command_line: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessStat {
+    pub name: String,
+    pub pid: u32,
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineMetrics {
+    pub running: bool,
+    pub runtime: EngineRuntime,
+    pub started_at: Option<u64>,
+    pub uptime_ms: Option<u64>,
+    pub restart_count: u32,
+    pub last_error: Option<String>,
+    pub stdout_bytes: usize,
+    pub stderr_bytes: usize,
+}
+
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenworkServerInfo {
@@ -96,6 +140,24 @@ pub struct OpenworkServerInfo {
     pub last_stderr: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenworkServerClient {
+    #[serde(default, alias = "clientId")]
+    pub id: Option<String>,
+    #[serde(default, alias = "remoteAddress", alias = "ip")]
+    pub address: Option<String>,
+    #[serde(default, alias = "connectedAt", alias = "since")]
+    pub connected_since: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenworkServerClients {
+    pub clients: Vec<OpenworkServerClient>,
+    pub note: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenwrkDaemonState {
@@ -197,6 +259,18 @@ pub struct EngineDoctorResult {
     pub serve_help_status: Option<i32>,
     pub serve_help_stdout: Option<String>,
     pub serve_help_stderr: Option<String>,
+    pub arch_mismatch: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashSnapshot {
+    pub openwork_version: String,
+    pub engine_stderr: Option<String>,
+    pub openwrk_stderr: Option<String>,
+    pub openwork_server_stderr: Option<String>,
+    pub owpenbot_stderr: Option<String>,
+    pub doctor: EngineDoctorResult,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -208,6 +282,14 @@ pub struct ExecResult {
     pub stderr: String,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallOutcome {
+    pub install_id: String,
+    #[serde(flatten)]
+    pub result: ExecResult,
+}
+
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OpencodeConfigFile {
@@ -225,6 +307,82 @@ pub struct UpdaterEnvironment {
     pub app_bundle_path: Option<String>,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCheck {
+    pub supported: bool,
+    pub reason: Option<String>,
+    pub available: bool,
+    pub current_version: String,
+    pub version: Option<String>,
+    pub notes: Option<String>,
+    pub pub_date: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInstall {
+    pub installed: bool,
+    pub version: Option<String>,
+    pub relaunch_required: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DataDirStatus {
+    pub writable: bool,
+    pub path: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemTimeStatus {
+    pub now_ms: u64,
+    pub clock_ok: bool,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpkgPackage {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpkgPackageList {
+    pub packages: Vec<OpkgPackage>,
+    pub raw: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingPlugin {
+    pub spec: String,
+    pub package: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CorsOrigins {
+    pub engine: Vec<String>,
+    pub openwork_server: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformCapabilities {
+    pub os: String,
+    pub arch: String,
+    pub scheduler_supported: bool,
+    pub guided_install_supported: bool,
+    pub sidecar_supported: bool,
+    pub updater_supported: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ScheduledJobRun {
@@ -263,6 +421,42 @@ pub struct ScheduledJob {
     pub last_run_error: Option<String>,
     pub last_run_source: Option<String>,
     pub last_run_status: Option<String>,
+    #[serde(default = "default_job_enabled")]
+    pub enabled: bool,
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub next_run_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledUnit {
+    pub slug: String,
+    pub label: String,
+    pub path: String,
+    pub enabled: bool,
+    pub has_job: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledUnitReport {
+    pub units: Vec<ScheduledUnit>,
+    pub orphaned_jobs: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulerRepairResult {
+    pub reinstalled: Vec<String>,
+    pub removed: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedSchedule {
+    pub cron: String,
+    pub next_run_at: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -314,6 +508,30 @@ pub struct WorkspaceInfo {
     pub openwork_workspace_id: Option<String>,
     #[serde(default)]
     pub openwork_workspace_name: Option<String>,
+    #[serde(default = "default_workspace_exists", skip_deserializing)]
+    pub exists: bool,
+}
+
+fn default_workspace_exists() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteTestStep {
+    pub step: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteReachability {
+    pub reachable: bool,
+    pub url: String,
+    pub status: Option<u16>,
+    pub latency_ms: u64,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -323,6 +541,51 @@ pub struct WorkspaceList {
     pub workspaces: Vec<WorkspaceInfo>,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceDirEntry {
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub mtime: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceWatchStatus {
+    pub installed: bool,
+    pub root: Option<String>,
+    pub watched: Vec<String>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthorizedRootRisk {
+    Ok,
+    Missing,
+    Warning,
+    Danger,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizedRootAudit {
+    pub root: String,
+    pub resolved: Option<String>,
+    pub risk: AuthorizedRootRisk,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRecentFile {
+    pub name: String,
+    pub path: String,
+    pub mtime: u64,
+    pub size: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OpencodeCommand {
@@ -338,6 +601,29 @@ pub struct OpencodeCommand {
     pub subtask: Option<bool>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceTemplate {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub prompt: String,
+    #[serde(default, alias = "created_at")]
+    pub created_at: u64,
+    #[serde(default)]
+    pub variables: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetDetails {
+    pub id: String,
+    pub name: String,
+    pub required_plugins: Vec<String>,
+    pub template_titles: Vec<String>,
+}
+
 fn default_workspace_state_version() -> u8 {
     1
 }
@@ -361,4 +647,104 @@ impl Default for WorkspaceState {
     }
 }
 
-pub const WORKSPACE_STATE_VERSION: u8 = 3;
+pub const WORKSPACE_STATE_VERSION: u8 = 4;
+
+fn default_backup_retention_days() -> u64 {
+    14
+}
+
+fn default_backup_max_count() -> usize {
+    20
+}
+
+fn default_collapse_duplicate_output() -> bool {
+    true
+}
+
+fn default_job_enabled() -> bool {
+    true
+}
+
+fn default_log_buffer_chars() -> usize {
+    8000
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpencodeCandidate {
+    pub path: String,
+    pub version: Option<String>,
+    pub selected: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Preferences {
+    #[serde(default = "default_backup_retention_days")]
+    pub backup_retention_days: u64,
+    #[serde(default = "default_backup_max_count")]
+    pub backup_max_count: usize,
+    #[serde(default)]
+    pub default_runtime: EngineRuntime,
+    #[serde(default)]
+    pub opencode_bin_path: Option<String>,
+    #[serde(default = "default_collapse_duplicate_output")]
+    pub collapse_duplicate_output: bool,
+    #[serde(default = "default_log_buffer_chars")]
+    pub log_buffer_chars: usize,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            backup_retention_days: default_backup_retention_days(),
+            backup_max_count: default_backup_max_count(),
+            default_runtime: EngineRuntime::default(),
+            opencode_bin_path: None,
+            collapse_duplicate_output: default_collapse_duplicate_output(),
+            log_buffer_chars: default_log_buffer_chars(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigProfile {
+    pub name: String,
+    pub modified_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBackup {
+    pub id: String,
+    pub created_at: u64,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigValidation {
+    pub path: String,
+    pub exists: bool,
+    pub valid: bool,
+    pub error: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigIssue {
+    pub message: String,
+    pub key: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigContentValidation {
+    pub valid: bool,
+    pub errors: Vec<ConfigIssue>,
+}
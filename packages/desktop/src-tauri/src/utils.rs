@@ -7,13 +7,208 @@ pub fn now_ms() -> u64 {
         .as_millis() as u64
 }
 
+/// 2024-01-01T00:00:00Z. A clock reading earlier than this (including the `0` that
+/// `now_ms` falls back to before the epoch) means the system clock is misconfigured.
+const CLOCK_SANITY_FLOOR_MS: u64 = 1_704_067_200_000;
+
+pub fn clock_looks_sane(ms: u64) -> bool {
+    ms >= CLOCK_SANITY_FLOOR_MS
+}
+
+/// `now_ms` plus whether the reading is plausible, so callers can flag timestamps such
+/// as `created_at` that will sort oddly.
+pub fn checked_now_ms() -> (u64, bool) {
+    let now = now_ms();
+    (now, clock_looks_sane(now))
+}
+
+/// Keeps the last `max_chars` characters (not bytes) of `input`.
 pub fn truncate_output(input: &str, max_chars: usize) -> String {
-    if input.len() <= max_chars {
+    let total_chars = input.chars().count();
+    if total_chars <= max_chars {
         return input.to_string();
     }
 
-    input
-        .chars()
-        .skip(input.chars().count() - max_chars)
+    let start = input
+        .char_indices()
+        .nth(total_chars - max_chars)
+        .map_or(input.len(), |(index, _)| index);
+    input[start..].to_string()
+}
+
+fn split_repeat_count(line: &str) -> (&str, usize) {
+    let parsed = line
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" (x"))
+        .and_then(|(text, count)| Some((text, count.parse::<usize>().ok()?)));
+    match parsed {
+        Some((text, count)) if count > 1 => (text, count),
+        _ => (line, 1),
+    }
+}
+
+/// Folds runs of identical consecutive lines into a single `line (xN)` entry. Lines that
+/// were already folded keep accumulating, so this can be applied to a growing buffer.
+pub fn collapse_duplicate_lines(input: &str) -> String {
+    let mut runs: Vec<(&str, usize)> = Vec::new();
+    for line in input.lines() {
+        let (text, count) = split_repeat_count(line);
+        match runs.last_mut() {
+            Some((last, total)) if *last == text => *total += count,
+            _ => runs.push((text, count)),
+        }
+    }
+
+    let mut output = String::with_capacity(input.len());
+    for (text, count) in runs {
+        output.push_str(text);
+        if count > 1 {
+            output.push_str(&format!(" (x{count})"));
+        }
+        output.push('\n');
+    }
+    if !input.ends_with('\n') {
+        output.pop();
+    }
+    output
+}
+
+pub fn compact_output(input: &str, max_chars: usize, collapse_duplicates: bool) -> String {
+    if collapse_duplicates {
+        truncate_output(&collapse_duplicate_lines(input), max_chars)
+    } else {
+        truncate_output(input, max_chars)
+    }
+}
+
+pub fn redact_secrets(input: &str, secrets: &[&str]) -> String {
+    let mut output = input.to_string();
+    for secret in secrets {
+        if secret.len() >= 4 {
+            output = output.replace(secret, "[redacted]");
+        }
+    }
+
+    output
+        .split_inclusive('\n')
+        .map(|line| match line.find("Bearer ") {
+            Some(index) => {
+                let token_start = index + "Bearer ".len();
+                let token_end = line[token_start..]
+                    .find(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+                    .map(|offset| token_start + offset)
+                    .unwrap_or(line.len());
+                format!("{}[redacted]{}", &line[..token_start], &line[token_end..])
+            }
+            None => line.to_string(),
+        })
         .collect()
 }
+
+/// 64-bit FNV-1a. Unlike `DefaultHasher`, the output is fixed across Rust releases and
+/// runs, so it is safe to persist or compare across sessions.
+pub struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl StableHasher {
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Renders `data` as a QR code PNG, base64-encoded for use in a data URL.
+pub fn qr_png_base64(data: &str) -> Result<String, String> {
+    use base64::engine::general_purpose;
+    use base64::Engine as _;
+    use image::{DynamicImage, ImageFormat, Luma};
+    use qrcode::QrCode;
+    use std::io::Cursor;
+
+    let code = QrCode::new(data.as_bytes()).map_err(|e| format!("Failed to encode QR: {e}"))?;
+    let image = code.render::<Luma<u8>>().min_dimensions(256, 256).build();
+    let mut buffer = Vec::new();
+    DynamicImage::ImageLuma8(image)
+        .write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode QR image: {e}"))?;
+    Ok(general_purpose::STANDARD.encode(buffer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_before_floor_is_flagged() {
+        assert!(!clock_looks_sane(0));
+        assert!(!clock_looks_sane(CLOCK_SANITY_FLOOR_MS - 1));
+        assert!(clock_looks_sane(CLOCK_SANITY_FLOOR_MS));
+    }
+
+    #[test]
+    fn stable_hasher_matches_fnv1a_vectors() {
+        assert_eq!(StableHasher::default().finish(), 0xcbf2_9ce4_8422_2325);
+        let mut hasher = StableHasher::default();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn collapses_consecutive_duplicate_lines() {
+        let input = "retrying\nretrying\nretrying\nconnected\nretrying\n";
+        assert_eq!(
+            collapse_duplicate_lines(input),
+            "retrying (x3)\nconnected\nretrying\n"
+        );
+    }
+
+    #[test]
+    fn collapse_accumulates_into_existing_counter() {
+        let once = collapse_duplicate_lines("error\nerror\n");
+        let again = collapse_duplicate_lines(&format!("{once}error\n"));
+        assert_eq!(again, "error (x3)\n");
+    }
+
+    #[test]
+    fn collapse_preserves_missing_trailing_newline() {
+        assert_eq!(collapse_duplicate_lines("a\na"), "a (x2)");
+        assert_eq!(collapse_duplicate_lines(""), "");
+    }
+
+    #[test]
+    fn truncate_keeps_tail_on_char_boundaries() {
+        let input = "héllo wörld";
+        assert_eq!(truncate_output(input, 5), "wörld");
+        assert_eq!(truncate_output(input, 11), input);
+    }
+
+    #[test]
+    fn truncate_does_not_underflow_on_multibyte_input() {
+        // 4 chars but 8 bytes: byte length exceeds the limit while the char count does not.
+        let input = "éééé";
+        assert_eq!(truncate_output(input, 6), input);
+    }
+
+    #[test]
+    fn truncate_counts_emoji_and_cjk_as_single_chars() {
+        let input = "日志🚀输出✅完成";
+        for max_chars in 0..=input.chars().count() {
+            let truncated = truncate_output(input, max_chars);
+            assert_eq!(truncated.chars().count(), max_chars);
+            assert!(input.ends_with(&truncated));
+        }
+        assert_eq!(truncate_output(input, 3), "✅完成");
+        assert_eq!(truncate_output("🚀🚀🚀", 0), "");
+    }
+}
@@ -20,24 +20,24 @@ pub fn sanitize_command_name(raw: &str) -> Option<String> {
     Some(out)
 }
 
-pub fn serialize_command_frontmatter(command: &OpencodeCommand) -> Result<String, String> {
-    fn escape_yaml_scalar(value: &str) -> String {
-        let mut out = String::with_capacity(value.len() + 2);
-        out.push('"');
-        for ch in value.chars() {
-            match ch {
-                '\\' => out.push_str("\\\\"),
-                '"' => out.push_str("\\\""),
-                '\n' => out.push_str("\\n"),
-                '\r' => out.push_str("\\r"),
-                '\t' => out.push_str("\\t"),
-                _ => out.push(ch),
-            }
+pub fn escape_yaml_scalar(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(ch),
         }
-        out.push('"');
-        out
     }
+    out.push('"');
+    out
+}
 
+pub fn serialize_command_frontmatter(command: &OpencodeCommand) -> Result<String, String> {
     let template = command.template.trim();
     if template.is_empty() {
         return Err("command.template is required".to_string());
@@ -5,9 +5,12 @@ use std::path::{Path, PathBuf};
 
 use zip::ZipArchive;
 
-use crate::types::{OpencodeCommand, WorkspaceOpenworkConfig};
-use crate::utils::now_ms;
+use crate::config::{backup_jsonc_config, parse_opencode_config, resolve_opencode_config_path};
+use crate::fs::write_atomic;
+use crate::types::{OpencodeCommand, WorkspaceOpenworkConfig, WorkspaceOpenworkWorkspace};
+use crate::utils::{now_ms, StableHasher};
 use crate::workspace::commands::{sanitize_command_name, serialize_command_frontmatter};
+use crate::workspace::presets::{find_preset, seed_preset_templates};
 
 pub fn merge_plugins(existing: Vec<String>, required: &[&str]) -> Vec<String> {
     let mut out = existing;
@@ -19,20 +22,10 @@ pub fn merge_plugins(existing: Vec<String>, required: &[&str]) -> Vec<String> {
     out
 }
 
-fn seed_workspace_guide(skill_root: &PathBuf) -> Result<(), String> {
-    let guide_dir = skill_root.join("workspace-guide");
-    if guide_dir.exists() {
-        return Ok(());
-    }
-
-    fs::create_dir_all(&guide_dir)
-        .map_err(|e| format!("Failed to create {}: {e}", guide_dir.display()))?;
-
-    let doc = r#"---
-name: workspace-guide
-description: Workspace guide to introduce OpenWork and onboard new users.
----
+/// Bump when the bundled guide changes so `update_workspace_guide` refreshes old copies.
+pub const WORKSPACE_GUIDE_VERSION: u32 = 1;
 
+const WORKSPACE_GUIDE_BODY: &str = r#"
 # Welcome to OpenWork
 
 Hi, I'm Ben and this is OpenWork. It's an open-source alternative to Claude's cowork. It helps you work on your files with AI and automate the mundane tasks so you don't have to.
@@ -76,12 +69,60 @@ Config reference:
 
 End with two friendly next actions to try in OpenWork."#;
 
-    fs::write(guide_dir.join("SKILL.md"), doc)
+fn workspace_guide_doc() -> String {
+    format!(
+        "---\nname: workspace-guide\ndescription: Workspace guide to introduce OpenWork and onboard new users.\nversion: {WORKSPACE_GUIDE_VERSION}\n---\n{WORKSPACE_GUIDE_BODY}"
+    )
+}
+
+fn seed_workspace_guide(skill_root: &PathBuf) -> Result<(), String> {
+    let guide_dir = skill_root.join("workspace-guide");
+    if guide_dir.exists() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(&guide_dir)
+        .map_err(|e| format!("Failed to create {}: {e}", guide_dir.display()))?;
+
+    fs::write(guide_dir.join("SKILL.md"), workspace_guide_doc())
         .map_err(|e| format!("Failed to write SKILL.md: {e}"))?;
 
     Ok(())
 }
 
+/// Reads `version:` from a guide's frontmatter; guides seeded before versioning have none.
+fn workspace_guide_version(raw: &str) -> Option<u32> {
+    let header = raw.strip_prefix("---")?;
+    let header = &header[..header.find("\n---")?];
+    header.lines().find_map(|line| {
+        line.strip_prefix("version:")
+            .and_then(|value| value.trim().trim_matches('"').parse().ok())
+    })
+}
+
+/// Rewrites `workspace-guide/SKILL.md` with the bundled guide when `force` is set or the
+/// installed copy is older. Other skills are never touched. Returns whether it wrote.
+pub fn update_workspace_guide(workspace_path: &Path, force: bool) -> Result<bool, String> {
+    let guide_dir = workspace_path
+        .join(".opencode")
+        .join("skills")
+        .join("workspace-guide");
+    let guide_path = guide_dir.join("SKILL.md");
+
+    if !force {
+        if let Ok(raw) = fs::read_to_string(&guide_path) {
+            if workspace_guide_version(&raw).unwrap_or(0) >= WORKSPACE_GUIDE_VERSION {
+                return Ok(false);
+            }
+        }
+    }
+
+    fs::create_dir_all(&guide_dir)
+        .map_err(|e| format!("Failed to create {}: {e}", guide_dir.display()))?;
+    write_atomic(&guide_path, workspace_guide_doc().as_bytes())?;
+    Ok(true)
+}
+
 fn seed_get_started_skill(skill_root: &PathBuf) -> Result<(), String> {
   let skill_dir = skill_root.join("get-started");
     if skill_dir.exists() {
@@ -284,7 +325,19 @@ fn seed_commands(commands_dir: &PathBuf, preset: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Seeds skills, commands and the preset's opencode config. An existing config that
+/// cannot be parsed is never overwritten and is reported as an error.
 pub fn ensure_workspace_files(workspace_path: &str, preset: &str) -> Result<(), String> {
+    seed_workspace_files(workspace_path, preset, true)
+}
+
+/// Like `ensure_workspace_files`, but an unparseable config only skips the config merge
+/// with a logged warning, so a typo in the starter workspace cannot block startup.
+pub fn ensure_workspace_files_lenient(workspace_path: &str, preset: &str) -> Result<(), String> {
+    seed_workspace_files(workspace_path, preset, false)
+}
+
+fn seed_workspace_files(workspace_path: &str, preset: &str, strict: bool) -> Result<(), String> {
     let root = PathBuf::from(workspace_path);
 
     let skill_root = root.join(".opencode").join("skills");
@@ -303,120 +356,396 @@ pub fn ensure_workspace_files(workspace_path: &str, preset: &str) -> Result<(),
         .map_err(|e| format!("Failed to create .opencode/commands: {e}"))?;
   seed_commands(&commands_dir, preset)?;
 
-    let config_path_jsonc = root.join("opencode.jsonc");
-    let config_path_json = root.join("opencode.json");
-    let config_path = if config_path_jsonc.exists() {
-        config_path_jsonc
-    } else if config_path_json.exists() {
-        config_path_json
+    let mut config = match load_project_config(workspace_path) {
+        Ok(config) => config,
+        Err(message) if !strict => {
+            println!("[workspace] warning: {message}; skipping config merge");
+            return Ok(());
+        }
+        Err(message) => return Err(message),
+    };
+    let mut config_changed = config.raw.is_none();
+
+    let definition = find_preset(preset);
+    let required_plugins: Vec<&str> = definition
+        .map(|definition| definition.required_plugins.to_vec())
+        .unwrap_or_default();
+
+    let should_seed_chrome_mcp = definition.is_some_and(|definition| definition.seed_chrome_mcp);
+
+    if merge_required_plugins(&mut config.value, &required_plugins) {
+        config_changed = true;
+    }
+
+    if should_seed_chrome_mcp {
+        let obj = &mut config.value;
+        let mcp_value = obj
+            .get("mcp")
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!({}));
+
+        let mut mcp_obj = match mcp_value {
+            serde_json::Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        };
+
+        if !mcp_obj.contains_key("chrome-devtools") {
+            mcp_obj.insert(
+                "chrome-devtools".to_string(),
+                serde_json::json!({
+                  "type": "local",
+                  "command": ["npx", "-y", "chrome-devtools-mcp@latest"]
+                }),
+            );
+            config_changed = true;
+        }
+
+        obj.insert("mcp".to_string(), serde_json::Value::Object(mcp_obj));
+    }
+
+    if config_changed {
+        save_project_config(&config)?;
+    }
+
+    let openwork_path = root.join(".opencode").join("openwork.json");
+    if !openwork_path.exists() {
+        let openwork = WorkspaceOpenworkConfig::new(workspace_path, preset, now_ms());
+        save_workspace_openwork(&openwork_path, &openwork)?;
+
+        // Only seed on first setup so templates the user deletes stay deleted.
+        seed_preset_templates(workspace_path, preset)?;
+    }
+
+    Ok(())
+}
+
+/// Additively moves a workspace onto `preset`: plugins and templates from the previous preset
+/// are left in place.
+pub fn migrate_workspace_preset(workspace_path: &str, preset: &str) -> Result<(), String> {
+    let definition = find_preset(preset).ok_or_else(|| format!("Unknown preset: {preset}"))?;
+    let root = PathBuf::from(workspace_path);
+
+    let mut config = load_project_config(workspace_path)?;
+    if merge_required_plugins(&mut config.value, definition.required_plugins)
+        || config.raw.is_none()
+    {
+        save_project_config(&config)?;
+    }
+
+    seed_preset_templates(workspace_path, preset)?;
+
+    let openwork_path = root.join(".opencode").join("openwork.json");
+    let mut openwork = if openwork_path.exists() {
+        let raw = fs::read_to_string(&openwork_path)
+            .map_err(|e| format!("Failed to read {}: {e}", openwork_path.display()))?;
+        serde_json::from_str::<WorkspaceOpenworkConfig>(&raw)
+            .map_err(|e| format!("Failed to parse {}: {e}", openwork_path.display()))?
     } else {
-        config_path_jsonc
+        WorkspaceOpenworkConfig::new(workspace_path, preset, now_ms())
     };
+    if openwork.workspace.is_none() {
+        openwork.workspace =
+            WorkspaceOpenworkConfig::new(workspace_path, preset, now_ms()).workspace;
+    }
+    if let Some(workspace) = openwork.workspace.as_mut() {
+        workspace.preset = Some(preset.to_string());
+    }
+
+    save_workspace_openwork(&openwork_path, &openwork)?;
 
-    let config_exists = config_path.exists();
-    let mut config_changed = !config_exists;
-    let mut config: serde_json::Value = if config_exists {
-        let raw = fs::read_to_string(&config_path)
-            .map_err(|e| format!("Failed to read {}: {e}", config_path.display()))?;
-        json5::from_str(&raw).unwrap_or_else(|_| serde_json::json!({}))
+    Ok(())
+}
+
+/// A workspace's project opencode config loaded for an additive merge. `raw` is `None` when
+/// the file does not exist yet.
+struct ProjectConfig {
+    path: PathBuf,
+    raw: Option<String>,
+    value: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Reads the project config, starting from an empty one when it is missing. A file that
+/// cannot be parsed or is not an object is an error so it is never overwritten.
+fn load_project_config(workspace_path: &str) -> Result<ProjectConfig, String> {
+    let path = resolve_opencode_config_path("project", workspace_path)?;
+    let raw = if path.exists() {
+        Some(
+            fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {e}", path.display()))?,
+        )
     } else {
-        serde_json::json!({
+        None
+    };
+    let value = match &raw {
+        Some(raw) => parse_opencode_config(raw).map_err(|e| {
+            format!(
+                "Refusing to overwrite {}: it could not be parsed ({e})",
+                path.display()
+            )
+        })?,
+        None => serde_json::json!({
           "$schema": "https://opencode.ai/config.json"
-        })
+        }),
+    };
+    let serde_json::Value::Object(value) = value else {
+        return Err(format!(
+            "Refusing to overwrite {}: expected a JSON object",
+            path.display()
+        ));
     };
+    Ok(ProjectConfig { path, raw, value })
+}
 
-    if !config.is_object() {
-        config = serde_json::json!({
-          "$schema": "https://opencode.ai/config.json"
-        });
-        config_changed = true;
+/// Adds any of `required` missing from the config's `plugin` list. Returns whether the
+/// list changed.
+fn merge_required_plugins(
+    config: &mut serde_json::Map<String, serde_json::Value>,
+    required: &[&str],
+) -> bool {
+    let existing: Vec<String> = match config.get("plugin") {
+        Some(serde_json::Value::Array(arr)) => arr
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect(),
+        Some(serde_json::Value::String(s)) => vec![s.clone()],
+        _ => vec![],
+    };
+    let merged = merge_plugins(existing.clone(), required);
+    if merged == existing {
+        return false;
+    }
+    config.insert(
+        "plugin".to_string(),
+        serde_json::Value::Array(merged.into_iter().map(serde_json::Value::String).collect()),
+    );
+    true
+}
+
+/// Writes the project config back, keeping a backup of the previous contents.
+fn save_project_config(config: &ProjectConfig) -> Result<(), String> {
+    if let Some(raw) = &config.raw {
+        backup_jsonc_config(&config.path, raw)?;
     }
+    let serialized = serde_json::to_string_pretty(&config.value).map_err(|e| e.to_string())?;
+    write_atomic(&config.path, serialized.as_bytes())
+}
 
-    let required_plugins: Vec<&str> = match preset {
-        "starter" => vec!["opencode-scheduler"],
-        "automation" => vec!["opencode-scheduler"],
-        _ => vec![],
+fn save_workspace_openwork(
+    openwork_path: &Path,
+    config: &WorkspaceOpenworkConfig,
+) -> Result<(), String> {
+    if let Some(parent) = openwork_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    write_openwork_config(openwork_path, config)
+}
+
+/// Writes openwork.json, stamping `updated_at` so writers holding an older copy can be
+/// detected.
+pub fn write_openwork_config(
+    openwork_path: &Path,
+    config: &WorkspaceOpenworkConfig,
+) -> Result<(), String> {
+    let stamped = WorkspaceOpenworkConfig {
+        updated_at: Some(now_ms()),
+        ..config.clone()
     };
+    let serialized = serde_json::to_string_pretty(&stamped).map_err(|e| e.to_string())?;
+    write_atomic(openwork_path, serialized.as_bytes())
+}
 
-    let should_seed_chrome_mcp = matches!(preset, "starter");
+/// Updates only `workspace.name` in openwork.json, leaving the preset, creation time and
+/// authorized roots as they are.
+pub fn set_workspace_openwork_name(
+    workspace_path: &str,
+    name: &str,
+) -> Result<WorkspaceOpenworkConfig, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("name is required".to_string());
+    }
 
-    if !required_plugins.is_empty() {
-        let plugins_value = config
-            .get("plugin")
-            .cloned()
-            .unwrap_or_else(|| serde_json::json!([]));
-
-        let existing_plugins: Vec<String> = match plugins_value {
-            serde_json::Value::Array(arr) => arr
-                .into_iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect(),
-            serde_json::Value::String(s) => vec![s],
-            _ => vec![],
-        };
+    let openwork_path = PathBuf::from(workspace_path)
+        .join(".opencode")
+        .join("openwork.json");
+    if !openwork_path.exists() {
+        return Err(format!("{} does not exist", openwork_path.display()));
+    }
+    let raw = fs::read_to_string(&openwork_path)
+        .map_err(|e| format!("Failed to read {}: {e}", openwork_path.display()))?;
+    let mut openwork = serde_json::from_str::<WorkspaceOpenworkConfig>(&raw)
+        .map_err(|e| format!("Failed to parse {}: {e}", openwork_path.display()))?;
+
+    let workspace = openwork
+        .workspace
+        .get_or_insert(WorkspaceOpenworkWorkspace {
+            name: None,
+            created_at: None,
+            preset: None,
+        });
+    workspace.name = Some(name.to_string());
 
-        let merged = merge_plugins(existing_plugins.clone(), &required_plugins);
-        if merged != existing_plugins {
-            config_changed = true;
-        }
-        if let Some(obj) = config.as_object_mut() {
-            obj.insert(
-                "plugin".to_string(),
-                serde_json::Value::Array(
-                    merged.into_iter().map(serde_json::Value::String).collect(),
-                ),
-            );
+    write_openwork_config(&openwork_path, &openwork)?;
+    Ok(openwork)
+}
+
+/// Points a copied `openwork.json` at its new home: `old_root` and folders inside it are
+/// rebased onto `workspace_path`, other authorized roots are kept as-is.
+pub fn rebase_openwork_authorized_roots(
+    workspace_path: &str,
+    old_root: &str,
+) -> Result<(), String> {
+    let openwork_path = PathBuf::from(workspace_path)
+        .join(".opencode")
+        .join("openwork.json");
+    if !openwork_path.exists() {
+        return Ok(());
+    }
+    let raw = fs::read_to_string(&openwork_path)
+        .map_err(|e| format!("Failed to read {}: {e}", openwork_path.display()))?;
+    let mut openwork = serde_json::from_str::<WorkspaceOpenworkConfig>(&raw)
+        .map_err(|e| format!("Failed to parse {}: {e}", openwork_path.display()))?;
+
+    let old_root = Path::new(old_root);
+    let mut roots: Vec<String> = Vec::new();
+    for root in openwork.authorized_roots {
+        let rebased = match Path::new(root.trim()).strip_prefix(old_root) {
+            Ok(rest) if rest.as_os_str().is_empty() => workspace_path.to_string(),
+            Ok(rest) => Path::new(workspace_path)
+                .join(rest)
+                .to_string_lossy()
+                .to_string(),
+            Err(_) => root,
+        };
+        if !roots.contains(&rebased) {
+            roots.push(rebased);
         }
     }
+    if !roots.iter().any(|root| root == workspace_path) {
+        roots.insert(0, workspace_path.to_string());
+    }
+    openwork.authorized_roots = roots;
 
-    if should_seed_chrome_mcp {
-        if let Some(obj) = config.as_object_mut() {
-            let mcp_value = obj
-                .get("mcp")
-                .cloned()
-                .unwrap_or_else(|| serde_json::json!({}));
-
-            let mut mcp_obj = match mcp_value {
-                serde_json::Value::Object(map) => map,
-                _ => serde_json::Map::new(),
-            };
-
-            if !mcp_obj.contains_key("chrome-devtools") {
-                mcp_obj.insert(
-                    "chrome-devtools".to_string(),
-                    serde_json::json!({
-                      "type": "local",
-                      "command": ["npx", "-y", "chrome-devtools-mcp@latest"]
-                    }),
-                );
-                config_changed = true;
-            }
+    write_openwork_config(&openwork_path, &openwork)
+}
 
-            obj.insert("mcp".to_string(), serde_json::Value::Object(mcp_obj));
+fn hash_section(hasher: &mut StableHasher, label: &str, content: Option<&[u8]>) {
+    hasher.write(label.as_bytes());
+    match content {
+        Some(bytes) => {
+            hasher.write(&(bytes.len() as u64).to_le_bytes());
+            hasher.write(bytes);
         }
+        None => hasher.write(b"\0missing"),
     }
+}
 
-    if config_changed {
-        fs::write(
-            &config_path,
-            serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?,
-        )
-        .map_err(|e| format!("Failed to write {}: {e}", config_path.display()))?;
+/// Digest of the files that make up a workspace's effective config: `opencode.json(c)`,
+/// `.opencode/openwork.json`, and the workspace's skill directory listing.
+pub fn workspace_config_fingerprint(workspace_path: &Path) -> Result<String, String> {
+    let mut hasher = StableHasher::default();
+
+    for relative in ["opencode.jsonc", "opencode.json", ".opencode/openwork.json"] {
+        let path = workspace_path.join(relative);
+        let content = match fs::read(&path) {
+            Ok(bytes) => Some(bytes),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(format!("Failed to read {}: {e}", path.display())),
+        };
+        hash_section(&mut hasher, relative, content.as_deref());
     }
 
-    let openwork_path = root.join(".opencode").join("openwork.json");
-    if !openwork_path.exists() {
-        let openwork = WorkspaceOpenworkConfig::new(workspace_path, preset, now_ms());
+    for relative in [".opencode/skills", ".opencode/skill", ".claude/skills"] {
+        let root = workspace_path.join(relative);
+        if !root.is_dir() {
+            hash_section(&mut hasher, relative, None);
+            continue;
+        }
+        let mut listing = String::new();
+        for entry in walkdir::WalkDir::new(&root)
+            .min_depth(1)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(Result::ok)
+        {
+            let name = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+            let size = entry
+                .metadata()
+                .ok()
+                .filter(|meta| meta.is_file())
+                .map(|meta| meta.len());
+            listing.push_str(&name.to_string_lossy().replace('\\', "/"));
+            if let Some(size) = size {
+                listing.push_str(&format!(" {size}"));
+            }
+            listing.push('\n');
+        }
+        hash_section(&mut hasher, relative, Some(listing.as_bytes()));
+    }
 
-        fs::create_dir_all(openwork_path.parent().unwrap())
-            .map_err(|e| format!("Failed to create {}: {e}", openwork_path.display()))?;
+    Ok(format!("{:016x}", hasher.finish()))
+}
 
-        fs::write(
-            &openwork_path,
-            serde_json::to_string_pretty(&openwork).map_err(|e| e.to_string())?,
-        )
-        .map_err(|e| format!("Failed to write {}: {e}", openwork_path.display()))?;
+#[cfg(unix)]
+const SKILL_DOC_EXTENSIONS: &[&str] = &["md", "txt", "json", "jsonc", "yaml", "yml", "toml"];
+
+/// Restores modes under `.opencode/skills` that a copy or restore may have stripped:
+/// scripts with a shebang become 755, docs 644. Other files are left alone. Returns the
+/// changed paths relative to the workspace.
+#[cfg(unix)]
+pub fn fix_skill_permissions(workspace_path: &Path) -> Result<Vec<String>, String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let root = workspace_path.join(".opencode").join("skills");
+    let mut changed = Vec::new();
+    if !root.is_dir() {
+        return Ok(changed);
     }
 
-    Ok(())
+    for entry in walkdir::WalkDir::new(&root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let mut head = [0u8; 2];
+        let has_shebang = fs::File::open(path)
+            .and_then(|mut file| file.read_exact(&mut head))
+            .is_ok()
+            && &head == b"#!";
+        let is_doc = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| SKILL_DOC_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+        let wanted = if has_shebang {
+            0o755
+        } else if is_doc {
+            0o644
+        } else {
+            continue;
+        };
+
+        let metadata =
+            fs::metadata(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let mut permissions = metadata.permissions();
+        if permissions.mode() & 0o777 == wanted {
+            continue;
+        }
+        permissions.set_mode((permissions.mode() & !0o777) | wanted);
+        fs::set_permissions(path, permissions)
+            .map_err(|e| format!("Failed to update {}: {e}", path.display()))?;
+        let relative = path.strip_prefix(workspace_path).unwrap_or(path);
+        changed.push(relative.to_string_lossy().to_string());
+    }
+    Ok(changed)
+}
+
+/// Windows has no execute bit to restore.
+#[cfg(not(unix))]
+pub fn fix_skill_permissions(_workspace_path: &Path) -> Result<Vec<String>, String> {
+    Ok(Vec::new())
 }
@@ -1,4 +1,9 @@
 pub mod commands;
 pub mod files;
+pub mod presets;
+pub mod remote;
+pub mod scope;
+pub mod setup_script;
 pub mod state;
+pub mod templates;
 pub mod watch;
@@ -0,0 +1,89 @@
+use crate::types::WorkspaceTemplate;
+use crate::utils::now_ms;
+use crate::workspace::templates::{
+    sanitize_template_id, template_exists, template_variables, templates_dir, write_template,
+};
+
+pub struct PresetTemplate {
+    pub title: &'static str,
+    pub description: &'static str,
+    pub prompt: &'static str,
+}
+
+pub struct PresetDefinition {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub required_plugins: &'static [&'static str],
+    pub seed_chrome_mcp: bool,
+    pub templates: &'static [PresetTemplate],
+}
+
+pub const PRESETS: &[PresetDefinition] = &[
+    PresetDefinition {
+        id: "starter",
+        name: "Starter workspace",
+        required_plugins: &["opencode-scheduler"],
+        seed_chrome_mcp: true,
+        templates: &[PresetTemplate {
+            title: "Summarize this workspace",
+            description: "Get an overview of the files in this workspace",
+            prompt: "Summarize the files in this workspace and suggest what I could do next.",
+        }],
+    },
+    PresetDefinition {
+        id: "automation",
+        name: "Automation workspace",
+        required_plugins: &["opencode-scheduler"],
+        seed_chrome_mcp: false,
+        templates: &[PresetTemplate {
+            title: "Run a scheduled task",
+            description: "Set up a job that runs on a schedule",
+            prompt:
+                "Help me create a scheduled job. Ask what it should do and how often it should run.",
+        }],
+    },
+    PresetDefinition {
+        id: "minimal",
+        name: "Empty workspace",
+        required_plugins: &[],
+        seed_chrome_mcp: false,
+        templates: &[],
+    },
+];
+
+pub fn find_preset(id: &str) -> Option<&'static PresetDefinition> {
+    PRESETS.iter().find(|preset| preset.id == id.trim())
+}
+
+/// Writes the preset's templates that are not already present; returns the ids it created.
+pub fn seed_preset_templates(workspace_path: &str, preset: &str) -> Result<Vec<String>, String> {
+    let Some(definition) = find_preset(preset) else {
+        return Ok(Vec::new());
+    };
+
+    let dir = templates_dir(workspace_path);
+    let mut seeded = Vec::new();
+    for template in definition.templates {
+        let Some(id) = sanitize_template_id(template.title) else {
+            continue;
+        };
+        if template_exists(&dir, &id) {
+            continue;
+        }
+
+        write_template(
+            workspace_path,
+            &WorkspaceTemplate {
+                id: id.clone(),
+                title: template.title.to_string(),
+                description: template.description.to_string(),
+                prompt: template.prompt.to_string(),
+                created_at: now_ms(),
+                variables: template_variables(template.prompt),
+            },
+        )?;
+        seeded.push(id);
+    }
+
+    Ok(seeded)
+}
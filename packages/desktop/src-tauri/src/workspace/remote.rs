@@ -0,0 +1,151 @@
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::types::{RemoteReachability, RemoteTestStep, RemoteType, WorkspaceInfo};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(3);
+
+struct Probe {
+    agent: ureq::Agent,
+    token: Option<String>,
+    directory: Option<String>,
+}
+
+impl Probe {
+    fn get(&self, url: &str, authenticated: bool) -> Result<Value, String> {
+        let mut request = self.agent.get(url).set("Accept", "application/json");
+        if authenticated {
+            let Some(token) = self.token.as_deref() else {
+                return Err("No token provided".to_string());
+            };
+            request = request.set("Authorization", &format!("Bearer {token}"));
+        }
+        if let Some(directory) = self.directory.as_deref() {
+            request = request.set("X-OpenCode-Directory", directory);
+        }
+
+        match request.call() {
+            Ok(response) => response
+                .into_json::<Value>()
+                .map_err(|e| format!("Failed to parse response from {url}: {e}")),
+            Err(ureq::Error::Status(401, _)) | Err(ureq::Error::Status(403, _)) => {
+                Err(format!("Token rejected by {url}"))
+            }
+            Err(ureq::Error::Status(code, _)) => Err(format!("{url} returned HTTP {code}")),
+            Err(e) => Err(format!("{url} unreachable: {e}")),
+        }
+    }
+}
+
+fn describe_list(value: &Value, key: &str, label: &str) -> String {
+    let items = value.as_array().or_else(|| value.get(key)?.as_array());
+    match items {
+        Some(items) => format!("{} {label}", items.len()),
+        None => "OK".to_string(),
+    }
+}
+
+/// Runs the remote connect flow step by step, stopping at the first failing step.
+pub fn run_remote_handshake(workspace: &WorkspaceInfo, token: Option<&str>) -> Vec<RemoteTestStep> {
+    let probe = Probe {
+        agent: ureq::AgentBuilder::new().timeout(PROBE_TIMEOUT).build(),
+        token: token
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty()),
+        directory: workspace.directory.clone(),
+    };
+
+    let base_url = workspace
+        .base_url
+        .as_deref()
+        .unwrap_or_default()
+        .trim_end_matches('/')
+        .to_string();
+    let is_openwork = workspace.remote_type == Some(RemoteType::Openwork);
+    let host_url = workspace
+        .openwork_host_url
+        .as_deref()
+        .unwrap_or_default()
+        .trim_end_matches('/')
+        .to_string();
+
+    let mut checks: Vec<(&str, String, bool)> = Vec::new();
+    if is_openwork {
+        checks.push(("health", format!("{host_url}/health"), false));
+        checks.push(("workspaces", format!("{host_url}/workspaces"), true));
+    } else {
+        checks.push(("health", format!("{base_url}/global/health"), false));
+    }
+    checks.push(("sessions", format!("{base_url}/session"), is_openwork));
+    if is_openwork {
+        checks.push(("authenticated", format!("{host_url}/status"), true));
+    }
+
+    let mut steps = Vec::new();
+    for (step, url, authenticated) in checks {
+        let result = probe.get(&url, authenticated);
+        let (ok, detail) = match result {
+            Ok(value) => (
+                true,
+                match step {
+                    "workspaces" => describe_list(&value, "items", "workspaces"),
+                    "sessions" => describe_list(&value, "items", "sessions"),
+                    _ => "OK".to_string(),
+                },
+            ),
+            Err(error) => (false, error),
+        };
+        steps.push(RemoteTestStep {
+            step: step.to_string(),
+            ok,
+            detail,
+        });
+        if !ok {
+            break;
+        }
+    }
+
+    steps
+}
+
+/// Single unauthenticated health request, cheap enough to poll for a status dot. Any HTTP
+/// response counts as reachable; only transport failures (DNS, refused, timeout) do not.
+pub fn check_remote_reachable(workspace: &WorkspaceInfo) -> RemoteReachability {
+    let base_url = workspace.base_url.as_deref().unwrap_or_default();
+    let url = if workspace.remote_type == Some(RemoteType::Openwork) {
+        let host_url = workspace
+            .openwork_host_url
+            .as_deref()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or(base_url);
+        format!("{}/health", host_url.trim().trim_end_matches('/'))
+    } else {
+        format!("{}/global/health", base_url.trim().trim_end_matches('/'))
+    };
+
+    let agent = ureq::AgentBuilder::new()
+        .timeout(REACHABILITY_TIMEOUT)
+        .build();
+    let started = Instant::now();
+    let result = agent.get(&url).call();
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let (reachable, status, error) = match result {
+        Ok(response) => (true, Some(response.status()), None),
+        Err(ureq::Error::Status(code, _)) => (
+            true,
+            Some(code),
+            Some(format!("{url} returned HTTP {code}")),
+        ),
+        Err(e) => (false, None, Some(format!("{url} unreachable: {e}"))),
+    };
+    RemoteReachability {
+        reachable,
+        url,
+        status,
+        latency_ms,
+        error,
+    }
+}
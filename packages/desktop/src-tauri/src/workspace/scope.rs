@@ -0,0 +1,340 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::types::{
+    AuthorizedRootAudit, AuthorizedRootRisk, WorkspaceOpenworkConfig, WorkspaceType,
+};
+use crate::workspace::state::load_workspace_state;
+
+pub fn read_workspace_openwork_config(
+    workspace_path: &Path,
+) -> Result<WorkspaceOpenworkConfig, String> {
+    let openwork_path = workspace_path.join(".opencode").join("openwork.json");
+    if !openwork_path.exists() {
+        let mut cfg = WorkspaceOpenworkConfig::default();
+        let workspace_value = workspace_path.to_string_lossy().to_string();
+        if !workspace_value.trim().is_empty() {
+            cfg.authorized_roots.push(workspace_value);
+        }
+        return Ok(cfg);
+    }
+
+    let raw = fs::read_to_string(&openwork_path)
+        .map_err(|e| format!("Failed to read {}: {e}", openwork_path.display()))?;
+
+    serde_json::from_str::<WorkspaceOpenworkConfig>(&raw)
+        .map_err(|e| format!("Failed to parse {}: {e}", openwork_path.display()))
+}
+
+/// Canonical workspace root plus every authorized root that still resolves on disk.
+pub fn allowed_roots(workspace_root: &Path) -> Result<Vec<PathBuf>, String> {
+    let config = read_workspace_openwork_config(workspace_root)?;
+    let mut roots = vec![workspace_root.to_path_buf()];
+
+    for root in config.authorized_roots {
+        let trimmed = root.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let Ok(root) = fs::canonicalize(trimmed) else {
+            continue;
+        };
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+
+    Ok(roots)
+}
+
+pub fn canonical_workspace_root(workspace_path: &str) -> Result<PathBuf, String> {
+    let trimmed = workspace_path.trim();
+    if trimmed.is_empty() {
+        return Err("workspacePath is required".to_string());
+    }
+
+    let root =
+        fs::canonicalize(trimmed).map_err(|e| format!("Failed to resolve workspacePath: {e}"))?;
+    if !root.is_dir() {
+        return Err("workspacePath must be a directory".to_string());
+    }
+    Ok(root)
+}
+
+/// Like [`canonical_workspace_root`], but only accepts the root of a registered local
+/// workspace, so IPC callers cannot point file commands at arbitrary folders.
+pub fn registered_workspace_root(
+    app: &tauri::AppHandle,
+    workspace_path: &str,
+) -> Result<PathBuf, String> {
+    let root = canonical_workspace_root(workspace_path)?;
+    let registered = load_workspace_state(app)?
+        .workspaces
+        .iter()
+        .filter(|workspace| workspace.workspace_type == WorkspaceType::Local)
+        .filter_map(|workspace| fs::canonicalize(workspace.path.trim()).ok())
+        .any(|path| path == root);
+    if !registered {
+        return Err(format!("{} is not a registered workspace", root.display()));
+    }
+    Ok(root)
+}
+
+fn validate_relative_path(relative_path: &str) -> Result<PathBuf, String> {
+    let relative = PathBuf::from(relative_path.trim());
+    if relative.is_absolute() {
+        return Err("relativePath must be relative to the workspace".to_string());
+    }
+
+    if relative.components().any(|component| {
+        matches!(
+            component,
+            Component::ParentDir | Component::RootDir | Component::Prefix(_)
+        )
+    }) {
+        return Err("relativePath must not contain '..'".to_string());
+    }
+
+    Ok(relative)
+}
+
+fn ensure_within_roots(candidate: &Path, roots: &[PathBuf]) -> Result<(), String> {
+    if roots.iter().any(|root| candidate.starts_with(root)) {
+        return Ok(());
+    }
+    Err(format!(
+        "{} is outside the workspace's authorized roots",
+        candidate.display()
+    ))
+}
+
+/// Resolves `relative_path` under the workspace, following symlinks, and rejects
+/// anything that lands outside the workspace or its authorized roots.
+pub fn resolve_existing_path(root: &Path, relative_path: &str) -> Result<PathBuf, String> {
+    let relative = validate_relative_path(relative_path)?;
+    let joined = root.join(relative);
+
+    let canonical = fs::canonicalize(&joined)
+        .map_err(|e| format!("Failed to resolve {}: {e}", joined.display()))?;
+    ensure_within_roots(&canonical, &allowed_roots(root)?)?;
+    Ok(canonical)
+}
+
+/// Resolves an existing directory, absolute or relative to the workspace, that must sit
+/// inside the workspace or one of its authorized roots.
+pub fn resolve_authorized_dir(workspace_path: &str, dir: &str) -> Result<PathBuf, String> {
+    let root = canonical_workspace_root(workspace_path)?;
+    let dir = dir.trim();
+    if dir.is_empty() {
+        return Err("directory is required".to_string());
+    }
+
+    let joined = root.join(dir);
+    let canonical = fs::canonicalize(&joined)
+        .map_err(|e| format!("Failed to resolve {}: {e}", joined.display()))?;
+    if !canonical.is_dir() {
+        return Err(format!("{} is not a directory", canonical.display()));
+    }
+    ensure_within_roots(&canonical, &allowed_roots(&root)?)?;
+    Ok(canonical)
+}
+
+/// Like [`resolve_existing_path`], but allows the target (and missing parent
+/// directories) to not exist yet. Containment is checked against the nearest
+/// existing ancestor so symlinked directories cannot be used to escape.
+pub fn resolve_writable_path(root: &Path, relative_path: &str) -> Result<PathBuf, String> {
+    let relative = validate_relative_path(relative_path)?;
+    if relative.as_os_str().is_empty() {
+        return Err("relativePath is required".to_string());
+    }
+    let joined = root.join(&relative);
+    let roots = allowed_roots(root)?;
+
+    if fs::symlink_metadata(&joined).is_ok() {
+        let canonical = fs::canonicalize(&joined)
+            .map_err(|e| format!("Failed to resolve {}: {e}", joined.display()))?;
+        ensure_within_roots(&canonical, &roots)?;
+        if canonical.is_dir() {
+            return Err(format!("{} is a directory", canonical.display()));
+        }
+        return Ok(canonical);
+    }
+
+    let mut ancestor = joined.as_path();
+    let mut missing = Vec::new();
+    while !ancestor.exists() {
+        let Some(name) = ancestor.file_name() else {
+            return Err(format!("Failed to resolve {}", joined.display()));
+        };
+        missing.push(name.to_os_string());
+        ancestor = ancestor
+            .parent()
+            .ok_or_else(|| format!("Failed to resolve {}", joined.display()))?;
+    }
+
+    let mut resolved = fs::canonicalize(ancestor)
+        .map_err(|e| format!("Failed to resolve {}: {e}", ancestor.display()))?;
+    ensure_within_roots(&resolved, &roots)?;
+    for name in missing.into_iter().rev() {
+        resolved.push(name);
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(not(windows))]
+const SYSTEM_DIRS: &[&str] = &[
+    "/",
+    "/bin",
+    "/boot",
+    "/dev",
+    "/etc",
+    "/lib",
+    "/proc",
+    "/sbin",
+    "/sys",
+    "/usr",
+    "/var",
+    "/System",
+    "/Library",
+    "/Applications",
+    "/private",
+    "/private/etc",
+    "/private/var",
+    "/Users",
+    "/home",
+];
+
+#[cfg(windows)]
+const SYSTEM_DIRS: &[&str] = &[
+    "C:\\",
+    "C:\\Windows",
+    "C:\\Windows\\System32",
+    "C:\\Program Files",
+    "C:\\Program Files (x86)",
+    "C:\\ProgramData",
+    "C:\\Users",
+];
+
+/// Rejects folders that would hand the engine the whole machine: filesystem roots,
+/// OS directories, and the user's home directory itself.
+pub fn reject_system_dir(path: &Path) -> Result<(), String> {
+    let is_system = path.parent().is_none()
+        || SYSTEM_DIRS.iter().any(|dir| path == Path::new(dir))
+        || crate::paths::home_dir().is_some_and(|home| path == home);
+    if is_system {
+        return Err(format!(
+            "{} is a system directory and cannot be authorized",
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+/// OS trees whose contents should never be handed to the agent, unlike `SYSTEM_DIRS`
+/// entries such as `/home` that merely must not be authorized wholesale.
+#[cfg(not(windows))]
+const SENSITIVE_SUBTREES: &[&str] = &[
+    "/bin",
+    "/boot",
+    "/dev",
+    "/etc",
+    "/lib",
+    "/proc",
+    "/sbin",
+    "/sys",
+    "/usr",
+    "/System",
+    "/Library",
+    "/private/etc",
+];
+
+#[cfg(windows)]
+const SENSITIVE_SUBTREES: &[&str] = &["C:\\Windows", "C:\\ProgramData"];
+
+const CREDENTIAL_DIRS: &[&str] = &[".ssh", ".aws", ".gnupg", ".kube", ".docker"];
+
+fn flag(audit: &mut AuthorizedRootAudit, risk: AuthorizedRootRisk, reason: String) {
+    audit.risk = audit.risk.max(risk);
+    audit.reasons.push(reason);
+}
+
+/// Re-checks one authorized root against the rules applied when it was added, since a
+/// folder may since have been replaced by a symlink to somewhere sensitive.
+pub fn audit_authorized_root(workspace_root: &Path, root: &str) -> AuthorizedRootAudit {
+    let mut audit = AuthorizedRootAudit {
+        root: root.to_string(),
+        resolved: None,
+        risk: AuthorizedRootRisk::Ok,
+        reasons: Vec::new(),
+    };
+
+    let literal = Path::new(root.trim());
+    let Ok(resolved) = fs::canonicalize(literal) else {
+        flag(
+            &mut audit,
+            AuthorizedRootRisk::Missing,
+            "Path no longer exists".to_string(),
+        );
+        return audit;
+    };
+    audit.resolved = Some(resolved.to_string_lossy().to_string());
+
+    if reject_system_dir(&resolved).is_err() {
+        flag(
+            &mut audit,
+            AuthorizedRootRisk::Danger,
+            "Resolves to a filesystem root, system directory, or the home directory".to_string(),
+        );
+    } else if SENSITIVE_SUBTREES
+        .iter()
+        .any(|dir| resolved.starts_with(dir))
+    {
+        flag(
+            &mut audit,
+            AuthorizedRootRisk::Danger,
+            "Resolves inside a system directory".to_string(),
+        );
+    }
+
+    if let Some(home) = crate::paths::home_dir() {
+        if home.starts_with(&resolved) && home != resolved {
+            flag(
+                &mut audit,
+                AuthorizedRootRisk::Danger,
+                "Contains the home directory".to_string(),
+            );
+        }
+        if CREDENTIAL_DIRS
+            .iter()
+            .any(|dir| resolved.starts_with(home.join(dir)))
+        {
+            flag(
+                &mut audit,
+                AuthorizedRootRisk::Danger,
+                "Resolves inside a credential store".to_string(),
+            );
+        }
+    }
+
+    let via_symlink = literal.is_absolute() && literal != resolved;
+    if via_symlink && !resolved.starts_with(workspace_root) {
+        flag(
+            &mut audit,
+            AuthorizedRootRisk::Warning,
+            format!(
+                "Symlink resolves outside the workspace to {}",
+                resolved.display()
+            ),
+        );
+    }
+    if !literal.is_absolute() {
+        flag(
+            &mut audit,
+            AuthorizedRootRisk::Warning,
+            "Relative path resolves against the app's working directory".to_string(),
+        );
+    }
+
+    audit
+}
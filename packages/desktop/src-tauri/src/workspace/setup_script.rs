@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::engine::spawn::{build_engine_args, engine_env, shell_quote};
+use crate::paths::home_dir;
+use crate::types::WorkspaceInfo;
+use crate::utils::redact_secrets;
+
+const HEREDOC_MARKER: &str = "OPENWORK_EOF";
+const PASSWORD_PLACEHOLDER: &str = "<OPENCODE_SERVER_PASSWORD>";
+
+fn is_secret_key(key: &str) -> bool {
+    let lower = key.to_ascii_lowercase().replace(['-', '_'], "");
+    lower.ends_with("apikey")
+        || lower.contains("token")
+        || lower.contains("secret")
+        || lower.contains("password")
+        || lower == "authorization"
+}
+
+fn placeholder_for(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("<{name}>")
+}
+
+/// Swaps secret-looking string values for `<KEY_NAME>` placeholders. `{env:VAR}`
+/// references are kept since they do not embed the secret itself.
+fn redact_json_secrets(value: &mut Value) -> bool {
+    let mut changed = false;
+    match value {
+        Value::Object(map) => {
+            for (key, entry) in map.iter_mut() {
+                match entry {
+                    Value::String(text) if is_secret_key(key) && !text.starts_with("{env:") => {
+                        *entry = Value::String(placeholder_for(key));
+                        changed = true;
+                    }
+                    _ => changed |= redact_json_secrets(entry),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                changed |= redact_json_secrets(item);
+            }
+        }
+        _ => {}
+    }
+    changed
+}
+
+fn redacted_file_content(relative: &str, content: &str) -> String {
+    if relative.ends_with(".json") || relative.ends_with(".jsonc") {
+        if let Ok(mut value) = json5::from_str::<Value>(content) {
+            if redact_json_secrets(&mut value) {
+                // Re-serializing drops JSONC comments; only done when a secret was found.
+                if let Ok(pretty) = serde_json::to_string_pretty(&value) {
+                    return format!("{pretty}\n");
+                }
+            }
+        }
+    }
+    redact_secrets(content, &[])
+}
+
+/// Escapes `value` for use inside a double-quoted shell string.
+fn double_quote_escape(value: &str) -> String {
+    value
+        .chars()
+        .flat_map(|c| match c {
+            '\\' | '"' | '$' | '`' => vec!['\\', c],
+            _ => vec![c],
+        })
+        .collect()
+}
+
+/// Flattens `value` onto one line so it cannot break out of a `#` comment.
+fn comment_text(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+fn default_workspace_dir(path: &Path) -> String {
+    if let Some(home) = home_dir() {
+        if let Ok(relative) = path.strip_prefix(&home) {
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            return format!("$HOME/{}", double_quote_escape(&relative));
+        }
+    }
+    double_quote_escape(&path.to_string_lossy())
+}
+
+/// Builds a POSIX shell script that installs OpenCode, recreates the workspace folder
+/// and its config `files` (absolute path, workspace-relative path), and exports the
+/// engine environment. Secrets are replaced with `<PLACEHOLDER>` markers.
+pub fn build_setup_script(workspace: &WorkspaceInfo, files: &[(PathBuf, String)]) -> String {
+    let mut lines = vec![
+        "#!/bin/sh".to_string(),
+        format!(
+            "# OpenWork setup for workspace {} (preset: {}).",
+            shell_quote(&comment_text(&workspace.name)),
+            shell_quote(&comment_text(&workspace.preset))
+        ),
+        "# Replace every <PLACEHOLDER> before running. Usage: sh setup.sh [workspace-dir]"
+            .to_string(),
+        "set -eu".to_string(),
+        String::new(),
+        "if ! command -v opencode >/dev/null 2>&1; then".to_string(),
+        "  curl -fsSL https://opencode.ai/install | bash".to_string(),
+        "fi".to_string(),
+        String::new(),
+        format!(
+            "WORKSPACE=\"${{1:-{}}}\"",
+            default_workspace_dir(Path::new(&workspace.path))
+        ),
+        "mkdir -p \"$WORKSPACE\"".to_string(),
+        "cd \"$WORKSPACE\"".to_string(),
+    ];
+
+    for (absolute, relative) in files {
+        lines.push(String::new());
+        let Ok(content) = fs::read_to_string(absolute) else {
+            lines.push(format!(
+                "# Skipped {}: not a text file.",
+                comment_text(relative)
+            ));
+            continue;
+        };
+        let content = redacted_file_content(relative, &content);
+        let mut marker = HEREDOC_MARKER.to_string();
+        while content.lines().any(|line| line == marker) {
+            marker.push('_');
+        }
+        if let Some(parent) = Path::new(relative).parent() {
+            if !parent.as_os_str().is_empty() {
+                let parent = parent.to_string_lossy().replace('\\', "/");
+                lines.push(format!("mkdir -p {}", shell_quote(&parent)));
+            }
+        }
+        lines.push(format!("cat > {} <<'{marker}'", shell_quote(relative)));
+        lines.push(content.strip_suffix('\n').unwrap_or(&content).to_string());
+        lines.push(marker);
+    }
+
+    lines.push(String::new());
+    lines.push("# Environment OpenWork sets when it starts the engine.".to_string());
+    for (key, value) in engine_env(Some("opencode"), Some(PASSWORD_PLACEHOLDER)) {
+        if key.starts_with("XDG_") {
+            lines.push(format!(
+                "# export {key}={}  # path on the exporting machine",
+                shell_quote(&value)
+            ));
+        } else {
+            lines.push(format!("export {key}={}", shell_quote(&value)));
+        }
+    }
+    let args: Vec<String> = build_engine_args("127.0.0.1", 4096)
+        .iter()
+        .map(|arg| shell_quote(arg))
+        .collect();
+    lines.push(format!("# opencode {}", args.join(" ")));
+
+    let mut script = lines.join("\n");
+    script.push('\n');
+    script
+}
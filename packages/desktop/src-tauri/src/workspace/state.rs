@@ -1,15 +1,57 @@
 use std::fs;
-use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use tauri::Manager;
 
-use crate::types::{WorkspaceInfo, WorkspaceState, WorkspaceType, WORKSPACE_STATE_VERSION};
+use crate::fs::{io_error_message, write_atomic};
+use crate::types::{
+    DataDirStatus, RemoteType, WorkspaceInfo, WorkspaceState, WorkspaceType,
+    WORKSPACE_STATE_VERSION,
+};
+use crate::utils::StableHasher;
+
+/// State files older than this hold ids from `DefaultHasher`, which is not stable across
+/// Rust releases; `load_workspace_state` re-derives them.
+const STABLE_ID_STATE_VERSION: u8 = 4;
 
 pub fn stable_workspace_id(path: &str) -> String {
-    let mut hasher = std::collections::hash_map::DefaultHasher::new();
-    path.hash(&mut hasher);
-    format!("ws-{:x}", hasher.finish())
+    let trimmed = path.trim();
+    let normalized = trimmed.trim_end_matches(['/', '\\']);
+    let normalized = if normalized.is_empty() {
+        trimmed
+    } else {
+        normalized
+    };
+    let mut hasher = StableHasher::default();
+    hasher.write(normalized.as_bytes());
+    format!("ws-{:016x}", hasher.finish())
+}
+
+fn derive_workspace_id(workspace: &WorkspaceInfo) -> String {
+    match (&workspace.workspace_type, &workspace.remote_type) {
+        (WorkspaceType::Local, _) => stable_workspace_id(&workspace.path),
+        (WorkspaceType::Remote, Some(RemoteType::Openwork)) => stable_workspace_id_for_openwork(
+            workspace.openwork_host_url.as_deref().unwrap_or(""),
+            workspace.openwork_workspace_id.as_deref(),
+        ),
+        (WorkspaceType::Remote, _) => stable_workspace_id_for_remote(
+            workspace.base_url.as_deref().unwrap_or(""),
+            workspace.directory.as_deref(),
+        ),
+    }
+}
+
+fn migrate_workspace_ids(state: &mut WorkspaceState) {
+    for workspace in state.workspaces.iter_mut() {
+        if !workspace.id.starts_with("ws-") {
+            continue;
+        }
+        let id = derive_workspace_id(workspace);
+        if state.active_id == workspace.id {
+            state.active_id = id.clone();
+        }
+        workspace.id = id;
+    }
 }
 
 pub fn openwork_state_paths(app: &tauri::AppHandle) -> Result<(PathBuf, PathBuf), String> {
@@ -21,6 +63,37 @@ pub fn openwork_state_paths(app: &tauri::AppHandle) -> Result<(PathBuf, PathBuf)
     Ok((data_dir, file_path))
 }
 
+pub fn check_data_dir_status(app: &tauri::AppHandle) -> DataDirStatus {
+    let (dir, _) = match openwork_state_paths(app) {
+        Ok(paths) => paths,
+        Err(error) => {
+            return DataDirStatus {
+                writable: false,
+                path: None,
+                error: Some(error),
+            }
+        }
+    };
+
+    let probe = dir.join(format!(".write-probe-{}", std::process::id()));
+    let result = fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {e}", dir.display()))
+        .and_then(|_| {
+            fs::write(&probe, b"ok")
+                .map_err(|e| format!("Failed to write {}: {e}", probe.display()))
+        })
+        .and_then(|_| {
+            fs::remove_file(&probe)
+                .map_err(|e| format!("Failed to remove {}: {e}", probe.display()))
+        });
+
+    DataDirStatus {
+        writable: result.is_ok(),
+        path: Some(dir.to_string_lossy().to_string()),
+        error: result.err(),
+    }
+}
+
 pub fn load_workspace_state(app: &tauri::AppHandle) -> Result<WorkspaceState, String> {
     let (_, path) = openwork_state_paths(app)?;
     if !path.exists() {
@@ -32,21 +105,36 @@ pub fn load_workspace_state(app: &tauri::AppHandle) -> Result<WorkspaceState, St
     let mut state: WorkspaceState = serde_json::from_str(&raw)
         .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
 
+    if state.version < STABLE_ID_STATE_VERSION {
+        migrate_workspace_ids(&mut state);
+    }
     if state.version < WORKSPACE_STATE_VERSION {
         state.version = WORKSPACE_STATE_VERSION;
     }
+    // Missing folders (deleted, or on an unplugged drive) are reported, not forgotten.
+    for workspace in state.workspaces.iter_mut() {
+        workspace.exists = workspace.workspace_type != WorkspaceType::Local
+            || Path::new(workspace.path.trim()).is_dir();
+    }
 
     Ok(state)
 }
 
 pub fn save_workspace_state(app: &tauri::AppHandle, state: &WorkspaceState) -> Result<(), String> {
     let (dir, path) = openwork_state_paths(app)?;
-    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
-    fs::write(
-        &path,
-        serde_json::to_string_pretty(state).map_err(|e| e.to_string())?,
-    )
-    .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    fs::create_dir_all(&dir).map_err(|e| io_error_message("create", &dir, &e))?;
+    let mut value = serde_json::to_value(state).map_err(|e| e.to_string())?;
+    // `exists` is recomputed on every load, so it is not persisted.
+    if let Some(workspaces) = value
+        .get_mut("workspaces")
+        .and_then(|workspaces| workspaces.as_array_mut())
+    {
+        for workspace in workspaces.iter_mut().filter_map(|w| w.as_object_mut()) {
+            workspace.remove("exists");
+        }
+    }
+    let serialized = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+    write_atomic(&path, serialized.as_bytes())?;
     Ok(())
 }
 
@@ -72,6 +160,7 @@ pub fn ensure_starter_workspace(app: &tauri::AppHandle) -> Result<WorkspaceInfo,
         openwork_host_url: None,
         openwork_workspace_id: None,
         openwork_workspace_name: None,
+        exists: true,
     })
 }
 
@@ -0,0 +1,470 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::fs::{io_error_message, write_atomic};
+use crate::types::WorkspaceTemplate;
+use crate::workspace::commands::escape_yaml_scalar;
+
+const TEMPLATE_FILE_NAME: &str = "template.yml";
+
+pub fn templates_dir(workspace_path: &str) -> PathBuf {
+    PathBuf::from(workspace_path)
+        .join(".openwork")
+        .join("templates")
+}
+
+pub fn sanitize_template_id(raw: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut dash = false;
+    for ch in raw.trim().to_lowercase().chars() {
+        if ch.is_ascii_alphanumeric() || ch == '_' {
+            out.push(ch);
+            dash = false;
+            continue;
+        }
+        if !dash {
+            out.push('-');
+            dash = true;
+        }
+    }
+
+    let out = out.trim_matches('-').to_string();
+    if out.is_empty() {
+        return None;
+    }
+
+    Some(out)
+}
+
+/// Picks an id derived from `title` that does not collide with an existing template.
+pub fn unique_template_id(workspace_path: &str, title: &str) -> Result<String, String> {
+    let base = sanitize_template_id(title).ok_or_else(|| "title is required".to_string())?;
+    let dir = templates_dir(workspace_path);
+
+    let mut candidate = base.clone();
+    let mut suffix = 2;
+    while template_exists(&dir, &candidate) {
+        candidate = format!("{base}-{suffix}");
+        suffix += 1;
+    }
+
+    Ok(candidate)
+}
+
+pub fn template_exists(dir: &Path, id: &str) -> bool {
+    dir.join(id).exists()
+        || dir.join(format!("{id}.json")).exists()
+        || dir.join(format!("{id}.yml")).exists()
+}
+
+pub fn serialize_template_frontmatter(template: &WorkspaceTemplate) -> Result<String, String> {
+    let prompt = template.prompt.trim();
+    if prompt.is_empty() {
+        return Err("template.prompt is required".to_string());
+    }
+
+    let title = template.title.trim();
+    if title.is_empty() {
+        return Err("template.title is required".to_string());
+    }
+
+    let mut out = String::new();
+    out.push_str("---\n");
+    out.push_str(&format!("id: {}\n", escape_yaml_scalar(&template.id)));
+    out.push_str(&format!("title: {}\n", escape_yaml_scalar(title)));
+    out.push_str(&format!(
+        "description: {}\n",
+        escape_yaml_scalar(template.description.trim())
+    ));
+    out.push_str(&format!("createdAt: {}\n", template.created_at));
+    if !template.variables.is_empty() {
+        // A JSON array of strings is also a valid YAML flow sequence.
+        let variables = serde_json::to_string(&template.variables).map_err(|e| e.to_string())?;
+        out.push_str(&format!("variables: {variables}\n"));
+    }
+    out.push_str("---\n\n");
+    out.push_str(prompt);
+    out.push('\n');
+    Ok(out)
+}
+
+pub fn write_template(
+    workspace_path: &str,
+    template: &WorkspaceTemplate,
+) -> Result<PathBuf, String> {
+    let id =
+        sanitize_template_id(&template.id).ok_or_else(|| "template.id is required".to_string())?;
+    let dir = templates_dir(workspace_path).join(&id);
+    fs::create_dir_all(&dir).map_err(|e| io_error_message("create", &dir, &e))?;
+
+    let payload = WorkspaceTemplate {
+        id,
+        ..template.clone()
+    };
+    let file_path = dir.join(TEMPLATE_FILE_NAME);
+    let serialized = serialize_template_frontmatter(&payload)?;
+    write_atomic(&file_path, serialized.as_bytes())?;
+
+    Ok(file_path)
+}
+
+/// Reverses [`escape_yaml_scalar`]; unquoted values are returned trimmed.
+fn parse_yaml_scalar(raw: &str) -> String {
+    let raw = raw.trim();
+    let Some(inner) = raw
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        return raw
+            .strip_prefix('\'')
+            .and_then(|rest| rest.strip_suffix('\''))
+            .map(|inner| inner.replace("''", "'"))
+            .unwrap_or_else(|| raw.to_string());
+    };
+
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Parses flat `key: value` lines. A `key: |` block scalar collects the following
+/// indented lines.
+fn parse_yaml_fields(raw: &str) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+    let mut block: Option<(String, Vec<String>)> = None;
+
+    for line in raw.lines() {
+        if let Some((key, lines)) = block.as_mut() {
+            if line.trim().is_empty() || line.starts_with(' ') || line.starts_with('\t') {
+                lines.push(line.to_string());
+                continue;
+            }
+            fields.push((key.clone(), dedent_block(lines)));
+            block = None;
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_string();
+        if key.is_empty() || key.starts_with('#') {
+            continue;
+        }
+        let value = value.trim();
+        if value == "|" || value == "|-" || value == ">" {
+            block = Some((key, Vec::new()));
+        } else {
+            fields.push((key, parse_yaml_scalar(value)));
+        }
+    }
+    if let Some((key, lines)) = block {
+        fields.push((key, dedent_block(&lines)));
+    }
+    fields
+}
+
+fn dedent_block(lines: &[String]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// Parses the format written by [`serialize_template_frontmatter`]; legacy flat `.yml`
+/// files without a `---` header keep the prompt in a `prompt` field instead.
+pub fn parse_template_yaml(raw: &str, fallback_id: &str) -> Result<WorkspaceTemplate, String> {
+    let raw = raw.trim_start_matches('\u{feff}');
+    let (header, body) = match raw.strip_prefix("---") {
+        Some(rest) => {
+            let rest = rest.trim_start_matches(['\r', '\n']);
+            match rest.find("\n---") {
+                Some(end) => {
+                    let body = &rest[end + 4..];
+                    (&rest[..end], Some(body.trim_start_matches(['\r', '\n'])))
+                }
+                None => return Err("Unterminated template frontmatter".to_string()),
+            }
+        }
+        None => (raw, None),
+    };
+
+    let mut template = WorkspaceTemplate {
+        id: fallback_id.to_string(),
+        title: String::new(),
+        description: String::new(),
+        prompt: body.unwrap_or_default().trim().to_string(),
+        created_at: 0,
+        variables: Vec::new(),
+    };
+    for (key, value) in parse_yaml_fields(header) {
+        match key.as_str() {
+            "id" if !value.trim().is_empty() => template.id = value,
+            "title" | "name" => template.title = value,
+            "description" => template.description = value,
+            "prompt" if body.is_none() => template.prompt = value.trim().to_string(),
+            "createdAt" | "created_at" => template.created_at = value.parse().unwrap_or(0),
+            "variables" => template.variables = serde_json::from_str(&value).unwrap_or_default(),
+            _ => {}
+        }
+    }
+
+    if template.prompt.is_empty() {
+        return Err("template.prompt is missing".to_string());
+    }
+    if template.title.trim().is_empty() {
+        template.title = template.id.clone();
+    }
+    Ok(template)
+}
+
+fn parse_legacy_json(raw: &str, fallback_id: &str) -> Result<WorkspaceTemplate, String> {
+    let mut value: serde_json::Value =
+        serde_json::from_str(raw).map_err(|e| format!("Invalid template JSON: {e}"))?;
+    let Some(object) = value.as_object_mut() else {
+        return Err("Template JSON must be an object".to_string());
+    };
+    object
+        .entry("id")
+        .or_insert_with(|| serde_json::Value::String(fallback_id.to_string()));
+    if !object.contains_key("title") {
+        let id = object["id"].clone();
+        object.insert("title".to_string(), id);
+    }
+    serde_json::from_value(value).map_err(|e| format!("Invalid template JSON: {e}"))
+}
+
+fn read_template_file(path: &Path, fallback_id: &str) -> Result<WorkspaceTemplate, String> {
+    let raw = fs::read_to_string(path).map_err(|e| io_error_message("read", path, &e))?;
+    let parsed = if path.extension().is_some_and(|ext| ext == "json") {
+        parse_legacy_json(&raw, fallback_id)
+    } else {
+        parse_template_yaml(&raw, fallback_id)
+    };
+    let mut template = parsed.map_err(|e| format!("{}: {e}", path.display()))?;
+    if template.variables.is_empty() {
+        template.variables = template_variables(&template.prompt);
+    }
+    Ok(template)
+}
+
+/// Where a template lives: `<id>/template.yml`, or a legacy flat `<id>.json`/`<id>.yml`.
+fn template_paths(dir: &Path, id: &str) -> [PathBuf; 3] {
+    [
+        dir.join(id).join(TEMPLATE_FILE_NAME),
+        dir.join(format!("{id}.json")),
+        dir.join(format!("{id}.yml")),
+    ]
+}
+
+pub fn read_template(workspace_path: &str, template_id: &str) -> Result<WorkspaceTemplate, String> {
+    let id =
+        sanitize_template_id(template_id).ok_or_else(|| "templateId is required".to_string())?;
+    let dir = templates_dir(workspace_path);
+    let path = template_paths(&dir, &id)
+        .into_iter()
+        .find(|path| path.is_file())
+        .ok_or_else(|| format!("Template not found: {id}"))?;
+    read_template_file(&path, &id)
+}
+
+/// Lists every readable template, newest first. Unparseable files are logged and skipped
+/// so one bad template does not hide the rest.
+pub fn list_templates(workspace_path: &str) -> Result<Vec<WorkspaceTemplate>, String> {
+    let dir = templates_dir(workspace_path);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut ids = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(|e| io_error_message("read", &dir, &e))? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        let id = if path.is_dir() {
+            path.file_name()
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == "json" || ext == "yml")
+        {
+            path.file_stem()
+        } else {
+            None
+        };
+        if let Some(id) = id.and_then(|id| id.to_str()) {
+            if !ids.iter().any(|existing| existing == id) {
+                ids.push(id.to_string());
+            }
+        }
+    }
+
+    let mut templates = Vec::new();
+    for id in ids {
+        let Some(path) = template_paths(&dir, &id)
+            .into_iter()
+            .find(|path| path.is_file())
+        else {
+            continue;
+        };
+        match read_template_file(&path, &id) {
+            Ok(template) => templates.push(template),
+            Err(err) => println!("[templates] skipping {err}"),
+        }
+    }
+    templates.sort_by(|a, b| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    Ok(templates)
+}
+
+/// Splits `prompt` into literal text, each followed by the `{{name}}` placeholder after it
+/// (names trimmed).
+fn template_segments(prompt: &str) -> Vec<(&str, Option<&str>)> {
+    let mut segments = Vec::new();
+    let mut rest = prompt;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + len + 2;
+        let name = rest[start + 2..start + 2 + len].trim();
+        if name.is_empty() {
+            // `{{}}` is kept as literal text.
+            segments.push((&rest[..end], None));
+        } else {
+            segments.push((&rest[..start], Some(name)));
+        }
+        rest = &rest[end..];
+    }
+    segments.push((rest, None));
+    segments
+}
+
+/// Placeholder names used in `prompt`, in order of first use.
+pub fn template_variables(prompt: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for (_, name) in template_segments(prompt) {
+        if let Some(name) = name {
+            if !names.iter().any(|existing| existing == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Substitutes `{{name}}` placeholders in one pass, so values containing braces are
+/// inserted verbatim. Placeholders without a value are an error, which catches typos.
+pub fn render_template_prompt(
+    prompt: &str,
+    vars: &HashMap<String, String>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(prompt.len());
+    let mut missing: Vec<&str> = Vec::new();
+    for (text, name) in template_segments(prompt) {
+        out.push_str(text);
+        let Some(name) = name else {
+            continue;
+        };
+        match vars.get(name) {
+            Some(value) => out.push_str(value),
+            None => {
+                if !missing.contains(&name) {
+                    missing.push(name);
+                }
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(format!(
+            "Missing values for template variables: {}",
+            missing.join(", ")
+        ));
+    }
+    Ok(out)
+}
+
+const TEMPLATE_BUNDLE_KIND: &str = "openwork-templates";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TemplateBundle {
+    kind: String,
+    version: u32,
+    templates: Vec<WorkspaceTemplate>,
+}
+
+/// Serializes every template in the workspace into one JSON string for sharing.
+pub fn export_template_bundle(workspace_path: &str) -> Result<String, String> {
+    let bundle = TemplateBundle {
+        kind: TEMPLATE_BUNDLE_KIND.to_string(),
+        version: 1,
+        templates: list_templates(workspace_path)?,
+    };
+    serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())
+}
+
+/// Writes each template from `bundle`, returning `(imported, skipped)` ids. Existing
+/// templates are only replaced when `overwrite` is set.
+pub fn import_template_bundle(
+    workspace_path: &str,
+    bundle: &str,
+    overwrite: bool,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let bundle: TemplateBundle =
+        serde_json::from_str(bundle).map_err(|e| format!("Invalid template bundle: {e}"))?;
+    if bundle.kind != TEMPLATE_BUNDLE_KIND {
+        return Err("Not an OpenWork template bundle".to_string());
+    }
+
+    let dir = templates_dir(workspace_path);
+    let mut imported = Vec::new();
+    let mut skipped = Vec::new();
+    for template in bundle.templates {
+        let Some(id) =
+            sanitize_template_id(&template.id).or_else(|| sanitize_template_id(&template.title))
+        else {
+            skipped.push(template.id);
+            continue;
+        };
+        if template_exists(&dir, &id) && !overwrite {
+            skipped.push(id);
+            continue;
+        }
+        write_template(
+            workspace_path,
+            &WorkspaceTemplate {
+                id: id.clone(),
+                ..template
+            },
+        )?;
+        imported.push(id);
+    }
+    Ok((imported, skipped))
+}
@@ -6,7 +6,7 @@ use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde_json::json;
 use tauri::{AppHandle, Emitter, State};
 
-use crate::types::{WorkspaceInfo, WorkspaceType};
+use crate::types::{WorkspaceInfo, WorkspaceType, WorkspaceWatchStatus};
 
 const RELOAD_EVENT: &str = "openwork://reload-required";
 
@@ -15,6 +15,46 @@ pub struct WorkspaceWatchState {
     watcher: Mutex<Option<RecommendedWatcher>>,
     last_emit: Arc<Mutex<Option<Instant>>>,
     root: Mutex<Option<PathBuf>>,
+    watched: Mutex<Vec<PathBuf>>,
+    last_error: Arc<Mutex<Option<String>>>,
+}
+
+fn set_last_error(last_error: &Mutex<Option<String>>, error: Option<String>) {
+    *last_error
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = error;
+}
+
+pub fn workspace_watch_snapshot(state: &WorkspaceWatchState) -> WorkspaceWatchStatus {
+    let installed = state
+        .watcher
+        .lock()
+        .map(|watcher| watcher.is_some())
+        .unwrap_or(false);
+    let root = state
+        .root
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_deref()
+        .map(normalize_path);
+    let watched = state
+        .watched
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .map(|path| normalize_path(path))
+        .collect();
+    let last_error = state
+        .last_error
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    WorkspaceWatchStatus {
+        installed,
+        root,
+        watched,
+        last_error,
+    }
 }
 
 fn normalize_path(path: &Path) -> String {
@@ -24,7 +64,10 @@ fn normalize_path(path: &Path) -> String {
 fn reason_for_path(path: &Path) -> Option<&'static str> {
     let normalized = normalize_path(path);
     let lower = normalized.to_lowercase();
-    if lower.contains("/.opencode/skills/") || lower.ends_with("/.opencode/skills") {
+    if lower.contains("/.opencode/skills/")
+        || lower.ends_with("/.opencode/skills")
+        || lower.contains("/.opencode/skills-disabled")
+    {
         return Some("skills");
     }
     if lower.contains("/.opencode/") || lower.ends_with("/.opencode") {
@@ -50,10 +93,22 @@ fn should_emit(last_emit: &Arc<Mutex<Option<Instant>>>) -> bool {
     true
 }
 
+/// Re-arms the watcher for `workspace`. Failures are also kept as `last_error` so
+/// `workspace_watch_status` can explain why reload events stopped.
 pub fn update_workspace_watch(
     app: &AppHandle,
     state: State<WorkspaceWatchState>,
     workspace: Option<&WorkspaceInfo>,
+) -> Result<(), String> {
+    let result = install_workspace_watch(app, &state, workspace);
+    set_last_error(&state.last_error, result.as_ref().err().cloned());
+    result
+}
+
+fn install_workspace_watch(
+    app: &AppHandle,
+    state: &WorkspaceWatchState,
+    workspace: Option<&WorkspaceInfo>,
 ) -> Result<(), String> {
     let mut watcher_guard = state
         .watcher
@@ -64,6 +119,11 @@ pub fn update_workspace_watch(
         .root
         .lock()
         .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    state
+        .watched
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
 
     let Some(active) = workspace else {
         return Ok(());
@@ -79,10 +139,17 @@ pub fn update_workspace_watch(
 
     let app_handle = app.clone();
     let last_emit = state.last_emit.clone();
+    let last_error = state.last_error.clone();
     let mut watcher = notify::recommended_watcher(move |result| {
         let event: Event = match result {
             Ok(event) => event,
-            Err(_) => return,
+            Err(error) => {
+                set_last_error(
+                    &last_error,
+                    Some(format!("Workspace watcher error: {error}")),
+                );
+                return;
+            }
         };
 
         match event.kind {
@@ -129,18 +196,24 @@ pub fn update_workspace_watch(
     watcher
         .watch(&root, RecursiveMode::NonRecursive)
         .map_err(|e| format!("Failed to watch workspace root: {e}"))?;
+    let mut watched = vec![root.clone()];
 
     let opencode_dir = root.join(".opencode");
     if opencode_dir.exists() {
         watcher
             .watch(&opencode_dir, RecursiveMode::Recursive)
             .map_err(|e| format!("Failed to watch .opencode: {e}"))?;
+        watched.push(opencode_dir);
     }
 
     *state
         .root
         .lock()
         .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(root);
+    *state
+        .watched
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = watched;
     *watcher_guard = Some(watcher);
     Ok(())
 }
@@ -1,8 +1,12 @@
+interface BunServer {
+  requestIP: (request: Request) => { address: string } | null;
+}
+
 declare const Bun: {
   serve: (options: {
     hostname: string;
     port: number;
-    fetch: (request: Request) => Response | Promise<Response>;
+    fetch: (request: Request, server: BunServer) => Response | Promise<Response>;
   }) => {
     port: number;
   };
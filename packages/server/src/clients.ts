@@ -0,0 +1,30 @@
+import type { ConnectedClient } from "./types.js";
+
+// Clients are seen per request rather than per socket, so one that stays quiet this long
+// is treated as disconnected.
+const CLIENT_IDLE_MS = 5 * 60 * 1000;
+
+export class ClientTracker {
+  private clients = new Map<string, ConnectedClient>();
+
+  touch(clientId: string | undefined, address: string | undefined) {
+    const key = clientId ?? address;
+    if (!key) return;
+    const now = Date.now();
+    const existing = this.clients.get(key);
+    if (existing && existing.lastSeenAt >= now - CLIENT_IDLE_MS) {
+      existing.lastSeenAt = now;
+      existing.address = address ?? existing.address;
+      return;
+    }
+    this.clients.set(key, { clientId, address, connectedAt: now, lastSeenAt: now });
+  }
+
+  list(): ConnectedClient[] {
+    const cutoff = Date.now() - CLIENT_IDLE_MS;
+    for (const [key, client] of this.clients) {
+      if (client.lastSeenAt < cutoff) this.clients.delete(key);
+    }
+    return [...this.clients.values()].sort((a, b) => a.connectedAt - b.connectedAt);
+  }
+}
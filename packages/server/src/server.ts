@@ -23,6 +23,7 @@ import { ApiError, formatError } from "./errors.js";
 import { readJsoncFile, updateJsoncTopLevel, writeJsoncFile } from "./jsonc.js";
 import { recordAudit, readAuditEntries, readLastAudit } from "./audit.js";
 import { ReloadEventStore } from "./events.js";
+import { ClientTracker } from "./clients.js";
 import { parseFrontmatter } from "./frontmatter.js";
 import { opencodeConfigPath, openworkConfigPath, projectCommandsDir, projectSkillsDir } from "./workspace-files.js";
 import { ensureDir, exists, hashToken, shortId } from "./utils.js";
@@ -188,23 +189,25 @@ interface RequestContext {
   config: ServerConfig;
   approvals: ApprovalService;
   reloadEvents: ReloadEventStore;
+  clients: ClientTracker;
   actor?: Actor;
 }
 
 export function startServer(config: ServerConfig) {
   const approvals = new ApprovalService(config.approval);
   const reloadEvents = new ReloadEventStore();
+  const clients = new ClientTracker();
   const routes = createRoutes(config, approvals);
   const logger = createServerLogger(config);
 
   const serverOptions: {
     hostname: string;
     port: number;
-    fetch: (request: Request) => Response | Promise<Response>;
+    fetch: (request: Request, server: BunServer) => Response | Promise<Response>;
   } = {
     hostname: config.host,
     port: config.port,
-    fetch: async (request: Request) => {
+    fetch: async (request: Request, server: BunServer) => {
       const url = new URL(request.url);
       const startedAt = Date.now();
       let authMode: AuthMode = "none";
@@ -235,7 +238,8 @@ export function startServer(config: ServerConfig) {
         authMode = "client";
         proxyBaseUrl = config.workspaces[0]?.baseUrl?.trim() || undefined;
         try {
-          requireClient(request, config);
+          const actor = requireClient(request, config);
+          clients.touch(actor.clientId, server.requestIP(request)?.address);
           const response = await proxyOpencodeRequest({ request, url, config });
           return finalize(response);
         } catch (error) {
@@ -256,6 +260,9 @@ export function startServer(config: ServerConfig) {
       authMode = route.auth;
       try {
         const actor = route.auth === "host" ? requireHost(request, config) : route.auth === "client" ? requireClient(request, config) : undefined;
+        if (actor?.type === "remote") {
+          clients.touch(actor.clientId, server.requestIP(request)?.address);
+        }
         const response = await route.handler({
           request,
           url,
@@ -263,6 +270,7 @@ export function startServer(config: ServerConfig) {
           config,
           approvals,
           reloadEvents,
+          clients,
           actor,
         });
         return finalize(response);
@@ -1141,6 +1149,10 @@ function createRoutes(config: ServerConfig, approvals: ApprovalService): Route[]
     return jsonResponse({ ok: true });
   });
 
+  addRoute(routes, "GET", "/clients", "host", async (ctx) => {
+    return jsonResponse({ clients: ctx.clients.list() });
+  });
+
   addRoute(routes, "GET", "/approvals", "host", async (ctx) => {
     return jsonResponse({ items: ctx.approvals.list() });
   });
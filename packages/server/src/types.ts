@@ -119,6 +119,13 @@ export interface CommandItem {
   scope: "workspace" | "global";
 }
 
+export interface ConnectedClient {
+  clientId?: string;
+  address?: string;
+  connectedAt: number;
+  lastSeenAt: number;
+}
+
 export interface Actor {
   type: "remote" | "host";
   clientId?: string;